@@ -1,343 +1,260 @@
-use cosmwasm_std::{
-    to_json_binary, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Order, QueryRequest,
-    Response, WasmMsg, WasmQuery,
-};
-
-use cw20::Cw20ExecuteMsg;
-use cw721::OwnerOfResponse;
+use cosmwasm_std::{Addr, BankMsg, Coin, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use serde::{Deserialize, Serialize};
 
-use crate::utils::{
-    check_sent_required_payment, fee_split, handle_swap_transfers, query_name_owner,
+use cw721_marketplace_utils::events::{
+    allowlist_event, listing_request_event, ListingRequestAction,
 };
-use cw721_marketplace_utils::{prelude::CW721Swap, prelude::SwapType, FeeSplit};
-
-use crate::error::ContractError;
-use crate::msg::{CancelMsg, FinishSwapMsg, SwapMsg, UpdateMsg, UpdateNftMsg, WithdrawMsg};
-use crate::state::{cw721_allowed, Config, CONFIG, CW721, SWAPS};
-
-pub fn execute_create(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    msg: SwapMsg,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    // If expired, revert
-    if msg.expires.is_expired(&env.block) {
-        return Err(ContractError::Expired {});
-    }
-    // If no cw721 permission, revert
-    if !cw721_allowed(deps.storage, &msg.cw721) {
-        return Err(ContractError::Unauthorized {});
-    }
+use cw721_marketplace_utils::payment::check_sent_required_payment_exact;
+use cw721_marketplace_utils::prelude::Role;
+use cw721_marketplace_utils::state::has_role;
 
-    let has_payment_token = msg.payment_token.is_some();
-    // SwapType::Sale
-    if msg.swap_type == SwapType::Sale {
-        let owner = query_name_owner(&msg.token_id, &msg.cw721, &deps).unwrap();
-        if owner.owner != info.sender {
-            return Err(ContractError::Unauthorized {});
-        }
-    // SwapType::Offer
-    } else if msg.swap_type == SwapType::Offer && !has_payment_token {
-        return Err(ContractError::InvalidPaymentToken {});
-    }
-    let swap = CW721Swap {
-        id: msg.id.clone(),
-        creator: info.sender,
-        nft_contract: msg.cw721,
-        payment_token: msg.payment_token,
-        token_id: msg.token_id,
-        expires: msg.expires,
-        price: msg.price,
-        swap_type: msg.swap_type,
-    };
-
-    // Try to store it, fail if the id already exists (unmodifiable swaps)
-    SWAPS.update(deps.storage, &msg.id, |existing| match existing {
-        None => Ok(swap.clone()),
-        Some(_) => Err(ContractError::AlreadyExists {}),
-    })?;
+use crate::msg::{RequestListingMsg, UpdateNftMsg};
+use crate::state::{
+    cw721_allowed, Config, ListingRequest, ALLOWED_CW721, LISTING_FEE, LISTING_REQUESTS,
+};
+use crate::ContractError;
 
-    let payment_token: String = if has_payment_token {
-        swap.payment_token.unwrap().to_string()
-    } else {
-        config.denom
-    };
+// Max length of collection listing request metadata
+pub const MAX_LISTING_METADATA: usize = 1024;
 
-    Ok(Response::new()
-        .add_attribute("action", "create")
-        .add_attribute("swap_id", msg.id)
-        .add_attribute("token_id", swap.token_id)
-        .add_attribute("payment_token", payment_token)
-        .add_attribute("price", swap.price))
+// Minter query of cw721-base collections
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum MinterQuery {
+    Minter {},
 }
 
-pub fn execute_update(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    msg: UpdateMsg,
-) -> Result<Response, ContractError> {
-    let swap = SWAPS.load(deps.storage, &msg.id)?;
+#[derive(Deserialize)]
+struct MinterResponse {
+    minter: String,
+}
 
-    // Only creator can update swap
-    if info.sender != swap.creator {
-        return Err(ContractError::Unauthorized {});
+/// Whether `addr` is the minter or the contract admin of `cw721`
+fn controls_collection(deps: Deps, cw721: &Addr, addr: &Addr) -> bool {
+    let minter: Option<MinterResponse> = deps
+        .querier
+        .query_wasm_smart(cw721, &MinterQuery::Minter {})
+        .ok();
+    if minter.is_some_and(|res| res.minter == addr.as_str()) {
+        return true;
     }
-
-    // For security reasons, creator, nft_contract, token_id,
-    // payment_token and swap_type should not be updatable
-    // E.g. only price and expiration can be modified
-    let swap = CW721Swap {
-        id: swap.id,
-        creator: swap.creator,
-        nft_contract: swap.nft_contract,
-        payment_token: swap.payment_token,
-        token_id: swap.token_id,
-        expires: msg.expires,
-        price: msg.price,
-        swap_type: swap.swap_type,
-    };
-    // Remove legacy swap and save updated swap
-    SWAPS.remove(deps.storage, &msg.id);
-    SWAPS.save(deps.storage, &msg.id, &swap)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "update")
-        .add_attribute("swap_id", &msg.id)
-        .add_attribute("token_id", &swap.token_id))
+    deps.querier
+        .query_wasm_contract_info(cw721)
+        .is_ok_and(|info| info.admin.as_deref() == Some(addr.as_str()))
 }
 
-pub fn execute_finish(
+pub fn execute_add_cw721(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
-    msg: FinishSwapMsg,
+    config: &Config,
+    msg: UpdateNftMsg,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let swap = SWAPS.load(deps.storage, &msg.id)?;
-    // If expired, revert
-    if swap.expires.is_expired(&env.block) {
-        return Err(ContractError::Expired {});
-    }
-    // If no cw721 permission, revert
-    if !cw721_allowed(deps.storage, &swap.nft_contract) {
+    if !has_role(deps.storage, config, &info.sender, Role::Curator) {
         return Err(ContractError::Unauthorized {});
     }
-
-    // If swapping for native `aarch`
-    // check payment conditions satisfied
-    if swap.payment_token.is_none() {
-        let required_payment = Coin {
-            denom: config.denom.clone(),
-            amount: swap.price,
-        };
-        check_sent_required_payment(&info.funds, Some(required_payment))?;
-
-        // Native aarch offers not allowed
-        if swap.swap_type == SwapType::Offer {
-            return Err(ContractError::InvalidInput {});
-        }
+    if cw721_allowed(deps.storage, &msg.cw721) {
+        return Err(ContractError::InvalidInput {});
     }
 
-    // Calculate fee split
-    let split = if swap.payment_token.is_none() {
-        let funds: Vec<Coin> = info
-            .funds
-            .into_iter()
-            .filter(|coin| coin.denom == config.denom)
-            .collect();
-
-        fee_split(&deps, funds[0].amount).unwrap_or(FeeSplit::only_seller(funds[0].amount))
-    } else {
-        fee_split(&deps, swap.price).unwrap_or(FeeSplit::only_seller(swap.price))
-    };
-
-    // Do swap transfer
-    let transfer_results = match swap.swap_type {
-        SwapType::Offer => {
-            let owner_of: OwnerOfResponse =
-                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                    contract_addr: swap.nft_contract.to_string(),
-                    msg: to_json_binary(&cw721::Cw721QueryMsg::OwnerOf {
-                        token_id: swap.token_id.clone(),
-                        include_expired: None,
-                    })?,
-                }))?;
-
-            if owner_of.owner != info.sender {
-                return Err(ContractError::Unauthorized {});
-            }
-
-            handle_swap_transfers(
-                env,
-                &info.sender,
-                &swap.creator,
-                swap.clone(),
-                config.denom.clone(),
-                split,
-            )?
-        }
-        SwapType::Sale => handle_swap_transfers(
-            env,
-            &swap.creator,
-            &info.sender,
-            swap.clone(),
-            config.denom.clone(),
-            split,
-        )?,
-    };
-
-    // Remove all swaps for this token_id
-    // (as they're no longer valid)
-    let swap_data = swap.clone();
-    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
-        .range(deps.storage, None, None, Order::Ascending)
-        .collect();
-    for swap in swaps.unwrap().iter() {
-        if swap.1.token_id == swap_data.token_id && swap.1.nft_contract == swap_data.nft_contract {
-            SWAPS.remove(deps.storage, &swap.0);
+    ALLOWED_CW721.save(deps.storage, msg.cw721.as_str(), &())?;
+    let event = allowlist_event(deps.storage, &msg.cw721, true)?;
+    let mut res = Response::new()
+        .add_event(event)
+        .add_attribute("action", "add_cw721")
+        .add_attribute("cw721", &msg.cw721);
+
+    // A pending listing request is no longer needed, its application fee is refunded
+    if let Some(request) = LISTING_REQUESTS.may_load(deps.storage, &msg.cw721)? {
+        LISTING_REQUESTS.remove(deps.storage, &msg.cw721);
+        let event = listing_request_event(
+            deps.storage,
+            ListingRequestAction::Reject,
+            &request.cw721,
+            &request.requester,
+            &request.fee,
+        )?;
+        res = res.add_event(event);
+        if !request.fee.amount.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: request.requester.to_string(),
+                amount: vec![request.fee],
+            });
         }
     }
 
-    let payment_token: String = if swap.payment_token.is_some() {
-        swap.payment_token.unwrap().to_string()
-    } else {
-        config.denom
-    };
-
-    Ok(Response::new()
-        .add_attribute("action", "finish")
-        .add_attribute("swap_id", swap.id)
-        .add_attribute("token_id", swap.token_id)
-        .add_attribute("payment_token", payment_token)
-        .add_attribute("price", swap.price)
-        .add_messages(transfer_results))
+    Ok(res)
 }
 
-pub fn execute_cancel(
+pub fn execute_remove_cw721(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    msg: CancelMsg,
+    config: &Config,
+    msg: UpdateNftMsg,
 ) -> Result<Response, ContractError> {
-    let swap = SWAPS.load(deps.storage, &msg.id)?;
-    if info.sender != swap.creator {
+    if !has_role(deps.storage, config, &info.sender, Role::Curator) {
         return Err(ContractError::Unauthorized {});
     }
+    if !cw721_allowed(deps.storage, &msg.cw721) {
+        return Err(ContractError::InvalidInput {});
+    }
 
-    SWAPS.remove(deps.storage, &msg.id);
+    ALLOWED_CW721.remove(deps.storage, msg.cw721.as_str());
+    let event = allowlist_event(deps.storage, &msg.cw721, false)?;
 
     Ok(Response::new()
-        .add_attribute("action", "cancel")
-        .add_attribute("swap_id", msg.id))
+        .add_event(event)
+        .add_attribute("action", "remove_cw721")
+        .add_attribute("cw721", msg.cw721))
 }
 
-pub fn execute_update_config(
+pub fn execute_request_listing(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    config_update: Config,
+    config: &Config,
+    msg: RequestListingMsg,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+    let fee = Coin {
+        denom: config.denom.clone(),
+        amount: LISTING_FEE.may_load(deps.storage)?.unwrap_or_default(),
+    };
 
-    if config.admin != info.sender {
+    let cw721 = deps.api.addr_validate(msg.cw721.as_str())?;
+    if cw721_allowed(deps.storage, &cw721) || msg.metadata.len() > MAX_LISTING_METADATA {
+        return Err(ContractError::InvalidInput {});
+    }
+    // Only the collection creator can apply for listing
+    if !controls_collection(deps.as_ref(), &cw721, &info.sender) {
         return Err(ContractError::Unauthorized {});
     }
+    if LISTING_REQUESTS.has(deps.storage, &cw721) {
+        return Err(ContractError::ListingRequested {});
+    }
 
-    CONFIG.save(deps.storage, &config_update)?;
+    // Application fee is held in escrow until the request is curated
+    if fee.amount.is_zero() {
+        if !info.funds.is_empty() {
+            return Err(ContractError::ExactFunds {});
+        }
+    } else {
+        if info.funds.len() != 1 {
+            return Err(ContractError::ExactFunds {});
+        }
+        check_sent_required_payment_exact(&info.funds, Some(fee.clone()))?;
+    }
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+    let request = ListingRequest {
+        cw721: cw721.clone(),
+        requester: info.sender,
+        metadata: msg.metadata,
+        fee: fee.clone(),
+    };
+    LISTING_REQUESTS.save(deps.storage, &cw721, &request)?;
+    let event = listing_request_event(
+        deps.storage,
+        ListingRequestAction::Request,
+        &cw721,
+        &request.requester,
+        &fee,
+    )?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "request_listing")
+        .add_attribute("cw721", cw721)
+        .add_attribute("requester", request.requester)
+        .add_attribute("fee", fee.amount))
 }
 
-pub fn execute_add_cw721(
+pub fn execute_approve_listing(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
+    config: &Config,
     msg: UpdateNftMsg,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    if config.admin != info.sender {
+    if !has_role(deps.storage, config, &info.sender, Role::Curator) {
         return Err(ContractError::Unauthorized {});
     }
-    if cw721_allowed(deps.storage, &msg.cw721) {
+    let request = LISTING_REQUESTS.load(deps.storage, &msg.cw721)?;
+    // Already allowed collections are added once, their requests can be rejected
+    if cw721_allowed(deps.storage, &request.cw721) {
         return Err(ContractError::InvalidInput {});
     }
 
-    CW721.save(deps.storage, msg.cw721.as_str(), &())?;
+    // Application fee is kept as marketplace revenue
+    LISTING_REQUESTS.remove(deps.storage, &msg.cw721);
+    ALLOWED_CW721.save(deps.storage, request.cw721.as_str(), &())?;
+    let approve_event = listing_request_event(
+        deps.storage,
+        ListingRequestAction::Approve,
+        &request.cw721,
+        &request.requester,
+        &request.fee,
+    )?;
+    let event = allowlist_event(deps.storage, &request.cw721, true)?;
 
     Ok(Response::new()
-        .add_attribute("action", "add_cw721")
-        .add_attribute("cw721", msg.cw721))
+        .add_event(approve_event)
+        .add_event(event)
+        .add_attribute("action", "approve_listing")
+        .add_attribute("cw721", request.cw721)
+        .add_attribute("requester", request.requester))
 }
 
-pub fn execute_remove_cw721(
+pub fn execute_reject_listing(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
+    config: &Config,
     msg: UpdateNftMsg,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    if config.admin != info.sender {
+    if !has_role(deps.storage, config, &info.sender, Role::Curator) {
         return Err(ContractError::Unauthorized {});
     }
-    if !cw721_allowed(deps.storage, &msg.cw721) {
-        return Err(ContractError::InvalidInput {});
+    let request = LISTING_REQUESTS.load(deps.storage, &msg.cw721)?;
+
+    LISTING_REQUESTS.remove(deps.storage, &msg.cw721);
+    let event = listing_request_event(
+        deps.storage,
+        ListingRequestAction::Reject,
+        &request.cw721,
+        &request.requester,
+        &request.fee,
+    )?;
+
+    // Refund the application fee
+    let mut res = Response::new()
+        .add_event(event)
+        .add_attribute("action", "reject_listing")
+        .add_attribute("cw721", request.cw721)
+        .add_attribute("requester", &request.requester);
+    if !request.fee.amount.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: request.requester.to_string(),
+            amount: vec![request.fee],
+        });
     }
 
-    CW721.remove(deps.storage, msg.cw721.as_str());
-
-    Ok(Response::new()
-        .add_attribute("action", "remove_cw721")
-        .add_attribute("cw721", msg.cw721))
+    Ok(res)
 }
 
-pub fn execute_withdraw_fees(
+pub fn execute_update_listing_fee(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    msg: WithdrawMsg,
+    config: &Config,
+    amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let denom = msg.denom;
-    let amount = msg.amount;
-
-    if config.admin != info.sender {
+    if !has_role(deps.storage, config, &info.sender, Role::FeeManager) {
         return Err(ContractError::Unauthorized {});
     }
 
-    let transfer_result = if msg.payment_token.is_none() {
-        let bank_transfer_msg = BankMsg::Send {
-            to_address: info.sender.into(),
-            amount: ([Coin {
-                denom: denom.clone(),
-                amount,
-            }])
-            .to_vec(),
-        };
-
-        let bank_transfer: CosmosMsg = cosmwasm_std::CosmosMsg::Bank(bank_transfer_msg);
-        bank_transfer
-    } else {
-        let cw20_transfer_msg = Cw20ExecuteMsg::Transfer {
-            recipient: info.sender.into(),
-            amount,
-        };
-
-        let cw20_transfer: CosmosMsg = cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: msg.payment_token.unwrap().into(),
-            msg: to_json_binary(&cw20_transfer_msg)?,
-            funds: vec![],
-        });
-        cw20_transfer
-    };
+    LISTING_FEE.save(deps.storage, &amount)?;
 
     Ok(Response::new()
-        .add_attribute("action", "withdraw")
-        .add_attribute("amount", amount.to_string())
-        .add_attribute("denom", denom)
-        .add_message(transfer_result))
+        .add_attribute("action", "update_listing_fee")
+        .add_attribute("amount", amount))
 }
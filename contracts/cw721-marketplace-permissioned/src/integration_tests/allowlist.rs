@@ -0,0 +1,97 @@
+#![cfg(test)]
+use cosmwasm_std::Addr;
+use cw_multi_test::{AppResponse, Executor};
+
+use cw721_marketplace_utils::prelude::Role;
+
+use crate::integration_tests::util::{create_swap, mock_app};
+use crate::msg::{ExecuteMsg, UpdateNftMsg};
+
+// Allowlist event of `res`, with its `old_allowed` and `allowed` attributes
+fn allowlist_event(res: &AppResponse) -> (String, String, String) {
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty.ends_with("-nft"))
+        .unwrap();
+    let value = |key: &str| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+            .unwrap()
+    };
+    (event.ty.clone(), value("old_allowed"), value("allowed"))
+}
+
+// Curators can add and remove allowed collections
+#[test]
+fn test_curator_role() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let curator = Addr::unchecked("curator");
+
+    let swap = create_swap(&mut app, &swap_admin, Addr::unchecked("nft"));
+
+    let add_msg = ExecuteMsg::AddNft(UpdateNftMsg {
+        cw721: Addr::unchecked("other_nft"),
+    });
+    let err = app
+        .execute_contract(curator.clone(), swap.clone(), &add_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(
+        swap_admin,
+        swap.clone(),
+        &ExecuteMsg::GrantRole {
+            role: Role::Curator,
+            address: curator.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res = app
+        .execute_contract(curator.clone(), swap.clone(), &add_msg, &[])
+        .unwrap();
+    assert_eq!(
+        allowlist_event(&res),
+        (
+            "wasm-marketplace-add-nft".to_string(),
+            "false".to_string(),
+            "true".to_string()
+        )
+    );
+    let res = app
+        .execute_contract(
+            curator.clone(),
+            swap.clone(),
+            &ExecuteMsg::RemoveNft(UpdateNftMsg {
+                cw721: Addr::unchecked("other_nft"),
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        allowlist_event(&res),
+        (
+            "wasm-marketplace-remove-nft".to_string(),
+            "true".to_string(),
+            "false".to_string()
+        )
+    );
+
+    // Curators can't manage fees
+    let err = app
+        .execute_contract(
+            curator,
+            swap,
+            &ExecuteMsg::UpdateFeeRecipients { recipients: vec![] },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+}
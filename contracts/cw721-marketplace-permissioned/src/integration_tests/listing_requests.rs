@@ -0,0 +1,293 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::{AppResponse, BankSudo, Executor, SudoMsg};
+
+use crate::integration_tests::util::{
+    bank_query, create_cw721, create_swap, mint_native, mock_app, query,
+};
+use crate::msg::{ExecuteMsg, QueryMsg, RequestListingMsg, UpdateNftMsg, WithdrawMsg};
+use crate::state::{Config, ListingRequest};
+
+static DENOM: &str = "aarch";
+
+// Attribute values of the `wasm-marketplace-<action>` listing request event of `res`
+fn listing_event(res: &AppResponse, action: &str) -> Vec<String> {
+    let ty = format!("wasm-marketplace-{action}");
+    let event = res.events.iter().find(|event| event.ty == ty).unwrap();
+    [
+        "collection",
+        "requester",
+        "fee",
+        "old_requested",
+        "requested",
+    ]
+    .iter()
+    .map(|key| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == *key)
+            .map(|attr| attr.value.clone())
+            .unwrap_or_default()
+    })
+    .collect()
+}
+
+// Collection creators can apply for listing, curators approve or reject
+#[test]
+fn test_listing_requests() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let creator = Addr::unchecked("creator");
+
+    let listed = create_cw721(&mut app, &swap_admin);
+    let approved = create_cw721(&mut app, &creator);
+    let rejected = create_cw721(&mut app, &creator);
+    let swap = create_swap(&mut app, &swap_admin, listed.clone());
+    mint_native(&mut app, creator.to_string(), Uint128::from(200_u128));
+
+    // Only admin or fee managers can set the application fee
+    let fee_msg = ExecuteMsg::UpdateListingFee {
+        amount: Uint128::from(100_u128),
+    };
+    let err = app
+        .execute_contract(creator.clone(), swap.clone(), &fee_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    app.execute_contract(swap_admin.clone(), swap.clone(), &fee_msg, &[])
+        .unwrap();
+    let fee: Uint128 = query(&mut app, swap.clone(), QueryMsg::ListingFee {}).unwrap();
+    assert_eq!(fee, Uint128::from(100_u128));
+
+    let fee_funds = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(100_u128),
+    }];
+    let request_msg = |cw721: &Addr| {
+        ExecuteMsg::RequestListing(RequestListingMsg {
+            cw721: cw721.clone(),
+            metadata: "Collection of petrified art".to_string(),
+        })
+    };
+
+    // Application fee must be paid
+    let err = app
+        .execute_contract(creator.clone(), swap.clone(), &request_msg(&approved), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Must send exactly the required funds"
+    );
+
+    // Already listed collections can't be requested
+    let err = app
+        .execute_contract(
+            creator.clone(),
+            swap.clone(),
+            &request_msg(&listed),
+            &fee_funds,
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    // Only the collection minter or admin can request its listing
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &request_msg(&approved),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    for cw721 in [&approved, &rejected] {
+        let res = app
+            .execute_contract(
+                creator.clone(),
+                swap.clone(),
+                &request_msg(cw721),
+                &fee_funds,
+            )
+            .unwrap();
+        assert_eq!(
+            listing_event(&res, "request-listing"),
+            vec![
+                cw721.to_string(),
+                creator.to_string(),
+                "100aarch".to_string(),
+                "false".to_string(),
+                "true".to_string(),
+            ]
+        );
+    }
+    let requests: Vec<ListingRequest> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::ListingRequests {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].requester, creator);
+    assert_eq!(requests[0].fee, fee_funds[0]);
+
+    // Pending application fees can't be withdrawn
+    let withdraw_msg = ExecuteMsg::Withdraw(WithdrawMsg {
+        amount: Uint128::from(100_u128),
+        denom: String::from(DENOM),
+        payment_token: None,
+    });
+    let err = app
+        .execute_contract(swap_admin.clone(), swap.clone(), &withdraw_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Insufficient contract balance"
+    );
+
+    // Only admin or curators can approve
+    let approve_msg = ExecuteMsg::ApproveListing(UpdateNftMsg {
+        cw721: approved.clone(),
+    });
+    let err = app
+        .execute_contract(creator.clone(), swap.clone(), &approve_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    let res = app
+        .execute_contract(swap_admin.clone(), swap.clone(), &approve_msg, &[])
+        .unwrap();
+    assert_eq!(
+        listing_event(&res, "approve-listing"),
+        vec![
+            approved.to_string(),
+            creator.to_string(),
+            "100aarch".to_string(),
+            "true".to_string(),
+            "false".to_string(),
+        ]
+    );
+
+    // Rejected application fee is refunded in the denom it was paid in
+    let mut config: Config = query(&mut app, swap.clone(), QueryMsg::Config {}).unwrap();
+    config.denom = "uatom".to_string();
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::UpdateConfig { config },
+        &[],
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &ExecuteMsg::RejectListing(UpdateNftMsg {
+                cw721: rejected.clone(),
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        listing_event(&res, "reject-listing"),
+        vec![
+            rejected.to_string(),
+            creator.to_string(),
+            "100aarch".to_string(),
+            "true".to_string(),
+            "false".to_string(),
+        ]
+    );
+    assert_eq!(bank_query(&app, &creator).amount, Uint128::from(100_u128));
+    assert_eq!(bank_query(&app, &swap).amount, Uint128::from(100_u128));
+
+    let requests: Vec<ListingRequest> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::ListingRequests {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(requests.is_empty());
+
+    // Approved application fee is marketplace revenue
+    app.execute_contract(swap_admin.clone(), swap.clone(), &withdraw_msg, &[])
+        .unwrap();
+    assert_eq!(
+        bank_query(&app, &swap_admin).amount,
+        Uint128::from(100_u128)
+    );
+
+    // Adding a requested collection refunds its application fee
+    let added = create_cw721(&mut app, &creator);
+    let uatom_fee = [Coin {
+        denom: "uatom".to_string(),
+        amount: Uint128::from(100_u128),
+    }];
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: creator.to_string(),
+        amount: uatom_fee.to_vec(),
+    }))
+    .unwrap();
+    app.execute_contract(
+        creator.clone(),
+        swap.clone(),
+        &request_msg(&added),
+        &uatom_fee,
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &ExecuteMsg::AddNft(UpdateNftMsg {
+                cw721: added.clone(),
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        listing_event(&res, "reject-listing"),
+        vec![
+            added.to_string(),
+            creator.to_string(),
+            "100uatom".to_string(),
+            "true".to_string(),
+            "false".to_string(),
+        ]
+    );
+    assert_eq!(
+        app.wrap().query_balance(&creator, "uatom").unwrap(),
+        uatom_fee[0]
+    );
+
+    // Its request can't be approved anymore
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::ApproveListing(UpdateNftMsg {
+            cw721: added.clone(),
+        }),
+        &[],
+    )
+    .unwrap_err();
+
+    let collections: Vec<Addr> = query(
+        &mut app,
+        swap,
+        QueryMsg::AllowedCollections {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert!(collections.contains(&listed));
+    assert!(collections.contains(&approved));
+    assert!(!collections.contains(&rejected));
+    assert!(collections.contains(&added));
+}
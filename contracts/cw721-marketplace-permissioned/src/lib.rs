@@ -1,9 +1,9 @@
 pub mod contract;
-mod error;
 pub mod execute;
 mod integration_tests;
 pub mod msg;
+#[cfg(any(test, feature = "testing"))]
+pub mod multitest;
 pub mod query;
 pub mod state;
-pub mod utils;
-pub use crate::error::ContractError;
+pub use cw721_marketplace_utils::ContractError;
@@ -1,10 +1,22 @@
-use crate::state::Config;
-use cosmwasm_std::{Addr, Uint128};
-use cw20::Expiration;
-use cw721_marketplace_utils::prelude::SwapType;
+use crate::state::{Config, ListingRequest, SwapIdRules};
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw721_marketplace_utils::hooks::HookSubject;
+use cw721_marketplace_utils::prelude::{
+    DetailsResponse, FeeRecipient, ListResponse, NegotiationsResponse, PageResult, Rental, Role,
+    SimulateFinishResponse, SwapType, Trait,
+};
+use cw721_marketplace_utils::state::ExpirationRules;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub use cw721_marketplace_utils::msg::{
+    BatchCreateResponse, BlockedToken, BlocklistMsg, CancelMsg, CounterMsg, CreateResponse,
+    DistributeFeesMsg, FillTraitOfferMsg, FinishSwapMsg, HookMsg, MigrateMsg, PauseScope,
+    PausedResponse, RentMsg, RentalMsg, SwapMsg, SweepMsg, SweepResponse, SweptSwap, TraitOfferMsg,
+    UpdateMsg, UpdatePaymentTokenMsg, UserResponse, WithdrawMsg,
+};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: Addr,
@@ -21,76 +33,123 @@ pub enum ExecuteMsg {
     Finish(FinishSwapMsg),
     Cancel(CancelMsg),
     Update(UpdateMsg),
+    UpdatePaymentToken(UpdatePaymentTokenMsg),
+    BatchCreate(Vec<SwapMsg>),
+    BatchFinish(Vec<String>),
+    BatchCancel(Vec<String>),
+    BatchUpdate(Vec<UpdateMsg>),
+    Sweep(SweepMsg),
+    Counter(CounterMsg),
+    DeclineCounter {
+        id: String,
+    },
+    ListRental(RentalMsg),
+    Rent(RentMsg),
+    CreateTraitOffer(TraitOfferMsg),
+    FillTraitOffer(FillTraitOfferMsg),
+
+    // Hook entry points
+    AddHook(HookMsg),
+    RemoveHook(HookMsg),
+
+    // Permissionless entry points
+    DistributeFees(DistributeFeesMsg),
+    /// Delists swaps of blocked addresses and tokens, looking
+    /// at `limit` swaps after `start_after` in id order
+    DelistBlocked {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    RequestListing(RequestListingMsg),
 
     // Admin entry points
-    UpdateConfig { config: Config },
+    UpdateConfig {
+        config: Config,
+    },
     AddNft(UpdateNftMsg),
     RemoveNft(UpdateNftMsg),
+    ApproveListing(UpdateNftMsg),
+    RejectListing(UpdateNftMsg),
+    UpdateListingFee {
+        amount: Uint128,
+    },
     Withdraw(WithdrawMsg),
+    UpdateFeeRecipients {
+        recipients: Vec<FeeRecipient>,
+    },
+    ProposeAdmin {
+        admin: Addr,
+    },
+    AcceptAdmin {},
+    RenounceAdmin {},
+    GrantRole {
+        role: Role,
+        address: Addr,
+    },
+    RevokeRole {
+        role: Role,
+        address: Addr,
+    },
+    Pause {
+        scope: PauseScope,
+    },
+    Unpause {
+        scope: PauseScope,
+    },
+    Block(BlocklistMsg),
+    Unblock(BlocklistMsg),
+    UpdateSwapIdRules {
+        rules: SwapIdRules,
+    },
+    /// Sets the minimum price of swaps paid in `payment_token`, or in the
+    /// native denom if `None`; a zero `min_price` removes the minimum
+    UpdateMinPrice {
+        payment_token: Option<Addr>,
+        min_price: Uint128,
+    },
+    /// Sets the maximum durations of listings and offers, and the
+    /// duration given to swaps created without an expiration
+    UpdateExpirationRules {
+        rules: ExpirationRules,
+    },
 }
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub struct CancelMsg {
-    pub id: String,
-}
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct UpdateMsg {
-    pub id: String,
-    pub expires: Expiration,
-    pub price: Uint128,
-}
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct SwapMsg {
-    pub id: String,
-    pub cw721: Addr,
-    pub payment_token: Option<Addr>, // Optional cw20 address; if `None` create swap for `aarch`
-    pub token_id: String,
-    pub expires: Expiration,
-    pub price: Uint128,
-    pub swap_type: SwapType,
-}
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct FinishSwapMsg {
-    pub id: String,
-}
-
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UpdateNftMsg {
     pub cw721: Addr,
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct WithdrawMsg {
-    pub amount: Uint128,
-    pub denom: String,
-    pub payment_token: Option<Addr>,
+pub struct RequestListingMsg {
+    pub cw721: Addr,
+    pub metadata: String, // E.g. collection name, links or a json document
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    // TODO: link to actual structs
     /// Get all swaps (enumerable)
-    /// Return type: ListResponse
+    #[returns(ListResponse)]
     List {
         start_after: Option<String>,
         limit: Option<u32>,
     },
     // Count total `SwapType::Offer` or `SwapType::Sale`
-    GetTotal {
-        swap_type: Option<SwapType>,
-    },
+    #[returns(u128)]
+    GetTotal { swap_type: Option<SwapType> },
     /// Get all swaps of type `SwapType::Offer`
+    #[returns(PageResult)]
     GetOffers {
         page: Option<u32>,
         limit: Option<u32>,
     },
     /// Get all swaps of type `SwapType::Sale`
+    #[returns(PageResult)]
     GetListings {
         page: Option<u32>,
         limit: Option<u32>,
     },
     /// Get all listings for a token of type `Swap::Sale` and `Swap::Offer`
     /// or both (`None`)
+    #[returns(PageResult)]
     ListingsOfToken {
         token_id: String,
         cw721: Addr,
@@ -98,8 +157,29 @@ pub enum QueryMsg {
         page: Option<u32>,
         limit: Option<u32>,
     },
+    /// Get all offers for a token, each with the counter offers made to it
+    #[returns(NegotiationsResponse)]
+    NegotiationsOfToken {
+        token_id: String,
+        cw721: Addr,
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Query the renter of a token, if it's currently rented
+    #[returns(UserResponse)]
+    UserOf { token_id: String, cw721: Addr },
+    /// Query the terms and renter of a rental
+    #[returns(Rental)]
+    Rental { id: String },
+    /// Query the traits a token needs to fill a trait offer
+    #[returns(Vec<Trait>)]
+    Traits { id: String },
+    /// Get the listeners subscribed to a collection or address
+    #[returns(Vec<Addr>)]
+    Hooks { subject: HookSubject },
     /// Show all swaps created by a specific address
     /// Defaults to SwapType::Sale if no `swap_type`
+    #[returns(PageResult)]
     SwapsOf {
         address: Addr,
         swap_type: Option<SwapType>,
@@ -108,6 +188,7 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     /// Show all swaps of a given price range
+    #[returns(PageResult)]
     SwapsByPrice {
         min: Option<Uint128>,
         max: Option<Uint128>,
@@ -118,6 +199,7 @@ pub enum QueryMsg {
     },
     /// Show all swaps of a given denom (contract address)
     /// Defaults to ARCH if no contract is sent
+    #[returns(PageResult)]
     SwapsByDenom {
         payment_token: Option<Addr>,
         swap_type: Option<SwapType>,
@@ -126,6 +208,7 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     /// Show all cw20 swaps, or all ARCH swaps
+    #[returns(PageResult)]
     SwapsByPaymentType {
         cw20: bool,
         swap_type: Option<SwapType>,
@@ -135,15 +218,84 @@ pub enum QueryMsg {
     },
 
     /// Returns the details of the named swap, error if not created.
-    /// Return type: DetailsResponse.
-    Details {
+    #[returns(DetailsResponse)]
+    Details { id: String },
+
+    /// Runs the checks of finishing the named swap by `sender` with `funds`,
+    /// returning the error it would fail with, or the transfers it would make
+    #[returns(SimulateFinishResponse)]
+    SimulateFinish {
         id: String,
+        sender: Addr,
+        funds: Vec<Coin>,
     },
 
     /// Query Config (useful for determining parameters for ExecuteMsg::UpdateConfig)
+    #[returns(Config)]
     Config {},
-}
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub struct MigrateMsg {}
+    /// Query marketplace fee recipients and their weights
+    #[returns(Vec<FeeRecipient>)]
+    FeeRecipients {},
+
+    /// Query the address proposed as next admin, if any
+    #[returns(Option<Addr>)]
+    PendingAdmin {},
+
+    /// Get all addresses granted a role (enumerable)
+    #[returns(Vec<Addr>)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Query what trading is currently paused for
+    #[returns(PausedResponse)]
+    Paused {},
+
+    /// Get all blocked addresses (enumerable)
+    #[returns(Vec<Addr>)]
+    BlockedAddresses {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Get all collections allowed in the marketplace (enumerable)
+    #[returns(Vec<Addr>)]
+    AllowedCollections {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Get all pending collection listing requests (enumerable)
+    #[returns(Vec<ListingRequest>)]
+    ListingRequests {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Query the application fee for collection listing requests
+    #[returns(Uint128)]
+    ListingFee {},
+
+    /// Get all blocked tokens (enumerable)
+    #[returns(Vec<BlockedToken>)]
+    BlockedTokens {
+        start_after: Option<BlockedToken>,
+        limit: Option<u32>,
+    },
+
+    /// Query the rules client supplied swap ids must follow
+    #[returns(SwapIdRules)]
+    SwapIdRules {},
+
+    /// Query the bounds of swap expirations
+    #[returns(ExpirationRules)]
+    ExpirationRules {},
+
+    /// Query the minimum price of swaps paid in `payment_token`,
+    /// or in the native denom if `None`
+    #[returns(Uint128)]
+    MinPrice { payment_token: Option<Addr> },
+}
@@ -1,35 +1,35 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Order, StdResult, Storage};
-use cw_storage_plus::{Bound, Item, Map};
+use cosmwasm_std::{Addr, Coin, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
 
-use cw721_marketplace_utils::prelude::CW721Swap;
 pub use cw721_marketplace_utils::prelude::SwapType;
+pub use cw721_marketplace_utils::state::{
+    cw721_allowed, Config, IdCharset, PauseState, SwapIdRules, ALLOWED_CW721, BLOCKED_ADDRESSES,
+    BLOCKED_TOKENS, CONFIG, FEE_RECIPIENTS, PAUSE, PAUSED_CW721, PENDING_ADMIN, ROLES, SWAPS,
+    SWAP_COUNT, SWAP_ID_RULES,
+};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
-pub struct Config {
-    pub admin: Addr,
-    pub denom: String,
-    pub fees: u64,
+// Collection listing requested by its creator, awaiting curation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingRequest {
+    pub cw721: Addr,
+    pub requester: Addr,
+    pub metadata: String,
+    pub fee: Coin, // Escrowed application fee, refunded as is on rejection
 }
 
-pub fn all_swap_ids<'a>(
-    storage: &dyn Storage,
-    start: Option<Bound<'a, &'a str>>,
-    limit: usize,
-) -> StdResult<Vec<String>> {
-    SWAPS
-        .keys(storage, start, None, Order::Ascending)
-        .take(limit)
-        .collect()
+/// Application fees in `denom` held in escrow for pending listing requests
+pub fn escrowed_listing_fees(storage: &dyn Storage, denom: &str) -> StdResult<Uint128> {
+    LISTING_REQUESTS
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((_, request)) if request.fee.denom != denom => None,
+            item => Some(item.map(|(_, request)| request.fee.amount)),
+        })
+        .sum()
 }
 
-/// Wrapper for checking cw721 validity
-pub fn cw721_allowed(storage: &dyn Storage, addr: &Addr) -> bool {
-    CW721.has(storage, addr.as_str())
-}
-
-pub const CW721: Map<&str, ()> = Map::new("allowed_cw721");
-pub const SWAPS: Map<&str, CW721Swap> = Map::new("cw721_swap");
-pub const CONFIG: Item<Config> = Item::new("config");
+pub const LISTING_FEE: Item<Uint128> = Item::new("listing_fee");
+pub const LISTING_REQUESTS: Map<&Addr, ListingRequest> = Map::new("listing_requests");
@@ -0,0 +1,116 @@
+#![cfg(test)]
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+use cw721_marketplace_utils::prelude::ListResponse;
+
+use crate::integration_tests::util::{create_cw721, create_swap, mock_app, query};
+use crate::msg::{BlocklistMsg, ExecuteMsg, QueryMsg, SwapMsg};
+use crate::state::SwapType;
+
+// Mints `token_id` to `owner` and approves the swap contract to transfer it
+fn mint_and_approve(app: &mut App, swap: &Addr, nft: &Addr, owner: &Addr, token_id: &str) {
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: None,
+    });
+    app.execute_contract(owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+}
+
+fn sale_msg(token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(token_id.to_string()),
+        payment_token: None,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000_u128),
+        swap_type: SwapType::Sale,
+    })
+}
+
+// Blocked tokens can't be traded and their swaps can be delisted
+#[test]
+fn test_blocked_token() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "stolen");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "legit");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "other");
+    for token_id in ["stolen", "legit"] {
+        app.execute_contract(cw721_owner.clone(), swap.clone(), &sale_msg(token_id), &[])
+            .unwrap();
+    }
+
+    let stolen = "stolen".to_string();
+    app.execute_contract(
+        swap_admin,
+        swap.clone(),
+        &ExecuteMsg::Block(BlocklistMsg {
+            addresses: vec![],
+            tokens: vec![stolen.clone()],
+        }),
+        &[],
+    )
+    .unwrap();
+    let blocked: Vec<String> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::BlockedTokens {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(blocked, vec![stolen]);
+
+    // Anyone can delist swaps of blocked tokens
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::DelistBlocked {
+            start_after: None,
+            limit: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let swaps: ListResponse = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(swaps.swaps, vec!["legit".to_string()]);
+
+    // Blocked token can't be listed again
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &sale_msg("stolen"), &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Token is blocked");
+
+    // Other tokens are unaffected
+    app.execute_contract(cw721_owner, swap, &sale_msg("other"), &[])
+        .unwrap();
+}
@@ -1,10 +1,8 @@
+#![cfg(test)]
 pub mod util;
 
-mod cancel;
-mod fees;
-mod invalid_payment;
-mod offer;
-mod overpayment;
-mod pagination;
-mod sale;
-mod update;
+// Tokens of the marketplace collection are blocklisted by id
+mod blocked_tokens;
+mod schema;
+
+cw721_marketplace_utils::marketplace_tests!(crate::integration_tests::util::Swap721);
@@ -0,0 +1,23 @@
+#![cfg(test)]
+use std::path::Path;
+
+use cosmwasm_schema::generate_api;
+
+use cw721_marketplace_utils::testing::assert_schema_current;
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+
+// The checked in schema is the one `cargo schema` writes
+#[test]
+fn test_schema_current() {
+    let api = generate_api! {
+        instantiate: InstantiateMsg,
+        execute: ExecuteMsg,
+        query: QueryMsg,
+        migrate: MigrateMsg,
+    };
+    assert_schema_current(
+        api,
+        Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/schema")),
+    );
+}
@@ -1,30 +1,54 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Order, StdResult, Storage};
-use cw_storage_plus::{Bound, Item, Map};
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+use cw721_marketplace_utils::state::{MarketConfig, CONFIG_KEY};
 
-use cw721_marketplace_utils::prelude::CW721Swap;
 pub use cw721_marketplace_utils::prelude::SwapType;
+pub use cw721_marketplace_utils::state::{
+    IdCharset, PauseState, SwapIdRules, BLOCKED_ADDRESSES, BLOCKED_TOKENS, FEE_RECIPIENTS, PAUSE,
+    PAUSED_CW721, PENDING_ADMIN, ROLES, SWAPS, SWAP_COUNT, SWAP_ID_RULES,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Config {
-    pub admin: Addr,
+    pub admin: Option<Addr>, // `None` once the admin role has been renounced
     pub denom: String,
     pub cw721: Addr,
     pub fees: u64,
 }
 
-pub fn all_swap_ids<'a>(
-    storage: &dyn Storage,
-    start: Option<Bound<'a, &'a str>>,
-    limit: usize,
-) -> StdResult<Vec<String>> {
-    SWAPS
-        .keys(storage, start, None, Order::Ascending)
-        .take(limit)
-        .collect()
+impl MarketConfig for Config {
+    fn admin(&self) -> Option<&Addr> {
+        self.admin.as_ref()
+    }
+
+    fn set_admin(&mut self, admin: Option<Addr>) {
+        self.admin = admin;
+    }
+
+    fn denom(&self) -> &str {
+        &self.denom
+    }
+
+    fn fees(&self) -> u64 {
+        self.fees
+    }
+
+    fn set_fees(&mut self, fees: u64) {
+        self.fees = fees;
+    }
+}
+
+// Config stored by v0.1.x, whose admin role couldn't be renounced
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ConfigV0_1 {
+    pub admin: Addr,
+    pub denom: String,
+    pub cw721: Addr,
+    pub fees: u64,
 }
 
-pub const SWAPS: Map<&str, CW721Swap> = Map::new("cw721_swap");
-pub const CONFIG: Item<Config> = Item::new("config");
+pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
@@ -1,24 +1,40 @@
 #[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdResult, SubMsgResult,
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    SubMsgResult,
 };
 
-use cw721_marketplace_utils::prelude::SwapType;
-
-use crate::error::ContractError;
-use crate::execute::{
-    execute_cancel, execute_create, execute_finish, execute_update, execute_update_config,
-    execute_withdraw_fees,
+use cosmwasm_std::Uint128;
+use cw721_marketplace_utils::execute::{
+    execute_accept_admin, execute_add_hook, execute_batch_cancel, execute_batch_create,
+    execute_batch_finish, execute_batch_update, execute_block, execute_cancel, execute_counter,
+    execute_create, execute_create_trait_offer, execute_decline_counter, execute_delist_blocked,
+    execute_distribute_fees, execute_fill_trait_offer, execute_finish, execute_grant_role,
+    execute_list_rental, execute_pause, execute_propose_admin, execute_remove_hook,
+    execute_renounce_admin, execute_rent, execute_revoke_role, execute_sweep, execute_unblock,
+    execute_unpause, execute_update, execute_update_config, execute_update_expiration_rules,
+    execute_update_fee_recipients, execute_update_min_price, execute_update_payment_token,
+    execute_update_swap_id_rules, execute_withdraw_fees,
 };
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::query::{
-    query_config, query_details, query_list, query_swap_total, query_swaps, query_swaps_by_creator,
+use cw721_marketplace_utils::hooks::{hook_failed, HOOK_REPLY_ID};
+use cw721_marketplace_utils::migrate::{check_no_migration, migrate_marketplace, ConfigV0_1};
+use cw721_marketplace_utils::prelude::{OnchainMetadata, Open, SwapType};
+use cw721_marketplace_utils::query::{
+    query_blocked_addresses, query_blocked_tokens, query_details, query_expiration_rules,
+    query_fee_recipients, query_hooks, query_list, query_min_price, query_negotiations_of_token,
+    query_paused, query_pending_admin, query_rental, query_role_members, query_simulate_finish,
+    query_swap_id_rules, query_swap_total, query_swaps, query_swaps_by_creator,
     query_swaps_by_denom, query_swaps_by_payment_type, query_swaps_by_price, query_swaps_of_token,
+    query_traits, query_user_of,
 };
+use cw721_marketplace_utils::settlement::{is_settlement_reply, settlement_reply};
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{Config, CONFIG};
+use crate::ContractError;
 
-use cw2::{get_contract_version, set_contract_version};
+use cw2::set_contract_version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:archid-marketplace";
@@ -41,7 +57,7 @@ pub fn instantiate(
     };
 
     let config = Config {
-        admin: msg.admin,
+        admin: Some(msg.admin),
         denom: msg.denom.clone(),
         fees: fee_percentage,
     };
@@ -60,32 +76,102 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    check_no_migration(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
     match msg {
         // Swap entry points
-        ExecuteMsg::Create(msg) => execute_create(deps, env, info, msg),
-        ExecuteMsg::Finish(msg) => execute_finish(deps, env, info, msg),
-        ExecuteMsg::Update(msg) => execute_update(deps, env, info, msg),
+        ExecuteMsg::Create(msg) => execute_create(deps, env, info, &config, &Open, msg),
+        ExecuteMsg::Finish(msg) => execute_finish(deps, env, info, &config, &Open, msg),
+        ExecuteMsg::Update(msg) => execute_update(deps, env, info, &config, msg),
+        ExecuteMsg::UpdatePaymentToken(msg) => {
+            execute_update_payment_token(deps, env, info, &config, msg)
+        }
         ExecuteMsg::Cancel(msg) => execute_cancel(deps, env, info, msg),
+        ExecuteMsg::BatchCreate(msgs) => {
+            execute_batch_create(deps, env, info, &config, &Open, msgs)
+        }
+        ExecuteMsg::BatchFinish(ids) => execute_batch_finish(deps, env, info, &config, &Open, ids),
+        ExecuteMsg::BatchCancel(ids) => execute_batch_cancel(deps, env, info, ids),
+        ExecuteMsg::BatchUpdate(msgs) => execute_batch_update(deps, env, info, &config, msgs),
+        ExecuteMsg::Sweep(msg) => execute_sweep(deps, env, info, &config, &Open, msg),
+        ExecuteMsg::Counter(msg) => execute_counter(deps, env, info, &config, &Open, msg),
+        ExecuteMsg::DeclineCounter { id } => execute_decline_counter(deps, env, info, id),
+        ExecuteMsg::ListRental(msg) => execute_list_rental(deps, env, info, &config, &Open, msg),
+        ExecuteMsg::Rent(msg) => execute_rent(deps, env, info, &config, &Open, msg),
+        ExecuteMsg::CreateTraitOffer(msg) => {
+            execute_create_trait_offer(deps, env, info, &config, &Open, msg)
+        }
+        ExecuteMsg::FillTraitOffer(msg) => {
+            execute_fill_trait_offer(deps, env, info, &config, &Open, &OnchainMetadata, msg)
+        }
+        ExecuteMsg::AddHook(msg) => execute_add_hook(deps, env, info, &config, msg),
+        ExecuteMsg::RemoveHook(msg) => execute_remove_hook(deps, env, info, &config, msg),
+        ExecuteMsg::DistributeFees(msg) => {
+            execute_distribute_fees(deps, env, info, &config, msg, Uint128::zero())
+        }
+        ExecuteMsg::DelistBlocked { start_after, limit } => {
+            execute_delist_blocked(deps, env, info, start_after, limit)
+        }
 
         // Admin only
-        ExecuteMsg::UpdateConfig { config } => execute_update_config(deps, env, info, config),
-        ExecuteMsg::Withdraw(msg) => execute_withdraw_fees(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig {
+            config: config_update,
+        } => execute_update_config(deps, env, info, &config, config_update),
+        ExecuteMsg::Withdraw(msg) => {
+            execute_withdraw_fees(deps, env, info, &config, msg, Uint128::zero())
+        }
+        ExecuteMsg::UpdateFeeRecipients { recipients } => {
+            execute_update_fee_recipients(deps, env, info, &config, recipients)
+        }
+        ExecuteMsg::ProposeAdmin { admin } => {
+            execute_propose_admin(deps, env, info, &config, admin)
+        }
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info, config),
+        ExecuteMsg::RenounceAdmin {} => execute_renounce_admin(deps, env, info, config),
+        ExecuteMsg::GrantRole { role, address } => {
+            execute_grant_role(deps, env, info, &config, role, address)
+        }
+        ExecuteMsg::RevokeRole { role, address } => {
+            execute_revoke_role(deps, env, info, &config, role, address)
+        }
+        ExecuteMsg::Pause { scope } => execute_pause(deps, env, info, &config, scope),
+        ExecuteMsg::Unpause { scope } => execute_unpause(deps, env, info, &config, scope),
+        ExecuteMsg::Block(msg) => execute_block(deps, env, info, &config, msg),
+        ExecuteMsg::Unblock(msg) => execute_unblock(deps, env, info, &config, msg),
+        ExecuteMsg::UpdateSwapIdRules { rules } => {
+            execute_update_swap_id_rules(deps, env, info, &config, rules)
+        }
+        ExecuteMsg::UpdateMinPrice {
+            payment_token,
+            min_price,
+        } => execute_update_min_price(deps, env, info, &config, payment_token, min_price),
+        ExecuteMsg::UpdateExpirationRules { rules } => {
+            execute_update_expiration_rules(deps, env, info, &config, rules)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::List { start_after, limit } => {
             to_json_binary(&query_list(deps, start_after, limit)?)
         }
-        QueryMsg::Details { id } => to_json_binary(&query_details(deps, id)?),
-        QueryMsg::GetTotal { swap_type } => to_json_binary(&query_swap_total(deps, swap_type)?),
+        QueryMsg::Details { id } => to_json_binary(&query_details(deps, env, id)?),
+        QueryMsg::SimulateFinish { id, sender, funds } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&query_simulate_finish(
+                deps, env, &config, &Open, id, sender, funds,
+            )?)
+        }
+        QueryMsg::GetTotal { swap_type } => {
+            to_json_binary(&query_swap_total(deps, &Open, swap_type)?)
+        }
         QueryMsg::GetOffers { page, limit } => {
-            to_json_binary(&query_swaps(deps, SwapType::Offer, page, limit)?)
+            to_json_binary(&query_swaps(deps, &Open, SwapType::Offer, page, limit)?)
         }
         QueryMsg::GetListings { page, limit } => {
-            to_json_binary(&query_swaps(deps, SwapType::Sale, page, limit)?)
+            to_json_binary(&query_swaps(deps, &Open, SwapType::Sale, page, limit)?)
         }
         QueryMsg::ListingsOfToken {
             token_id,
@@ -94,8 +180,33 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             page,
             limit,
         } => to_json_binary(&query_swaps_of_token(
-            deps, token_id, cw721, swap_type, page, limit,
+            deps,
+            &Open,
+            token_id,
+            Some(cw721),
+            swap_type,
+            page,
+            limit,
+        )?),
+        QueryMsg::NegotiationsOfToken {
+            token_id,
+            cw721,
+            page,
+            limit,
+        } => to_json_binary(&query_negotiations_of_token(
+            deps,
+            &Open,
+            token_id,
+            Some(cw721),
+            page,
+            limit,
         )?),
+        QueryMsg::UserOf { token_id, cw721 } => {
+            to_json_binary(&query_user_of(deps, env, cw721, token_id)?)
+        }
+        QueryMsg::Rental { id } => to_json_binary(&query_rental(deps, id)?),
+        QueryMsg::Traits { id } => to_json_binary(&query_traits(deps, id)?),
+        QueryMsg::Hooks { subject } => to_json_binary(&query_hooks(deps, subject)?),
         QueryMsg::SwapsOf {
             address,
             swap_type,
@@ -103,7 +214,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             page,
             limit,
         } => to_json_binary(&query_swaps_by_creator(
-            deps, address, swap_type, cw721, page, limit,
+            deps, &Open, address, swap_type, cw721, page, limit,
         )?),
         QueryMsg::SwapsByPrice {
             min,
@@ -113,7 +224,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             page,
             limit,
         } => to_json_binary(&query_swaps_by_price(
-            deps, min, max, swap_type, cw721, page, limit,
+            deps, &Open, min, max, swap_type, cw721, page, limit,
         )?),
         QueryMsg::SwapsByDenom {
             payment_token,
@@ -123,6 +234,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
         } => to_json_binary(&query_swaps_by_denom(
             deps,
+            &Open,
             payment_token,
             swap_type,
             cw721,
@@ -136,14 +248,42 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             page,
             limit,
         } => to_json_binary(&query_swaps_by_payment_type(
-            deps, cw20, swap_type, cw721, page, limit,
+            deps, &Open, cw20, swap_type, cw721, page, limit,
         )?),
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::FeeRecipients {} => to_json_binary(&query_fee_recipients(deps)?),
+        QueryMsg::PendingAdmin {} => to_json_binary(&query_pending_admin(deps)?),
+        QueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::Paused {} => to_json_binary(&query_paused(deps)?),
+        QueryMsg::BlockedAddresses { start_after, limit } => {
+            to_json_binary(&query_blocked_addresses(deps, start_after, limit)?)
+        }
+        QueryMsg::BlockedTokens { start_after, limit } => {
+            to_json_binary(&query_blocked_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::SwapIdRules {} => to_json_binary(&query_swap_id_rules(deps)?),
+        QueryMsg::ExpirationRules {} => to_json_binary(&query_expiration_rules(deps)?),
+        QueryMsg::MinPrice { payment_token } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&query_min_price(deps, &config, payment_token)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // Failed hooks don't revert the action that triggered them
+    if msg.id == HOOK_REPLY_ID {
+        return Ok(hook_failed(msg));
+    }
+    // Failed settlement transfers revert the swap
+    if is_settlement_reply(deps.storage, msg.id) {
+        return settlement_reply(deps, msg);
+    }
     match msg.result {
         SubMsgResult::Ok(_) => Ok(Response::default()),
         SubMsgResult::Err(_) => Err(ContractError::Unauthorized {}),
@@ -151,18 +291,19 @@ pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, Contract
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let original_version = get_contract_version(deps.storage)?;
-    let name = CONTRACT_NAME.to_string();
-    let version = CONTRACT_VERSION.to_string();
-    if original_version.contract != name {
-        return Err(ContractError::InvalidInput {});
-    }
-    if original_version.version >= version {
-        return Err(ContractError::InvalidInput {});
-    }
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrate_marketplace(
+        deps.storage,
+        &env.block,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        msg,
+        |config: ConfigV0_1| Config {
+            admin: Some(config.admin),
+            denom: config.denom,
+            fees: config.fees,
+        },
+    )
 }
 
 #[cfg(test)]
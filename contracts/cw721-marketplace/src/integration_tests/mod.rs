@@ -1,10 +1,7 @@
+#![cfg(test)]
 pub mod util;
 
-mod cancel;
-mod fees;
-mod invalid_payment;
-mod offer;
-mod overpayment;
-mod pagination;
-mod sale;
-mod update;
+mod schema;
+
+cw721_marketplace_utils::marketplace_tests!(crate::integration_tests::util::Swap721);
+cw721_marketplace_utils::multi_collection_tests!(crate::integration_tests::util::Swap721);
@@ -1,9 +1,7 @@
 pub mod contract;
-mod error;
-pub mod execute;
 mod integration_tests;
 pub mod msg;
-pub mod query;
+#[cfg(any(test, feature = "testing"))]
+pub mod multitest;
 pub mod state;
-pub mod utils;
-pub use crate::error::ContractError;
+pub use cw721_marketplace_utils::ContractError;
@@ -0,0 +1,49 @@
+//! Multi-test wrapper of the marketplace, to run it in a `cw_multi_test::App`
+use cosmwasm_std::{to_json_binary, Addr, Binary, Empty};
+use cw_multi_test::{Contract, ContractWrapper};
+
+use cw721_marketplace_utils::migrate::ConfigV0_1;
+use cw721_marketplace_utils::testing::Marketplace;
+
+use crate::msg::InstantiateMsg;
+use crate::state::Config;
+
+static DENOM: &str = "aarch";
+
+/// The marketplace contract, run by the shared suite of
+/// `cw721_marketplace_utils::testing` (see `testing::Marketplace`)
+pub struct Swap721;
+
+impl Marketplace for Swap721 {
+    type Config = Config;
+    type InstantiateMsg = InstantiateMsg;
+
+    fn contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply(crate::contract::reply)
+        .with_migrate(crate::contract::migrate);
+        Box::new(contract)
+    }
+
+    // Any cw721 can be traded
+    fn instantiate_msg(admin: &Addr, _cw721: &Addr, fee_percentage: u64) -> InstantiateMsg {
+        InstantiateMsg {
+            admin: admin.clone(),
+            denom: DENOM.into(),
+            fee_percentage,
+        }
+    }
+
+    fn legacy_config(admin: &Addr, _cw721: &Addr, fee_percentage: u64) -> Binary {
+        to_json_binary(&ConfigV0_1 {
+            admin: admin.clone(),
+            denom: DENOM.into(),
+            fees: fee_percentage,
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{bail, Context, Result};
+use cosmwasm_std::{Addr, Uint128};
+use serde_json::Value;
+
+use cw721_marketplace_permissioned::state::ListingRequest;
+use cw721_marketplace_single_collection::state::Config as SingleCollectionConfig;
+use cw721_marketplace_utils::msg::{BlockedToken, PausedResponse, UserResponse};
+use cw721_marketplace_utils::prelude::{
+    Config, DetailsResponse, FeeRecipient, ListResponse, NegotiationsResponse, PageResult, Rental,
+    SimulateFinishResponse, Trait,
+};
+use cw721_marketplace_utils::state::{ExpirationRules, SwapIdRules};
+
+use crate::messages::{typed, Contract};
+
+/// Decodes `response`, the response of query `query` of `contract`, as the
+/// response type of the query. Responses of `archwayd query wasm contract-state
+/// smart` (the response under `data`) are decoded as well
+pub fn decode(contract: Contract, query: &str, response: &Value) -> Result<Value> {
+    let response = match response.as_object() {
+        Some(fields) if fields.len() == 1 && fields.contains_key("data") => &fields["data"],
+        _ => response,
+    };
+    let query = query.replace('-', "_");
+    let decoded = match (contract, query.as_str()) {
+        (_, "list") => typed::<ListResponse>(response),
+        (_, "get_total") => typed::<u128>(response),
+        (
+            _,
+            "get_offers"
+            | "get_listings"
+            | "listings_of_token"
+            | "swaps_of"
+            | "swaps_by_price"
+            | "swaps_by_denom"
+            | "swaps_by_payment_type",
+        ) => typed::<PageResult>(response),
+        (_, "negotiations_of_token") => typed::<NegotiationsResponse>(response),
+        (_, "user_of") => typed::<UserResponse>(response),
+        (_, "rental") => typed::<Rental>(response),
+        (_, "traits") => typed::<Vec<Trait>>(response),
+        (_, "hooks" | "role_members" | "blocked_addresses") => typed::<Vec<Addr>>(response),
+        (_, "details") => typed::<DetailsResponse>(response),
+        (_, "simulate_finish") => typed::<SimulateFinishResponse>(response),
+        (Contract::SingleCollection, "config") => typed::<SingleCollectionConfig>(response),
+        (_, "config") => typed::<Config>(response),
+        (_, "fee_recipients") => typed::<Vec<FeeRecipient>>(response),
+        (_, "pending_admin") => typed::<Option<Addr>>(response),
+        (_, "paused") => typed::<PausedResponse>(response),
+        (Contract::SingleCollection, "blocked_tokens") => typed::<Vec<String>>(response),
+        (_, "blocked_tokens") => typed::<Vec<BlockedToken>>(response),
+        (_, "swap_id_rules") => typed::<SwapIdRules>(response),
+        (_, "expiration_rules") => typed::<ExpirationRules>(response),
+        (_, "min_price") => typed::<Uint128>(response),
+        (Contract::Permissioned, "allowed_collections") => typed::<Vec<Addr>>(response),
+        (Contract::Permissioned, "listing_requests") => typed::<Vec<ListingRequest>>(response),
+        (Contract::Permissioned, "listing_fee") => typed::<Uint128>(response),
+        _ => bail!("unknown query `{query}` of the {contract:?} marketplace"),
+    };
+    decoded.with_context(|| format!("invalid `{query}` response"))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::messages::{messages, MsgKind};
+
+    #[test]
+    fn responses_decoded() {
+        let details = json!({
+            "creator": "archway1seller",
+            "contract": "archway1nft",
+            "payment_token": null,
+            "token_id": "1",
+            "expires": {"never": {}},
+            "price": "1000",
+            "swap_type": "Sale",
+            "status": "filled",
+            "filler": "archway1buyer",
+            "close_height": 12345
+        });
+        let wrapped = json!({ "data": details });
+        assert_eq!(
+            decode(Contract::Open, "details", &wrapped).unwrap(),
+            details
+        );
+
+        let err = decode(Contract::Open, "config", &details).unwrap_err();
+        assert!(err.root_cause().to_string().contains("missing field"));
+
+        let err = decode(Contract::Open, "listing_fee", &json!("10")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown query `listing_fee` of the Open marketplace"
+        );
+        assert_eq!(
+            decode(Contract::Permissioned, "listing_fee", &json!("10")).unwrap(),
+            json!("10")
+        );
+    }
+
+    #[test]
+    fn every_query_decoded() {
+        for contract in [
+            Contract::Open,
+            Contract::Permissioned,
+            Contract::SingleCollection,
+        ] {
+            for (query, _) in messages(contract, MsgKind::Query) {
+                let err = decode(contract, &query, &json!({"unexpected": true}));
+                let err = err.unwrap_err().to_string();
+                assert_eq!(err, format!("invalid `{query}` response"));
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{anyhow, Context, Result};
+use cosmwasm_std::{Addr, Uint128};
+use serde_json::{json, Value};
+
+use cw721_marketplace_interface::{quote_fee_percentage, quote_fee_shares};
+use cw721_marketplace_utils::prelude::FeeRecipient;
+
+/// Parses a fee recipient given as `address=weight`
+pub fn parse_recipient(recipient: &str) -> Result<FeeRecipient> {
+    let (address, weight) = recipient
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected `address=weight`, got `{recipient}`"))?;
+    Ok(FeeRecipient {
+        address: Addr::unchecked(address),
+        weight: weight
+            .parse()
+            .with_context(|| format!("invalid weight `{weight}`"))?,
+    })
+}
+
+/// Split of `price` between the marketplace and the seller by a marketplace
+/// charging `fee_percentage`, computed as when the swap is settled, with the
+/// share of the marketplace fee paid to each of its fee `recipients`
+pub fn quote(price: Uint128, fee_percentage: u64, recipients: &[FeeRecipient]) -> Value {
+    let split = quote_fee_percentage(fee_percentage, price);
+    let shares: Vec<Value> = quote_fee_shares(&split, recipients)
+        .into_iter()
+        .zip(recipients)
+        .map(|((address, share), recipient)| {
+            json!({
+                "recipient": address,
+                "weight": recipient.weight,
+                "share": share,
+            })
+        })
+        .collect();
+    json!({
+        "price": price,
+        "fee_percentage": fee_percentage,
+        "marketplace": split.marketplace,
+        "seller": split.seller,
+        "fee_recipients": shares,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fees_quoted() {
+        let recipients = vec![
+            parse_recipient("treasury=3").unwrap(),
+            parse_recipient("partner=1").unwrap(),
+        ];
+        assert_eq!(
+            quote(Uint128::new(1000), 20, &recipients),
+            json!({
+                "price": "1000",
+                "fee_percentage": 20,
+                "marketplace": "200",
+                "seller": "800",
+                "fee_recipients": [
+                    {"recipient": "treasury", "weight": 3, "share": "150"},
+                    {"recipient": "partner", "weight": 1, "share": "50"},
+                ],
+            })
+        );
+
+        // Fees taking the whole price aren't charged
+        let quote = quote(Uint128::new(1000), 100, &[]);
+        assert_eq!(quote["marketplace"], json!("0"));
+        assert_eq!(quote["seller"], json!("1000"));
+
+        assert!(parse_recipient("treasury").is_err());
+        assert!(parse_recipient("treasury=x").is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Offline command line tool for operating the marketplaces: builds validated
+//! json of their messages, quotes fees and decodes query responses
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use cosmwasm_std::Uint128;
+use serde_json::Value;
+
+mod decode;
+mod fees;
+mod messages;
+mod table;
+
+use crate::messages::{Contract, MsgKind};
+
+#[derive(Parser)]
+#[command(name = "marketplace-cli", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Builds the json of an `ExecuteMsg`, ie: `execute permissioned add_nft cw721=archway1...`
+    Execute(MsgArgs),
+    /// Builds the json of a `QueryMsg`, ie: `query open get_listings limit=10`
+    Query(MsgArgs),
+    /// Lists the messages of a marketplace and their fields
+    Messages { contract: Contract, kind: MsgKind },
+    /// Splits a price between the marketplace fee and the seller
+    Fees(FeesArgs),
+    /// Decodes the json response of a query into tables
+    Decode(DecodeArgs),
+}
+
+#[derive(Args)]
+struct MsgArgs {
+    contract: Contract,
+    /// Message name, ie: `update_config`
+    msg: String,
+    /// Fields of the message, as `field=value`; values of non string fields are json
+    fields: Vec<String>,
+    /// Json body of the message, instead of its fields
+    #[arg(long, conflicts_with = "fields")]
+    json: Option<String>,
+}
+
+#[derive(Args)]
+struct FeesArgs {
+    /// Swap price
+    price: u128,
+    /// Fee percentage of the marketplace (ie: 1 = 1%), the `fees` of its config
+    #[arg(long)]
+    fee_percentage: u64,
+    /// Fee recipient of the marketplace, as `address=weight`
+    #[arg(long = "recipient")]
+    recipients: Vec<String>,
+    /// Prints json instead of tables
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct DecodeArgs {
+    contract: Contract,
+    /// Query of the response, ie: `get_listings`
+    query: String,
+    /// File of the response; read from stdin if `-` or missing
+    file: Option<PathBuf>,
+    /// Prints the validated json instead of tables
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Execute(args) => print_msg(MsgKind::Execute, args),
+        Command::Query(args) => print_msg(MsgKind::Query, args),
+        Command::Messages { contract, kind } => {
+            let rows: Vec<Vec<String>> = messages::messages(contract, kind)
+                .into_iter()
+                .map(|(name, fields)| vec![name, fields])
+                .collect();
+            print!("{}", table::grid(&["message", "fields"], &rows));
+            Ok(())
+        }
+        Command::Fees(args) => {
+            let recipients = args
+                .recipients
+                .iter()
+                .map(|recipient| fees::parse_recipient(recipient))
+                .collect::<Result<Vec<_>>>()?;
+            let quote = fees::quote(Uint128::new(args.price), args.fee_percentage, &recipients);
+            print_value(&quote, args.json)
+        }
+        Command::Decode(args) => {
+            let response = read_input(args.file)?;
+            let response: Value =
+                serde_json::from_str(&response).context("response isn't valid json")?;
+            let decoded = decode::decode(args.contract, &args.query, &response)?;
+            print_value(&decoded, args.json)
+        }
+    }
+}
+
+fn print_msg(kind: MsgKind, args: MsgArgs) -> Result<()> {
+    let msg = messages::build(
+        args.contract,
+        kind,
+        &args.msg,
+        &args.fields,
+        args.json.as_deref(),
+    )?;
+    println!("{}", serde_json::to_string_pretty(&msg)?);
+    Ok(())
+}
+
+fn print_value(value: &Value, json: bool) -> Result<()> {
+    match json {
+        true => println!("{}", serde_json::to_string_pretty(value)?),
+        false => print!("{}", table::render(value)),
+    }
+    Ok(())
+}
+
+fn read_input(file: Option<PathBuf>) -> Result<String> {
+    match file {
+        Some(path) if path.as_os_str() != "-" => {
+            fs::read_to_string(&path).with_context(|| format!("can't read {}", path.display()))
+        }
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
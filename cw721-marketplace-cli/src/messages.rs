@@ -0,0 +1,445 @@
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
+use cosmwasm_schema::schema_for;
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use cw721_marketplace::msg as open;
+use cw721_marketplace_permissioned::msg as permissioned;
+use cw721_marketplace_single_collection::msg as single_collection;
+
+/// Marketplace contract whose messages are built
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Contract {
+    /// cw721-marketplace
+    Open,
+    /// cw721-marketplace-permissioned
+    Permissioned,
+    /// cw721-marketplace-single-collection
+    SingleCollection,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MsgKind {
+    Execute,
+    Query,
+}
+
+impl Contract {
+    /// Json schema of the contract's messages of `kind`
+    pub fn schema(self, kind: MsgKind) -> RootSchema {
+        match (self, kind) {
+            (Contract::Open, MsgKind::Execute) => schema_for!(open::ExecuteMsg),
+            (Contract::Open, MsgKind::Query) => schema_for!(open::QueryMsg),
+            (Contract::Permissioned, MsgKind::Execute) => schema_for!(permissioned::ExecuteMsg),
+            (Contract::Permissioned, MsgKind::Query) => schema_for!(permissioned::QueryMsg),
+            (Contract::SingleCollection, MsgKind::Execute) => {
+                schema_for!(single_collection::ExecuteMsg)
+            }
+            (Contract::SingleCollection, MsgKind::Query) => {
+                schema_for!(single_collection::QueryMsg)
+            }
+        }
+    }
+
+    /// Checks `msg` is a message of `kind` of the contract, returning its json
+    /// as serialized by the contract's own message types
+    pub fn validate(self, kind: MsgKind, msg: &Value) -> Result<Value> {
+        let msg = match (self, kind) {
+            (Contract::Open, MsgKind::Execute) => typed::<open::ExecuteMsg>(msg),
+            (Contract::Open, MsgKind::Query) => typed::<open::QueryMsg>(msg),
+            (Contract::Permissioned, MsgKind::Execute) => typed::<permissioned::ExecuteMsg>(msg),
+            (Contract::Permissioned, MsgKind::Query) => typed::<permissioned::QueryMsg>(msg),
+            (Contract::SingleCollection, MsgKind::Execute) => {
+                typed::<single_collection::ExecuteMsg>(msg)
+            }
+            (Contract::SingleCollection, MsgKind::Query) => {
+                typed::<single_collection::QueryMsg>(msg)
+            }
+        }?;
+        Ok(without_nulls(msg))
+    }
+}
+
+/// Decodes `value` as a `T`, failing on the fields `T` doesn't have (which
+/// serde would otherwise ignore), and returns the json of the decoded `T`
+pub fn typed<T: Serialize + DeserializeOwned>(value: &Value) -> Result<Value> {
+    let decoded: T = serde_json::from_value(value.clone())?;
+    let json = serde_json::to_value(decoded)?;
+    known_fields(value, &json, "")?;
+    Ok(json)
+}
+
+fn known_fields(value: &Value, decoded: &Value, path: &str) -> Result<()> {
+    match (value, decoded) {
+        (Value::Object(fields), Value::Object(decoded)) => {
+            for (key, value) in fields {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match decoded.get(key) {
+                    Some(decoded) => known_fields(value, decoded, &path)?,
+                    None => bail!("unknown field `{path}`"),
+                }
+            }
+        }
+        (Value::Array(items), Value::Array(decoded)) => {
+            for (i, (value, decoded)) in items.iter().zip(decoded).enumerate() {
+                known_fields(value, decoded, &format!("{path}[{i}]"))?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+// Optional fields left out, as the contracts decode missing fields as `None`
+fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(without_nulls).collect()),
+        value => value,
+    }
+}
+
+/// Builds message `name` of `kind` of `contract`, either from its `fields`
+/// (`field=value`) or from the json `body` of the message. Values of string
+/// fields (addresses, amounts, ids) are taken as is, other values as json
+pub fn build(
+    contract: Contract,
+    kind: MsgKind,
+    name: &str,
+    fields: &[String],
+    body: Option<&str>,
+) -> Result<Value> {
+    let schema = contract.schema(kind);
+    let name = name.replace('-', "_");
+    let variant = variant(&schema, &name)?;
+
+    let body = match body {
+        Some(body) => serde_json::from_str(body).context("invalid json body")?,
+        None => {
+            let mut values = Map::new();
+            for field in fields {
+                let (key, raw) = field
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("expected `field=value`, got `{field}`"))?;
+                let field_schema = properties(&schema, variant)
+                    .and_then(|properties| properties.get(key))
+                    .ok_or_else(|| anyhow!("`{name}` has no field `{key}`"))?;
+                values.insert(key.to_string(), parse_value(&schema, field_schema, raw));
+            }
+            Value::Object(values)
+        }
+    };
+
+    let mut msg = Map::new();
+    msg.insert(name, body);
+    contract
+        .validate(kind, &Value::Object(msg))
+        .with_context(|| format!("invalid {kind:?} message of the {contract:?} marketplace"))
+}
+
+/// Messages of `kind` of `contract` and their fields, with the type of each
+/// field; optional fields end with `?`
+pub fn messages(contract: Contract, kind: MsgKind) -> Vec<(String, String)> {
+    let schema = contract.schema(kind);
+    variants(&schema)
+        .map(|(name, body)| {
+            let fields = match properties(&schema, body) {
+                Some(properties) => {
+                    let required = &resolve(&schema, body)
+                        .object
+                        .as_ref()
+                        .expect("message bodies with properties are objects")
+                        .required;
+                    properties
+                        .iter()
+                        .map(|(field, field_schema)| {
+                            let optional = if required.contains(field) { "" } else { "?" };
+                            format!("{field}{optional}: {}", type_name(field_schema))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+                // A message whose body isn't an object, ie: a list of swaps
+                None => format!("json: {}", type_name(body)),
+            };
+            (name.clone(), fields)
+        })
+        .collect()
+}
+
+// Name and body schema of each message
+fn variants(schema: &RootSchema) -> impl Iterator<Item = (&String, &Schema)> {
+    schema
+        .schema
+        .subschemas
+        .iter()
+        .flat_map(|subschemas| subschemas.one_of.iter().flatten())
+        .filter_map(|variant| match variant {
+            Schema::Object(SchemaObject {
+                object: Some(object),
+                ..
+            }) => object.properties.iter().next(),
+            _ => None,
+        })
+}
+
+fn variant<'a>(schema: &'a RootSchema, name: &str) -> Result<&'a Schema> {
+    variants(schema)
+        .find(|(variant, _)| variant.as_str() == name)
+        .map(|(_, body)| body)
+        .ok_or_else(|| anyhow!("unknown message `{name}`"))
+}
+
+fn properties<'a>(
+    schema: &'a RootSchema,
+    body: &'a Schema,
+) -> Option<&'a schemars::Map<String, Schema>> {
+    resolve(schema, body)
+        .object
+        .as_ref()
+        .map(|object| &object.properties)
+}
+
+// Schema of `schema`, following its references to the schema's definitions
+fn resolve<'a>(root: &'a RootSchema, schema: &'a Schema) -> &'a SchemaObject {
+    static ANY: SchemaObject = SchemaObject {
+        metadata: None,
+        instance_type: None,
+        format: None,
+        enum_values: None,
+        const_value: None,
+        subschemas: None,
+        number: None,
+        string: None,
+        array: None,
+        object: None,
+        reference: None,
+        extensions: schemars::Map::new(),
+    };
+    let object = match schema {
+        Schema::Object(object) => object,
+        Schema::Bool(_) => return &ANY,
+    };
+    if let Some(definition) = object
+        .reference
+        .as_deref()
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+        .and_then(|name| root.definitions.get(name))
+    {
+        return resolve(root, definition);
+    }
+    match object.subschemas.as_ref().and_then(|s| s.all_of.as_ref()) {
+        Some(all_of) if all_of.len() == 1 => resolve(root, &all_of[0]),
+        _ => object,
+    }
+}
+
+// Whether `schema` is a string, or an optional string, in json
+fn is_string(root: &RootSchema, schema: &Schema) -> bool {
+    let object = resolve(root, schema);
+    let string = match &object.instance_type {
+        Some(SingleOrVec::Single(ty)) => **ty == InstanceType::String,
+        Some(SingleOrVec::Vec(types)) => types.contains(&InstanceType::String),
+        None => false,
+    };
+    string
+        || object
+            .subschemas
+            .iter()
+            .flat_map(|s| s.any_of.iter().chain(s.one_of.iter()).flatten())
+            .any(|schema| is_string(root, schema))
+}
+
+fn parse_value(root: &RootSchema, schema: &Schema, raw: &str) -> Value {
+    if is_string(root, schema) && !raw.starts_with('"') && raw != "null" {
+        return Value::String(raw.to_string());
+    }
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+fn type_name(schema: &Schema) -> String {
+    let Schema::Object(object) = schema else {
+        return "any".to_string();
+    };
+    if let Some(name) = object
+        .reference
+        .as_deref()
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+    {
+        return name.to_string();
+    }
+    if let Some(subschemas) = &object.subschemas {
+        let options = subschemas
+            .all_of
+            .iter()
+            .chain(subschemas.any_of.iter())
+            .chain(subschemas.one_of.iter())
+            .flatten()
+            .map(type_name)
+            .filter(|name| name != "null")
+            .collect::<Vec<_>>();
+        if !options.is_empty() {
+            return options.join(" | ");
+        }
+    }
+    if let Some(SingleOrVec::Single(item)) =
+        object.array.as_ref().and_then(|array| array.items.as_ref())
+    {
+        return format!("[{}]", type_name(item));
+    }
+    let types = match &object.instance_type {
+        Some(SingleOrVec::Single(ty)) => vec![**ty],
+        Some(SingleOrVec::Vec(types)) => types.clone(),
+        None => vec![],
+    };
+    let names = types
+        .iter()
+        .filter(|ty| **ty != InstanceType::Null)
+        .map(|ty| match ty {
+            InstanceType::Boolean => "bool",
+            InstanceType::Object => "object",
+            InstanceType::Array => "array",
+            InstanceType::Number => "number",
+            InstanceType::String => "string",
+            InstanceType::Integer => "integer",
+            InstanceType::Null => "null",
+        })
+        .collect::<Vec<_>>();
+    match names.is_empty() {
+        true if types.contains(&InstanceType::Null) => "null".to_string(),
+        true => "any".to_string(),
+        false => names.join(" | "),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn fields(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn messages_built_from_fields() {
+        // Amounts and addresses are strings, other fields json
+        let msg = build(
+            Contract::Open,
+            MsgKind::Execute,
+            "create",
+            &fields(&[
+                "cw721=archway1nft",
+                "token_id=1",
+                "price=1000",
+                "swap_type=Sale",
+                r#"expires={"never":{}}"#,
+            ]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            json!({"create": {
+                "cw721": "archway1nft",
+                "token_id": "1",
+                "price": "1000",
+                "swap_type": "Sale",
+                "expires": {"never": {}},
+            }})
+        );
+
+        let msg = build(
+            Contract::Permissioned,
+            MsgKind::Execute,
+            "add-nft",
+            &fields(&["cw721=archway1nft"]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(msg, json!({"add_nft": {"cw721": "archway1nft"}}));
+
+        let msg = build(
+            Contract::SingleCollection,
+            MsgKind::Query,
+            "get_listings",
+            &fields(&["limit=10"]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(msg, json!({"get_listings": {"limit": 10}}));
+
+        let config = r#"{"config": {"admin": "archway1admin", "denom": "aarch", "fees": 2}}"#;
+        let msg = build(
+            Contract::Open,
+            MsgKind::Execute,
+            "update_config",
+            &[],
+            Some(config),
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            json!({"update_config": {"config": {"admin": "archway1admin", "denom": "aarch", "fees": 2}}})
+        );
+    }
+
+    #[test]
+    fn invalid_messages_rejected() {
+        let err = build(Contract::Open, MsgKind::Execute, "add_nft", &[], None).unwrap_err();
+        assert_eq!(err.to_string(), "unknown message `add_nft`");
+
+        let err = build(
+            Contract::Permissioned,
+            MsgKind::Execute,
+            "add_nft",
+            &fields(&["collection=archway1nft"]),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "`add_nft` has no field `collection`");
+
+        // Missing fields
+        let err = build(
+            Contract::Open,
+            MsgKind::Execute,
+            "withdraw",
+            &fields(&["amount=10"]),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.root_cause().to_string().contains("missing field"));
+
+        // Fields which serde would ignore
+        let body = r#"{"id": "1", "price": "10", "expires": {"never": {}}, "cw721": "a"}"#;
+        let err = build(Contract::Open, MsgKind::Execute, "update", &[], Some(body)).unwrap_err();
+        assert_eq!(err.root_cause().to_string(), "unknown field `update.cw721`");
+    }
+
+    #[test]
+    fn every_message_listed() {
+        for contract in Contract::value_variants() {
+            for kind in MsgKind::value_variants() {
+                let messages = messages(*contract, *kind);
+                let schema = contract.schema(*kind);
+                let count = schema.schema.subschemas.unwrap().one_of.unwrap().len();
+                assert_eq!(messages.len(), count);
+            }
+        }
+        let messages = messages(Contract::Permissioned, MsgKind::Execute);
+        assert!(messages.contains(&("add_nft".to_string(), "cw721: Addr".to_string())));
+    }
+}
@@ -0,0 +1,152 @@
+use serde_json::Value;
+
+/// Renders `value` as text tables: lists of objects as one row per item,
+/// objects as `field | value` rows (nested fields joined with `.`), with
+/// their lists of objects as tables of their own
+pub fn render(value: &Value) -> String {
+    match value {
+        Value::Array(items) if items.is_empty() => "(empty)\n".to_string(),
+        Value::Array(items) if items.iter().all(Value::is_object) => rows(items),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| format!("{}\n", cell(item)))
+            .collect(),
+        Value::Object(_) => {
+            let mut fields = vec![];
+            let mut lists = vec![];
+            flatten(value, "", &mut fields, &mut lists);
+            let mut out = grid(&["field", "value"], &fields);
+            for (key, items) in lists {
+                out.push_str(&format!("\n{key}\n{}", render(items)));
+            }
+            out
+        }
+        value => format!("{}\n", cell(value)),
+    }
+}
+
+/// Text table of `rows`, with a line under the `headers`
+pub fn grid(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect();
+        format!("{}\n", cells.join(" | ").trim_end())
+    };
+    let mut out = line(headers.to_vec());
+    let rules: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    out.push_str(&format!("{}\n", rules.join("-+-")));
+    for row in rows {
+        out.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    out
+}
+
+// One row per object of `items`, with a column for each of their fields
+fn rows(items: &[Value]) -> String {
+    let mut headers: Vec<&str> = vec![];
+    for item in items {
+        for key in item
+            .as_object()
+            .into_iter()
+            .flat_map(|fields| fields.keys())
+        {
+            if !headers.contains(&key.as_str()) {
+                headers.push(key);
+            }
+        }
+    }
+    let rows: Vec<Vec<String>> = items
+        .iter()
+        .map(|item| {
+            headers
+                .iter()
+                .map(|key| {
+                    item.get(*key)
+                        .map(cell)
+                        .unwrap_or_else(|| cell(&Value::Null))
+                })
+                .collect()
+        })
+        .collect();
+    grid(&headers, &rows)
+}
+
+fn flatten<'a>(
+    value: &'a Value,
+    path: &str,
+    fields: &mut Vec<Vec<String>>,
+    lists: &mut Vec<(String, &'a Value)>,
+) {
+    match value {
+        // Objects with a single field are enums, ie: `{"never": {}}`
+        Value::Object(object) if object.len() > 1 || path.is_empty() => {
+            for (key, value) in object {
+                let path = match path {
+                    "" => key.clone(),
+                    path => format!("{path}.{key}"),
+                };
+                flatten(value, &path, fields, lists);
+            }
+        }
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            lists.push((path.to_string(), value));
+        }
+        value => fields.push(vec![path.to_string(), cell(value)]),
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn page_rendered() {
+        let page = json!({
+            "swaps": [
+                {"id": "1", "price": "1000", "payment_token": null, "expires": {"never": {}}},
+                {"id": "2", "price": "50", "payment_token": "archway1cw20", "expires": {"at_height": 10}},
+            ],
+            "page": 1,
+            "total": 2
+        });
+        assert_eq!(
+            render(&page),
+            "field | value\n\
+             ------+------\n\
+             page  | 1\n\
+             total | 2\n\
+             \n\
+             swaps\n\
+             expires          | id | payment_token | price\n\
+             -----------------+----+---------------+------\n\
+             {\"never\":{}}     | 1  | -             | 1000\n\
+             {\"at_height\":10} | 2  | archway1cw20  | 50\n"
+        );
+        assert_eq!(render(&json!(["a", "b"])), "a\nb\n");
+        assert_eq!(render(&json!([])), "(empty)\n");
+        assert_eq!(render(&json!("1000")), "1000\n");
+    }
+}
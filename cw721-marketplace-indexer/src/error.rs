@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IndexerError {
+    #[error("{0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Event {action} #{sequence} has no `{key}` attribute")]
+    MissingAttribute {
+        action: String,
+        sequence: u64,
+        key: String,
+    },
+
+    #[error("Invalid `{key}` attribute of event {action} #{sequence}: {value}")]
+    InvalidAttribute {
+        action: String,
+        sequence: u64,
+        key: String,
+        value: String,
+    },
+
+    #[error("Marketplace event without a contract address or sequence: {0}")]
+    InvalidEvent(String),
+
+    #[error("Invalid indexed {field}: {value}")]
+    InvalidRow { field: String, value: String },
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::error::IndexerError;
+
+const EVENT_PREFIX: &str = "wasm-marketplace-";
+
+// Attribute naming the contract of a `wasm-*` event, as set by wasmd and multi-test
+const CONTRACT_ATTRIBUTES: [&str; 2] = ["_contract_address", "_contract_addr"];
+
+/// A `wasm-marketplace-<action>` event of a marketplace contract, see
+/// `cw721_marketplace_utils::events`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarketplaceEvent {
+    pub contract: String,
+    pub action: String,
+    pub sequence: u64,
+    pub attributes: BTreeMap<String, String>,
+    /// Height and hash of the transaction of the event, if found in the input
+    pub height: Option<u64>,
+    pub tx_hash: Option<String>,
+}
+
+impl MarketplaceEvent {
+    pub fn attribute(&self, key: &str) -> Result<&str, IndexerError> {
+        self.attributes
+            .get(key)
+            .map(String::as_str)
+            .ok_or_else(|| IndexerError::MissingAttribute {
+                action: self.action.clone(),
+                sequence: self.sequence,
+                key: key.to_string(),
+            })
+    }
+
+    /// Attribute `key` as a `T` encoded as a json string, ie: amounts, swap types
+    pub fn value<T: DeserializeOwned>(&self, key: &str) -> Result<T, IndexerError> {
+        let value = self.attribute(key)?;
+        serde_json::from_value(Value::String(value.to_string()))
+            .map_err(|_| self.invalid(key, value))
+    }
+
+    /// Optional attribute `key` as a `T` encoded as a json string, ie: expiration times
+    pub fn optional_value<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, IndexerError> {
+        match self.attributes.contains_key(key) {
+            true => self.value(key).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    /// Attribute `key` as a `T` encoded as json, ie: expirations
+    pub fn json<T: DeserializeOwned>(&self, key: &str) -> Result<T, IndexerError> {
+        let value = self.attribute(key)?;
+        serde_json::from_str(value).map_err(|_| self.invalid(key, value))
+    }
+
+    fn invalid(&self, key: &str, value: &str) -> IndexerError {
+        IndexerError::InvalidAttribute {
+            action: self.action.clone(),
+            sequence: self.sequence,
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+// Transaction of the events found in a json object
+#[derive(Clone, Default)]
+struct Tx {
+    height: Option<u64>,
+    tx_hash: Option<String>,
+}
+
+/// Marketplace events found anywhere in `input`, a json document or one json
+/// document per line: lists of events (ie: `AppResponse::events` of multi-test)
+/// or transaction responses, whose `height` and `txhash` are kept with their
+/// events. Events are returned as found, duplicates included
+pub fn parse_events(input: &str) -> Result<Vec<MarketplaceEvent>, IndexerError> {
+    let documents: Vec<Value> = match serde_json::from_str(input) {
+        Ok(document) => vec![document],
+        Err(_) => input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?,
+    };
+    let mut events = vec![];
+    for document in &documents {
+        collect(document, &Tx::default(), &mut events)?;
+    }
+    Ok(events)
+}
+
+fn collect(value: &Value, tx: &Tx, events: &mut Vec<MarketplaceEvent>) -> Result<(), IndexerError> {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                collect(item, tx, events)?;
+            }
+        }
+        Value::Object(fields) => match fields.get("type").and_then(Value::as_str) {
+            Some(ty) if ty.starts_with(EVENT_PREFIX) => events.push(event(ty, fields, tx)?),
+            _ => {
+                let tx = Tx {
+                    height: fields.get("height").and_then(height).or(tx.height),
+                    tx_hash: fields
+                        .get("txhash")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .or_else(|| tx.tx_hash.clone()),
+                };
+                for value in fields.values() {
+                    collect(value, &tx, events)?;
+                }
+            }
+        },
+        _ => {}
+    }
+    Ok(())
+}
+
+fn event(ty: &str, fields: &Map<String, Value>, tx: &Tx) -> Result<MarketplaceEvent, IndexerError> {
+    let attributes: BTreeMap<String, String> = fields
+        .get("attributes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|attribute| {
+            let key = attribute.get("key")?.as_str()?;
+            let value = attribute.get("value")?.as_str()?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect();
+    let invalid = || IndexerError::InvalidEvent(Value::Object(fields.clone()).to_string());
+
+    let contract = CONTRACT_ATTRIBUTES
+        .iter()
+        .find_map(|key| attributes.get(*key))
+        .ok_or_else(invalid)?
+        .clone();
+    let sequence = attributes
+        .get("sequence")
+        .and_then(|sequence| sequence.parse().ok())
+        .ok_or_else(invalid)?;
+    Ok(MarketplaceEvent {
+        contract,
+        action: ty[EVENT_PREFIX.len()..].to_string(),
+        sequence,
+        attributes,
+        height: tx.height,
+        tx_hash: tx.tx_hash.clone(),
+    })
+}
+
+// Heights are strings in transaction responses
+fn height(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(height) => height.parse().ok(),
+        value => value.as_u64(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn events_of_tx_responses() {
+        let tx = r#"{
+            "height": "1200",
+            "txhash": "ABCD",
+            "events": [
+                {"type": "message", "attributes": [{"key": "sender", "value": "archway1seller"}]},
+                {"type": "wasm-marketplace-cancel", "attributes": [
+                    {"key": "_contract_address", "value": "archway1marketplace"},
+                    {"key": "sequence", "value": "7"},
+                    {"key": "swap_id", "value": "swap1"},
+                    {"key": "status", "value": "cancelled"}
+                ]}
+            ]
+        }"#;
+        // One transaction per line
+        let input = format!("{}\n\n{}\n", tx.replace('\n', ""), tx.replace('\n', ""));
+        let events = parse_events(&input).unwrap();
+        assert_eq!(events.len(), 2);
+        let event = &events[0];
+        assert_eq!(event.contract, "archway1marketplace");
+        assert_eq!(event.action, "cancel");
+        assert_eq!(event.sequence, 7);
+        assert_eq!(event.height, Some(1200));
+        assert_eq!(event.tx_hash.as_deref(), Some("ABCD"));
+        assert_eq!(event.attribute("swap_id").unwrap(), "swap1");
+        assert_eq!(
+            event.attribute("price").unwrap_err().to_string(),
+            "Event cancel #7 has no `price` attribute"
+        );
+
+        // Marketplace events without a sequence aren't indexable
+        let err = parse_events(r#"[{"type": "wasm-marketplace-cancel", "attributes": []}]"#);
+        assert!(matches!(err, Err(IndexerError::InvalidEvent(_))));
+    }
+}
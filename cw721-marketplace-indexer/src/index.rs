@@ -0,0 +1,664 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::str::FromStr;
+
+use cosmwasm_std::{Uint128, Uint64};
+use cw20::Expiration;
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use cw721_marketplace_utils::prelude::{SwapStatus, SwapType};
+
+use crate::error::IndexerError;
+use crate::events::MarketplaceEvent;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    contract TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    attributes TEXT NOT NULL,
+    height INTEGER,
+    tx_hash TEXT,
+    PRIMARY KEY (contract, sequence)
+);
+CREATE TABLE IF NOT EXISTS swaps (
+    contract TEXT NOT NULL,
+    id TEXT NOT NULL,
+    swap_type TEXT NOT NULL,
+    creator TEXT NOT NULL,
+    collection TEXT NOT NULL,
+    token_id TEXT,
+    payment_token TEXT NOT NULL,
+    price TEXT NOT NULL,
+    expires TEXT NOT NULL,
+    expires_at INTEGER,
+    status TEXT NOT NULL,
+    filler TEXT,
+    created_sequence INTEGER NOT NULL,
+    closed_sequence INTEGER,
+    PRIMARY KEY (contract, id)
+);
+CREATE INDEX IF NOT EXISTS swaps_by_token ON swaps (contract, collection, token_id);
+CREATE INDEX IF NOT EXISTS swaps_by_status ON swaps (contract, status, swap_type);
+CREATE INDEX IF NOT EXISTS swaps_by_expiry ON swaps (contract, status, expires_at);
+CREATE TABLE IF NOT EXISTS sales (
+    contract TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    swap_id TEXT NOT NULL,
+    swap_type TEXT NOT NULL,
+    buyer TEXT NOT NULL,
+    seller TEXT NOT NULL,
+    collection TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    payment_token TEXT NOT NULL,
+    price TEXT NOT NULL,
+    fee TEXT NOT NULL,
+    royalty TEXT NOT NULL,
+    seller_amount TEXT NOT NULL,
+    height INTEGER,
+    tx_hash TEXT,
+    PRIMARY KEY (contract, sequence)
+);
+CREATE TABLE IF NOT EXISTS rentals (
+    contract TEXT NOT NULL,
+    sequence INTEGER NOT NULL,
+    swap_id TEXT NOT NULL,
+    renter TEXT NOT NULL,
+    collection TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    payment_token TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    rented_until TEXT NOT NULL,
+    PRIMARY KEY (contract, sequence)
+);
+CREATE TABLE IF NOT EXISTS collections (
+    contract TEXT NOT NULL,
+    collection TEXT NOT NULL,
+    allowed INTEGER NOT NULL,
+    PRIMARY KEY (contract, collection)
+);
+CREATE TABLE IF NOT EXISTS configs (
+    contract TEXT PRIMARY KEY,
+    denom TEXT NOT NULL,
+    fees INTEGER NOT NULL,
+    config TEXT NOT NULL
+);
+";
+
+const SWAP_COLUMNS: &str = "contract, id, swap_type, creator, collection, token_id, \
+    payment_token, price, expires, expires_at, status, filler";
+
+/// A swap rebuilt from the events of its marketplace
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct IndexedSwap {
+    pub contract: String,
+    pub id: String,
+    pub swap_type: SwapType,
+    pub creator: String,
+    pub collection: String,
+    pub token_id: Option<String>, // `None` for trait offers until they're filled
+    pub payment_token: String,    // The cw20 address or the native denom
+    pub price: Uint128,
+    pub expires: Expiration,
+    pub expires_at: Option<u64>, // Time of `expires` in seconds, `None` if it never expires
+    pub status: SwapStatus,
+    pub filler: Option<String>,
+}
+
+/// A filled swap, from its `sale` event
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Sale {
+    pub contract: String,
+    pub sequence: u64,
+    pub swap_id: String,
+    pub swap_type: SwapType,
+    pub buyer: String,
+    pub seller: String,
+    pub collection: String,
+    pub token_id: String,
+    pub payment_token: String,
+    pub price: Uint128,
+    pub fee: Uint128,
+    pub royalty: Uint128,
+    pub seller_amount: Uint128,
+    pub height: Option<u64>,
+    pub tx_hash: Option<String>,
+}
+
+/// A rent of a rental listing, from its `rent` event
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Rental {
+    pub contract: String,
+    pub sequence: u64,
+    pub swap_id: String,
+    pub renter: String,
+    pub collection: String,
+    pub token_id: String,
+    pub payment_token: String,
+    pub amount: Uint128,
+    pub rented_until: Expiration,
+}
+
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    pub ingested: usize,
+    pub duplicates: usize, // Events already indexed, skipped
+}
+
+/// Events of `contract` missing from the index, between sequences `after` and `before`
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Gap {
+    pub contract: String,
+    pub after: u64,
+    pub before: u64,
+}
+
+/// SQLite index of the marketplaces, rebuilt from their events
+pub struct Indexer {
+    conn: Connection,
+}
+
+impl Indexer {
+    /// Opens the index at `path`, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, IndexerError> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, IndexerError> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(conn: Connection) -> Result<Self, IndexerError> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Applies `events` to the index, in sequence order of each marketplace;
+    /// events already indexed are skipped, so inputs can overlap. Marketplaces
+    /// receiving events older than ones already indexed are rebuilt from all
+    /// their events. Events are applied in a single transaction, nothing is
+    /// indexed if one is invalid
+    pub fn ingest(
+        &mut self,
+        mut events: Vec<MarketplaceEvent>,
+    ) -> Result<IngestReport, IndexerError> {
+        events.sort_by(|a, b| (&a.contract, a.sequence).cmp(&(&b.contract, b.sequence)));
+        let mut report = IngestReport::default();
+        let mut rebuilt: BTreeSet<&str> = BTreeSet::new();
+        let tx = self.conn.transaction()?;
+        for event in &events {
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO events (contract, sequence, action, attributes, height, tx_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    event.contract,
+                    event.sequence,
+                    event.action,
+                    serde_json::to_string(&event.attributes)?,
+                    event.height,
+                    event.tx_hash,
+                ],
+            )?;
+            if inserted == 0 {
+                report.duplicates += 1;
+                continue;
+            }
+            report.ingested += 1;
+            if rebuilt.contains(event.contract.as_str()) || indexed_after(&tx, event)? {
+                rebuilt.insert(&event.contract);
+                continue;
+            }
+            apply(&tx, event)?;
+        }
+        for contract in rebuilt {
+            rebuild(&tx, contract)?;
+        }
+        tx.commit()?;
+        Ok(report)
+    }
+
+    /// Sequences missing from the indexed events of each marketplace
+    pub fn gaps(&self) -> Result<Vec<Gap>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT contract, previous, sequence FROM (
+                SELECT contract, sequence,
+                    COALESCE(LAG(sequence) OVER (PARTITION BY contract ORDER BY sequence), 0) AS previous
+                FROM events
+            ) WHERE sequence > previous + 1 ORDER BY contract, sequence",
+        )?;
+        let gaps = stmt
+            .query_map([], |row| {
+                Ok(Gap {
+                    contract: row.get(0)?,
+                    after: row.get(1)?,
+                    before: row.get(2)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(gaps)
+    }
+
+    /// Swap `id` of `contract`, active or closed
+    pub fn swap(&self, contract: &str, id: &str) -> Result<Option<IndexedSwap>, IndexerError> {
+        self.conn
+            .query_row(
+                &format!("SELECT {SWAP_COLUMNS} FROM swaps WHERE contract = ?1 AND id = ?2"),
+                params![contract, id],
+                |row| Ok(read_swap(row)),
+            )
+            .optional()?
+            .transpose()
+    }
+
+    /// Active sales and rental listings, of `contract` or of every marketplace
+    pub fn listings(&self, contract: Option<&str>) -> Result<Vec<IndexedSwap>, IndexerError> {
+        self.active(contract, &[SwapType::Sale, SwapType::Rental])
+    }
+
+    /// Active offers, trait offers and counter offers
+    pub fn offers(&self, contract: Option<&str>) -> Result<Vec<IndexedSwap>, IndexerError> {
+        self.active(
+            contract,
+            &[SwapType::Offer, SwapType::TraitOffer, SwapType::Counter],
+        )
+    }
+
+    /// Active swaps of every type
+    pub fn active_swaps(&self, contract: Option<&str>) -> Result<Vec<IndexedSwap>, IndexerError> {
+        let sql = format!(
+            "SELECT {SWAP_COLUMNS} FROM swaps
+             WHERE status = 'active' AND (?1 IS NULL OR contract = ?1)
+             ORDER BY contract, created_sequence"
+        );
+        self.select(&sql, contract, read_swap)
+    }
+
+    /// Sale history, oldest first
+    pub fn sales(&self, contract: Option<&str>) -> Result<Vec<Sale>, IndexerError> {
+        let sql = "SELECT contract, sequence, swap_id, swap_type, buyer, seller, collection,
+                token_id, payment_token, price, fee, royalty, seller_amount, height, tx_hash
+             FROM sales WHERE ?1 IS NULL OR contract = ?1 ORDER BY contract, sequence";
+        self.select(sql, contract, |row| {
+            Ok(Sale {
+                contract: row.get(0)?,
+                sequence: row.get(1)?,
+                swap_id: row.get(2)?,
+                swap_type: unquoted("swap_type", &row.get::<_, String>(3)?)?,
+                buyer: row.get(4)?,
+                seller: row.get(5)?,
+                collection: row.get(6)?,
+                token_id: row.get(7)?,
+                payment_token: row.get(8)?,
+                price: amount("price", &row.get::<_, String>(9)?)?,
+                fee: amount("fee", &row.get::<_, String>(10)?)?,
+                royalty: amount("royalty", &row.get::<_, String>(11)?)?,
+                seller_amount: amount("seller_amount", &row.get::<_, String>(12)?)?,
+                height: row.get(13)?,
+                tx_hash: row.get(14)?,
+            })
+        })
+    }
+
+    /// Rents of rental listings, oldest first
+    pub fn rentals(&self, contract: Option<&str>) -> Result<Vec<Rental>, IndexerError> {
+        let sql = "SELECT contract, sequence, swap_id, renter, collection, token_id,
+                payment_token, amount, rented_until
+             FROM rentals WHERE ?1 IS NULL OR contract = ?1 ORDER BY contract, sequence";
+        self.select(sql, contract, |row| {
+            Ok(Rental {
+                contract: row.get(0)?,
+                sequence: row.get(1)?,
+                swap_id: row.get(2)?,
+                renter: row.get(3)?,
+                collection: row.get(4)?,
+                token_id: row.get(5)?,
+                payment_token: row.get(6)?,
+                amount: amount("amount", &row.get::<_, String>(7)?)?,
+                rented_until: json("rented_until", &row.get::<_, String>(8)?)?,
+            })
+        })
+    }
+
+    fn active(
+        &self,
+        contract: Option<&str>,
+        swap_types: &[SwapType],
+    ) -> Result<Vec<IndexedSwap>, IndexerError> {
+        Ok(self
+            .active_swaps(contract)?
+            .into_iter()
+            .filter(|swap| swap_types.contains(&swap.swap_type))
+            .collect())
+    }
+
+    // Rows of `sql`, filtered by an optional `contract`
+    fn select<T>(
+        &self,
+        sql: &str,
+        contract: Option<&str>,
+        read: impl Fn(&Row) -> Result<T, IndexerError>,
+    ) -> Result<Vec<T>, IndexerError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt
+            .query_map(params![contract], |row| Ok(read(row)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter().collect()
+    }
+}
+
+fn read_swap(row: &Row) -> Result<IndexedSwap, IndexerError> {
+    Ok(IndexedSwap {
+        contract: row.get(0)?,
+        id: row.get(1)?,
+        swap_type: unquoted("swap_type", &row.get::<_, String>(2)?)?,
+        creator: row.get(3)?,
+        collection: row.get(4)?,
+        token_id: row.get(5)?,
+        payment_token: row.get(6)?,
+        price: amount("price", &row.get::<_, String>(7)?)?,
+        expires: json("expires", &row.get::<_, String>(8)?)?,
+        expires_at: row.get(9)?,
+        status: unquoted("status", &row.get::<_, String>(10)?)?,
+        filler: row.get(11)?,
+    })
+}
+
+// Expiration time of a created or updated swap, missing in events of marketplaces
+// older than `expires_at`
+fn expires_at(event: &MarketplaceEvent) -> Result<Option<u64>, IndexerError> {
+    Ok(event
+        .optional_value::<Uint64>("expires_at")?
+        .map(|time| time.u64()))
+}
+
+fn apply(tx: &Transaction, event: &MarketplaceEvent) -> Result<(), IndexerError> {
+    let contract = &event.contract;
+    match event.action.as_str() {
+        "create" => {
+            tx.execute(
+                "INSERT OR REPLACE INTO swaps (contract, id, swap_type, creator, collection,
+                    token_id, payment_token, price, expires, expires_at, status, created_sequence)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    contract,
+                    event.attribute("swap_id")?,
+                    stored(&event.value::<SwapType>("swap_type")?)?,
+                    event.attribute("creator")?,
+                    event.attribute("collection")?,
+                    event.attributes.get("token_id"),
+                    event.attribute("payment_token")?,
+                    event.value::<Uint128>("price")?.to_string(),
+                    stored_json(&event.json::<Expiration>("expires")?)?,
+                    expires_at(event)?,
+                    stored(&SwapStatus::Active)?,
+                    event.sequence,
+                ],
+            )?;
+        }
+        "update" => {
+            tx.execute(
+                "UPDATE swaps SET price = ?3, expires = ?4, expires_at = ?5,
+                    payment_token = COALESCE(?6, payment_token)
+                 WHERE contract = ?1 AND id = ?2",
+                params![
+                    contract,
+                    event.attribute("swap_id")?,
+                    event.value::<Uint128>("price")?.to_string(),
+                    stored_json(&event.json::<Expiration>("expires")?)?,
+                    expires_at(event)?,
+                    // Missing in events of marketplaces older than `UpdatePaymentToken`
+                    event.attributes.get("payment_token"),
+                ],
+            )?;
+        }
+        "sale" => {
+            let swap_type: SwapType = event.value("swap_type")?;
+            let buyer = event.attribute("buyer")?;
+            let seller = event.attribute("seller")?;
+            tx.execute(
+                "INSERT INTO sales (contract, sequence, swap_id, swap_type, buyer, seller,
+                    collection, token_id, payment_token, price, fee, royalty, seller_amount,
+                    height, tx_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                params![
+                    contract,
+                    event.sequence,
+                    event.attribute("swap_id")?,
+                    stored(&swap_type)?,
+                    buyer,
+                    seller,
+                    event.attribute("collection")?,
+                    event.attribute("token_id")?,
+                    event.attribute("payment_token")?,
+                    event.value::<Uint128>("price")?.to_string(),
+                    event.value::<Uint128>("fee")?.to_string(),
+                    event.value::<Uint128>("royalty")?.to_string(),
+                    event.value::<Uint128>("seller_amount")?.to_string(),
+                    event.height,
+                    event.tx_hash,
+                ],
+            )?;
+            // Offers are filled by the seller, other swaps by the buyer
+            let filler = match swap_type {
+                SwapType::Offer | SwapType::TraitOffer => seller,
+                _ => buyer,
+            };
+            // Trait offers are filled with a token of the collection
+            tx.execute(
+                "UPDATE swaps SET status = ?3, filler = ?4, token_id = ?5, closed_sequence = ?6
+                 WHERE contract = ?1 AND id = ?2",
+                params![
+                    contract,
+                    event.attribute("swap_id")?,
+                    stored(&SwapStatus::Filled)?,
+                    filler,
+                    event.attribute("token_id")?,
+                    event.sequence,
+                ],
+            )?;
+        }
+        "cancel" | "close" => {
+            tx.execute(
+                "UPDATE swaps SET status = ?3, closed_sequence = ?4 WHERE contract = ?1 AND id = ?2",
+                params![
+                    contract,
+                    event.attribute("swap_id")?,
+                    stored(&event.value::<SwapStatus>("status")?)?,
+                    event.sequence,
+                ],
+            )?;
+        }
+        "rent" => {
+            tx.execute(
+                "INSERT INTO rentals (contract, sequence, swap_id, renter, collection, token_id,
+                    payment_token, amount, rented_until)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    contract,
+                    event.sequence,
+                    event.attribute("swap_id")?,
+                    event.attribute("renter")?,
+                    event.attribute("collection")?,
+                    event.attribute("token_id")?,
+                    event.attribute("payment_token")?,
+                    event.value::<Uint128>("amount")?.to_string(),
+                    stored_json(&event.json::<Expiration>("rented_until")?)?,
+                ],
+            )?;
+        }
+        "add-nft" | "remove-nft" => {
+            tx.execute(
+                "INSERT OR REPLACE INTO collections (contract, collection, allowed) VALUES (?1, ?2, ?3)",
+                params![
+                    contract,
+                    event.attribute("collection")?,
+                    event.json::<bool>("allowed")?
+                ],
+            )?;
+        }
+        "update-config" => {
+            tx.execute(
+                "INSERT OR REPLACE INTO configs (contract, denom, fees, config) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    contract,
+                    event.attribute("denom")?,
+                    event.json::<u64>("fees")?,
+                    event.attribute("config")?,
+                ],
+            )?;
+        }
+        // Events of later versions are kept in `events` only
+        _ => {}
+    }
+    Ok(())
+}
+
+// Whether events of the marketplace of `event` were indexed after it
+fn indexed_after(tx: &Transaction, event: &MarketplaceEvent) -> Result<bool, IndexerError> {
+    Ok(tx.query_row(
+        "SELECT EXISTS (SELECT 1 FROM events WHERE contract = ?1 AND sequence > ?2)",
+        params![event.contract, event.sequence],
+        |row| row.get(0),
+    )?)
+}
+
+// Reapplies every indexed event of `contract` in sequence order, so events
+// indexed late don't apply on top of newer ones
+fn rebuild(tx: &Transaction, contract: &str) -> Result<(), IndexerError> {
+    for table in ["swaps", "sales", "rentals", "collections", "configs"] {
+        tx.execute(
+            &format!("DELETE FROM {table} WHERE contract = ?1"),
+            params![contract],
+        )?;
+    }
+    let mut stmt = tx.prepare(
+        "SELECT sequence, action, attributes, height, tx_hash FROM events
+         WHERE contract = ?1 ORDER BY sequence",
+    )?;
+    let rows = stmt
+        .query_map(params![contract], |row| {
+            Ok((
+                row.get::<_, u64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<u64>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (sequence, action, attributes, height, tx_hash) in rows {
+        let event = MarketplaceEvent {
+            contract: contract.to_string(),
+            action,
+            sequence,
+            attributes: json("attributes", &attributes)?,
+            height,
+            tx_hash,
+        };
+        apply(tx, &event)?;
+    }
+    Ok(())
+}
+
+// Unit enum variants are stored as their json strings, unquoted
+fn stored(value: &impl Serialize) -> Result<String, IndexerError> {
+    match serde_json::to_value(value)? {
+        Value::String(value) => Ok(value),
+        value => Ok(value.to_string()),
+    }
+}
+
+fn stored_json(value: &impl Serialize) -> Result<String, IndexerError> {
+    Ok(serde_json::to_string(value)?)
+}
+
+fn unquoted<T: DeserializeOwned>(field: &str, value: &str) -> Result<T, IndexerError> {
+    serde_json::from_value(Value::String(value.to_string())).map_err(|_| invalid(field, value))
+}
+
+fn json<T: DeserializeOwned>(field: &str, value: &str) -> Result<T, IndexerError> {
+    serde_json::from_str(value).map_err(|_| invalid(field, value))
+}
+
+fn amount(field: &str, value: &str) -> Result<Uint128, IndexerError> {
+    Uint128::from_str(value).map_err(|_| invalid(field, value))
+}
+
+fn invalid(field: &str, value: &str) -> IndexerError {
+    IndexerError::InvalidRow {
+        field: field.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn event(action: &str, sequence: u64, attributes: &[(&str, &str)]) -> MarketplaceEvent {
+        MarketplaceEvent {
+            contract: "archway1marketplace".to_string(),
+            action: action.to_string(),
+            sequence,
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            height: None,
+            tx_hash: None,
+        }
+    }
+
+    #[test]
+    fn events_ingested_out_of_order() {
+        let create = event(
+            "create",
+            1,
+            &[
+                ("swap_id", "swap1"),
+                ("swap_type", "Sale"),
+                ("creator", "archway1seller"),
+                ("collection", "archway1nft"),
+                ("token_id", "token1"),
+                ("payment_token", "aarch"),
+                ("price", "1000"),
+                ("expires", r#"{"never":{}}"#),
+            ],
+        );
+        let sale = event(
+            "sale",
+            2,
+            &[
+                ("swap_id", "swap1"),
+                ("swap_type", "Sale"),
+                ("buyer", "archway1buyer"),
+                ("seller", "archway1seller"),
+                ("collection", "archway1nft"),
+                ("token_id", "token1"),
+                ("payment_token", "aarch"),
+                ("price", "1000"),
+                ("fee", "10"),
+                ("royalty", "0"),
+                ("seller_amount", "990"),
+            ],
+        );
+
+        // The create of a sale indexed late doesn't reopen the sale
+        let mut indexer = Indexer::open_in_memory().unwrap();
+        indexer.ingest(vec![sale]).unwrap();
+        let report = indexer.ingest(vec![create]).unwrap();
+        assert_eq!(report.ingested, 1);
+        assert!(indexer.gaps().unwrap().is_empty());
+        let swap = indexer
+            .swap("archway1marketplace", "swap1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(swap.status, SwapStatus::Filled);
+        assert_eq!(swap.filler.as_deref(), Some("archway1buyer"));
+        assert!(indexer.active_swaps(None).unwrap().is_empty());
+        assert_eq!(indexer.sales(None).unwrap().len(), 1);
+    }
+}
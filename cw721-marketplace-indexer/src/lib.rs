@@ -0,0 +1,9 @@
+mod error;
+mod events;
+mod index;
+mod verify;
+
+pub use crate::error::IndexerError;
+pub use crate::events::{parse_events, MarketplaceEvent};
+pub use crate::index::{Gap, IndexedSwap, Indexer, IngestReport, Rental, Sale};
+pub use crate::verify::{Mismatch, StateDump};
@@ -0,0 +1,110 @@
+//! Rebuilds the state of the marketplaces into SQLite from their events
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use cw721_marketplace_indexer::{parse_events, Indexer, StateDump};
+
+#[derive(Parser)]
+#[command(name = "marketplace-indexer", version, about)]
+struct Cli {
+    /// SQLite database of the index, created if missing
+    #[arg(long, default_value = "marketplace.sqlite")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Indexes the marketplace events of transaction or event json files (stdin if none)
+    Ingest { files: Vec<PathBuf> },
+    /// Prints the active sales and rental listings
+    Listings {
+        #[arg(long)]
+        contract: Option<String>,
+    },
+    /// Prints the active offers, trait offers and counter offers
+    Offers {
+        #[arg(long)]
+        contract: Option<String>,
+    },
+    /// Prints the sale history
+    Sales {
+        #[arg(long)]
+        contract: Option<String>,
+    },
+    /// Prints the rents of rental listings
+    Rentals {
+        #[arg(long)]
+        contract: Option<String>,
+    },
+    /// Prints the sequences of the events missing from the index
+    Gaps,
+    /// Checks the index of a marketplace against a `StateDump` json file of its
+    /// `List` and `Details` responses, failing if they differ
+    Verify {
+        #[arg(long)]
+        contract: String,
+        dump: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut indexer = Indexer::open(&cli.db)
+        .with_context(|| format!("can't open the index {}", cli.db.display()))?;
+    match cli.command {
+        Command::Ingest { files } => {
+            let mut events = vec![];
+            if files.is_empty() {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                events.extend(parse_events(&input).context("invalid events from stdin")?);
+            }
+            for file in files {
+                let input = fs::read_to_string(&file)
+                    .with_context(|| format!("can't read {}", file.display()))?;
+                events.extend(
+                    parse_events(&input)
+                        .with_context(|| format!("invalid events in {}", file.display()))?,
+                );
+            }
+            print_json(&indexer.ingest(events)?)?;
+            for gap in indexer.gaps()? {
+                eprintln!(
+                    "warning: events {}..{} of {} are missing",
+                    gap.after + 1,
+                    gap.before,
+                    gap.contract
+                );
+            }
+            Ok(())
+        }
+        Command::Listings { contract } => print_json(&indexer.listings(contract.as_deref())?),
+        Command::Offers { contract } => print_json(&indexer.offers(contract.as_deref())?),
+        Command::Sales { contract } => print_json(&indexer.sales(contract.as_deref())?),
+        Command::Rentals { contract } => print_json(&indexer.rentals(contract.as_deref())?),
+        Command::Gaps => print_json(&indexer.gaps()?),
+        Command::Verify { contract, dump } => {
+            let dump = fs::read_to_string(&dump)
+                .with_context(|| format!("can't read {}", dump.display()))?;
+            let dump: StateDump = serde_json::from_str(&dump).context("invalid state dump")?;
+            let mismatches = indexer.verify(&contract, &dump)?;
+            if !mismatches.is_empty() {
+                print_json(&mismatches)?;
+                bail!("the index of {contract} doesn't match its state");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
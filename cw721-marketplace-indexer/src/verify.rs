@@ -0,0 +1,329 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use cw721_marketplace_utils::prelude::{DetailsResponse, ListResponse, SwapStatus};
+
+use crate::error::IndexerError;
+use crate::index::{IndexedSwap, Indexer};
+
+/// State of a marketplace, as queried from the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StateDump {
+    /// Native denom of the marketplace, the `denom` of its `Config`
+    pub denom: String,
+    /// `List` response, the ids of all active swaps
+    pub list: ListResponse,
+    /// `Details` responses, by swap id; swaps closed or not, and not necessarily all of them
+    #[serde(default)]
+    pub details: BTreeMap<String, DetailsResponse>,
+}
+
+/// Field of a swap whose indexed value isn't the `expected` value of the dump
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub swap_id: String,
+    pub field: String,
+    pub indexed: String,
+    pub expected: String,
+}
+
+impl Indexer {
+    /// Differences between the index of `contract` and its state `dump`:
+    /// active swaps must be those of `List`, and swaps of `Details` must
+    /// have the same fields and status. No mismatches if the index is in sync
+    pub fn verify(&self, contract: &str, dump: &StateDump) -> Result<Vec<Mismatch>, IndexerError> {
+        let mut mismatches = vec![];
+
+        let listed: BTreeSet<&str> = dump.list.swaps.iter().map(String::as_str).collect();
+        let active: Vec<IndexedSwap> = self.active_swaps(Some(contract))?;
+        let indexed: BTreeSet<&str> = active.iter().map(|swap| swap.id.as_str()).collect();
+        // Swaps with `Details` are compared field by field below
+        let unlisted = |id: &&&str| !dump.details.contains_key(**id);
+        for id in listed.difference(&indexed).filter(unlisted) {
+            let indexed = match self.swap(contract, id)? {
+                Some(swap) => status(&swap.status),
+                None => "missing".to_string(),
+            };
+            mismatches.push(mismatch(id, "status", indexed, status(&SwapStatus::Active)));
+        }
+        for id in indexed.difference(&listed).filter(unlisted) {
+            mismatches.push(mismatch(
+                id,
+                "status",
+                status(&SwapStatus::Active),
+                "not listed",
+            ));
+        }
+
+        for (id, details) in &dump.details {
+            let Some(swap) = self.swap(contract, id)? else {
+                mismatches.push(mismatch(id, "swap", "missing", "indexed"));
+                continue;
+            };
+            let payment_token = match &details.payment_token {
+                Some(token) => token.to_string(),
+                None => dump.denom.clone(),
+            };
+            let filler = match details.status {
+                SwapStatus::Filled => details.filler.as_ref().map(|filler| filler.to_string()),
+                _ => swap.filler.clone(),
+            };
+            let fields = [
+                ("creator", swap.creator.clone(), details.creator.to_string()),
+                (
+                    "collection",
+                    swap.collection.clone(),
+                    details.contract.to_string(),
+                ),
+                (
+                    "token_id",
+                    swap.token_id.clone().unwrap_or_default(),
+                    details.token_id.clone(),
+                ),
+                ("payment_token", swap.payment_token.clone(), payment_token),
+                ("price", swap.price.to_string(), details.price.to_string()),
+                (
+                    "swap_type",
+                    json(&swap.swap_type)?,
+                    json(&details.swap_type)?,
+                ),
+                ("expires", json(&swap.expires)?, json(&details.expires)?),
+                ("status", status(&swap.status), status(&details.status)),
+                (
+                    "filler",
+                    swap.filler.clone().unwrap_or_default(),
+                    filler.unwrap_or_default(),
+                ),
+            ];
+            for (field, indexed, expected) in fields {
+                if indexed != expected {
+                    mismatches.push(mismatch(id, field, indexed, expected));
+                }
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+fn mismatch(
+    swap_id: &str,
+    field: &str,
+    indexed: impl Into<String>,
+    expected: impl Into<String>,
+) -> Mismatch {
+    Mismatch {
+        swap_id: swap_id.to_string(),
+        field: field.to_string(),
+        indexed: indexed.into(),
+        expected: expected.into(),
+    }
+}
+
+fn json(value: &impl Serialize) -> Result<String, IndexerError> {
+    Ok(serde_json::to_string(value)?)
+}
+
+// Statuses as their json, unquoted
+fn status(status: &SwapStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{Addr, CosmosMsg, Event, Uint128};
+    use cw20::{Cw20ExecuteMsg, Expiration};
+    use cw_multi_test::{App, Executor};
+
+    use cw721_marketplace_interface::multitest::{
+        create_cw20, create_cw721, create_swap_with_fees, mint_and_approve, mint_native, mock_app,
+        MultiTestVariant,
+    };
+    use cw721_marketplace_interface::{
+        MarketplaceContract, OpenMarketplace, PermissionedMarketplace, SingleCollectionMarketplace,
+    };
+    use cw721_marketplace_utils::prelude::{MarketConfig, SwapType};
+
+    use super::*;
+    use crate::events::{parse_events, MarketplaceEvent};
+
+    // Events of every transaction, as the fixture json of the multi-test suite
+    #[derive(Default)]
+    struct Fixture(Vec<Vec<Event>>);
+
+    impl Fixture {
+        fn execute(&mut self, app: &mut App, sender: &Addr, msg: CosmosMsg) {
+            let res = app.execute(sender.clone(), msg).unwrap();
+            self.0.push(res.events);
+        }
+
+        fn events(&self) -> Vec<MarketplaceEvent> {
+            parse_events(&serde_json::to_string(&self.0).unwrap()).unwrap()
+        }
+    }
+
+    // State dump of the `Details` of every swap created in `events`
+    fn dump<V: MultiTestVariant>(
+        app: &App,
+        swap: &MarketplaceContract<V>,
+        events: &[MarketplaceEvent],
+    ) -> StateDump {
+        let details = events
+            .iter()
+            .filter(|event| event.action == "create")
+            .map(|event| {
+                let id = event.attribute("swap_id").unwrap().to_string();
+                let details = swap.query_details(&app.wrap(), &id).unwrap();
+                (id, details)
+            })
+            .collect();
+        StateDump {
+            denom: swap.query_config(&app.wrap()).unwrap().denom().to_string(),
+            list: swap.query_list(&app.wrap(), None, None).unwrap(),
+            details,
+        }
+    }
+
+    // Index rebuilt from the events of a multi-test run matches the marketplace
+    fn index_rebuilt<V: MultiTestVariant>() {
+        let mut app = mock_app();
+        let mut fixture = Fixture::default();
+
+        let swap_admin = Addr::unchecked("swap_deployer");
+        let cw721_owner = Addr::unchecked("original_owner");
+        let buyer = Addr::unchecked("buyer");
+        let bidder = Addr::unchecked("bidder");
+
+        let nft = create_cw721(&mut app, &cw721_owner);
+        let swap = create_swap_with_fees::<V>(&mut app, &swap_admin, nft.clone(), 10);
+        for token_id in ["token1", "token2", "token3"] {
+            mint_and_approve(&mut app, swap.addr(), &nft, &cw721_owner, token_id);
+        }
+        mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u32));
+        let cw20 = create_cw20(
+            &mut app,
+            &bidder,
+            "Token".to_string(),
+            "TKN".to_string(),
+            Uint128::from(1000_u32),
+        );
+        app.execute_contract(
+            bidder.clone(),
+            cw20.clone(),
+            &Cw20ExecuteMsg::IncreaseAllowance {
+                spender: swap.addr().to_string(),
+                amount: Uint128::from(1000_u32),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let price = Uint128::from(500_u32);
+        for token_id in ["token1", "token2", "token3"] {
+            let msg = swap
+                .create_sale(&nft, token_id, price, None, Expiration::Never {})
+                .unwrap();
+            fixture.execute(&mut app, &cw721_owner, msg);
+        }
+        let offer_expires_at = app.block_info().time.plus_seconds(3600);
+        let msg = swap
+            .create_offer(
+                &nft,
+                "token3",
+                Uint128::from(300_u32),
+                cw20.clone(),
+                Expiration::AtTime(offer_expires_at),
+            )
+            .unwrap();
+        fixture.execute(&mut app, &bidder, msg);
+
+        let listings = swap.query_listings(&app.wrap(), None, None).unwrap().swaps;
+        let (token1, token2, token3) = (
+            listings[0].clone(),
+            listings[1].clone(),
+            listings[2].clone(),
+        );
+        let msg = swap
+            .update(&token2.id, Uint128::from(800_u32), Expiration::Never {})
+            .unwrap();
+        fixture.execute(&mut app, &cw721_owner, msg);
+        let msg = swap.buy(&token1, "aarch").unwrap();
+        fixture.execute(&mut app, &buyer, msg);
+        let msg = swap.cancel(&token2.id).unwrap();
+        fixture.execute(&mut app, &cw721_owner, msg);
+        let msg = swap
+            .update_payment_token(&token3.id, Some(cw20.clone()), Uint128::from(400_u32))
+            .unwrap();
+        fixture.execute(&mut app, &cw721_owner, msg);
+
+        let events = fixture.events();
+        let mut indexer = Indexer::open_in_memory().unwrap();
+        let report = indexer.ingest(events.clone()).unwrap();
+        assert_eq!(report.ingested, 8);
+        assert!(indexer.gaps().unwrap().is_empty());
+
+        let contract = swap.addr().as_str();
+        let dump = dump(&app, &swap, &events);
+        assert_eq!(indexer.verify(contract, &dump).unwrap(), vec![]);
+
+        let listings = indexer.listings(Some(contract)).unwrap();
+        assert_eq!(listings.len(), 1);
+        assert_eq!(listings[0].token_id.as_deref(), Some("token3"));
+        assert_eq!(listings[0].expires_at, None);
+        assert_eq!(listings[0].payment_token, cw20.as_str());
+        assert_eq!(listings[0].price, Uint128::from(400_u32));
+        let offers = indexer.offers(Some(contract)).unwrap();
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].swap_type, SwapType::Offer);
+        assert_eq!(offers[0].expires_at, Some(offer_expires_at.seconds()));
+        let sales = indexer.sales(Some(contract)).unwrap();
+        assert_eq!(sales.len(), 1);
+        assert_eq!(sales[0].swap_id, token1.id);
+        assert_eq!(sales[0].buyer, buyer.as_str());
+        assert_eq!(sales[0].fee, Uint128::from(50_u32));
+        assert_eq!(sales[0].seller_amount, Uint128::from(450_u32));
+        let token2 = indexer.swap(contract, &token2.id).unwrap().unwrap();
+        assert_eq!(token2.status, SwapStatus::Cancelled);
+        assert_eq!(token2.price, Uint128::from(800_u32));
+
+        // Inputs can overlap
+        let report = indexer.ingest(events.clone()).unwrap();
+        assert_eq!(report.ingested, 0);
+        assert_eq!(report.duplicates, 8);
+
+        // A missed sale is detected
+        let mut indexer = Indexer::open_in_memory().unwrap();
+        let missed: Vec<MarketplaceEvent> = events
+            .into_iter()
+            .filter(|event| event.action != "sale")
+            .collect();
+        indexer.ingest(missed).unwrap();
+        assert_eq!(indexer.gaps().unwrap().len(), 1);
+        assert_eq!(
+            indexer.verify(contract, &dump).unwrap(),
+            vec![
+                mismatch(&token1.id, "status", "active", "filled"),
+                mismatch(&token1.id, "filler", "", buyer.as_str()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_marketplace() {
+        index_rebuilt::<OpenMarketplace>();
+    }
+
+    #[test]
+    fn test_permissioned_marketplace() {
+        index_rebuilt::<PermissionedMarketplace>();
+    }
+
+    #[test]
+    fn test_single_collection_marketplace() {
+        index_rebuilt::<SingleCollectionMarketplace>();
+    }
+}
@@ -0,0 +1,270 @@
+use std::marker::PhantomData;
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+use cw20::Expiration;
+use serde::{de::DeserializeOwned, Serialize};
+
+use cw721_marketplace_utils::msg::{
+    CancelMsg, ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg, SweepMsg, UpdateMsg,
+    UpdatePaymentTokenMsg,
+};
+use cw721_marketplace_utils::prelude::{
+    CW721Swap, DetailsResponse, ListResponse, PageResult, SimulateFinishResponse, SwapType,
+};
+
+use cw721_marketplace_permissioned::msg::{
+    ExecuteMsg as PermissionedExecuteMsg, RequestListingMsg, UpdateNftMsg,
+};
+
+use crate::variant::{MarketplaceVariant, PermissionedMarketplace};
+
+/// A deployed marketplace of variant `V`, building its messages and
+/// queries. Entry points with the same json in every marketplace are
+/// built from the shared `cw721_marketplace_utils::msg` messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarketplaceContract<V> {
+    addr: Addr,
+    variant: PhantomData<V>,
+}
+
+impl<V: MarketplaceVariant> MarketplaceContract<V> {
+    pub fn new(addr: Addr) -> Self {
+        Self {
+            addr,
+            variant: PhantomData,
+        }
+    }
+
+    pub fn addr(&self) -> &Addr {
+        &self.addr
+    }
+
+    /// Executes `msg` of the marketplace with `funds`
+    pub fn call(&self, msg: &V::ExecuteMsg, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.execute(msg, funds)
+    }
+
+    /// Queries `msg` of the marketplace, decoding its response as `T`
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        msg: &V::QueryMsg,
+    ) -> StdResult<T> {
+        querier.query_wasm_smart(&self.addr, msg)
+    }
+
+    /// Creates `swap`; offers are paid in cw20s, which the
+    /// marketplace must be allowed to spend when they're accepted
+    pub fn create(&self, swap: SwapMsg) -> StdResult<CosmosMsg> {
+        self.execute(&V::create_msg(swap), vec![])
+    }
+
+    /// Lists `token_id` of `cw721` for sale at `price` of cw20
+    /// `payment_token`, or of the native denom if `None`
+    pub fn create_sale(
+        &self,
+        cw721: &Addr,
+        token_id: &str,
+        price: Uint128,
+        payment_token: Option<Addr>,
+        expires: Expiration,
+    ) -> StdResult<CosmosMsg> {
+        self.create(SwapMsg {
+            id: None,
+            cw721: cw721.clone(),
+            payment_token,
+            token_id: token_id.to_string(),
+            expires,
+            price,
+            swap_type: SwapType::Sale,
+        })
+    }
+
+    /// Offers `price` of cw20 `payment_token` for `token_id` of `cw721`
+    pub fn create_offer(
+        &self,
+        cw721: &Addr,
+        token_id: &str,
+        price: Uint128,
+        payment_token: Addr,
+        expires: Expiration,
+    ) -> StdResult<CosmosMsg> {
+        self.create(SwapMsg {
+            id: None,
+            cw721: cw721.clone(),
+            payment_token: Some(payment_token),
+            token_id: token_id.to_string(),
+            expires,
+            price,
+            swap_type: SwapType::Offer,
+        })
+    }
+
+    /// Fills swap `id` with `funds`
+    pub fn finish(&self, id: &str, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::Finish(FinishSwapMsg { id: id.to_string() });
+        self.execute(&msg, funds)
+    }
+
+    /// Buys `swap`, sending its price in `denom` if it's a native sale
+    pub fn buy(&self, swap: &CW721Swap, denom: &str) -> StdResult<CosmosMsg> {
+        let funds = match swap.payment_token {
+            Some(_) => vec![],
+            None => vec![Coin {
+                denom: denom.to_string(),
+                amount: swap.price,
+            }],
+        };
+        self.finish(&swap.id, funds)
+    }
+
+    pub fn cancel(&self, id: &str) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::Cancel(CancelMsg { id: id.to_string() });
+        self.execute(&msg, vec![])
+    }
+
+    pub fn update(&self, id: &str, price: Uint128, expires: Expiration) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::Update(UpdateMsg {
+            id: id.to_string(),
+            expires,
+            price,
+        });
+        self.execute(&msg, vec![])
+    }
+
+    /// Prices swap `id` in `payment_token`, or in the native denom if `None`
+    pub fn update_payment_token(
+        &self,
+        id: &str,
+        payment_token: Option<Addr>,
+        price: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::UpdatePaymentToken(UpdatePaymentTokenMsg {
+            id: id.to_string(),
+            payment_token,
+            price,
+        });
+        self.execute(&msg, vec![])
+    }
+
+    /// Buys the cheapest listings of `sweep.cw721`; native sweeps are paid
+    /// with `funds`, and the unspent funds are refunded
+    pub fn sweep(&self, sweep: SweepMsg, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        self.execute(&V::sweep_msg(sweep), funds)
+    }
+
+    /// All swaps, by id
+    pub fn query_list(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListResponse> {
+        querier.query_wasm_smart(&self.addr, &QueryMsg::List { start_after, limit })
+    }
+
+    /// All sales
+    pub fn query_listings(
+        &self,
+        querier: &QuerierWrapper,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> StdResult<PageResult> {
+        querier.query_wasm_smart(&self.addr, &QueryMsg::GetListings { page, limit })
+    }
+
+    /// All offers
+    pub fn query_offers(
+        &self,
+        querier: &QuerierWrapper,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> StdResult<PageResult> {
+        querier.query_wasm_smart(&self.addr, &QueryMsg::GetOffers { page, limit })
+    }
+
+    /// Swaps of `token_id` of `cw721`, of `swap_type` or of any type if `None`
+    pub fn query_listings_of_token(
+        &self,
+        querier: &QuerierWrapper,
+        cw721: &Addr,
+        token_id: &str,
+        swap_type: Option<SwapType>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> StdResult<PageResult> {
+        let msg =
+            V::listings_of_token_msg(cw721.clone(), token_id.to_string(), swap_type, page, limit);
+        self.query(querier, &msg)
+    }
+
+    /// Swap `id`, open or closed
+    pub fn query_details(&self, querier: &QuerierWrapper, id: &str) -> StdResult<DetailsResponse> {
+        let msg = QueryMsg::Details { id: id.to_string() };
+        querier.query_wasm_smart(&self.addr, &msg)
+    }
+
+    /// Error or transfers of finishing swap `id` by `sender` with `funds`
+    pub fn query_simulate_finish(
+        &self,
+        querier: &QuerierWrapper,
+        id: &str,
+        sender: &Addr,
+        funds: Vec<Coin>,
+    ) -> StdResult<SimulateFinishResponse> {
+        let msg = QueryMsg::SimulateFinish {
+            id: id.to_string(),
+            sender: sender.clone(),
+            funds,
+        };
+        querier.query_wasm_smart(&self.addr, &msg)
+    }
+
+    pub fn query_config(&self, querier: &QuerierWrapper) -> StdResult<V::Config> {
+        querier.query_wasm_smart(&self.addr, &QueryMsg::Config {})
+    }
+
+    fn execute(&self, msg: &impl Serialize, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr.to_string(),
+            msg: to_json_binary(msg)?,
+            funds,
+        }
+        .into())
+    }
+}
+
+impl MarketplaceContract<PermissionedMarketplace> {
+    /// Adds `cw721` to the allowlist, sent by a curator
+    pub fn add_nft(&self, cw721: &Addr) -> StdResult<CosmosMsg> {
+        let msg = PermissionedExecuteMsg::AddNft(UpdateNftMsg {
+            cw721: cw721.clone(),
+        });
+        self.call(&msg, vec![])
+    }
+
+    /// Removes `cw721` from the allowlist, sent by a curator
+    pub fn remove_nft(&self, cw721: &Addr) -> StdResult<CosmosMsg> {
+        let msg = PermissionedExecuteMsg::RemoveNft(UpdateNftMsg {
+            cw721: cw721.clone(),
+        });
+        self.call(&msg, vec![])
+    }
+
+    /// Requests `cw721` to be allowlisted, paying the listing fee
+    /// with `funds` (none if the marketplace doesn't charge one)
+    pub fn request_listing(
+        &self,
+        cw721: &Addr,
+        metadata: &str,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = PermissionedExecuteMsg::RequestListing(RequestListingMsg {
+            cw721: cw721.clone(),
+            metadata: metadata.to_string(),
+        });
+        self.call(&msg, funds)
+    }
+}
@@ -0,0 +1,67 @@
+use cosmwasm_std::{Addr, Uint128};
+
+use cw721_marketplace_utils::payment::fee_split;
+use cw721_marketplace_utils::prelude::{weighted_shares, Config, FeeRecipient, MarketConfig};
+use cw721_marketplace_utils::FeeSplit;
+
+/// Split of `price` between the marketplace and the seller when a swap
+/// is settled by a marketplace with `config`. As in settlement, the seller
+/// is paid the whole price if the fee would take all of it
+pub fn quote_fees(config: &impl MarketConfig, price: Uint128) -> FeeSplit {
+    fee_split(config, price).unwrap_or(FeeSplit::only_seller(price))
+}
+
+/// Split of `price` by a marketplace charging `fee_percentage` (ie: 1 = 1%)
+pub fn quote_fee_percentage(fee_percentage: u64, price: Uint128) -> FeeSplit {
+    let config = Config {
+        admin: None,
+        denom: String::new(),
+        fees: fee_percentage,
+    };
+    quote_fees(&config, price)
+}
+
+/// Shares of the marketplace fee of `split` paid to each of the marketplace's
+/// fee `recipients`; without recipients the fee is kept by the marketplace
+pub fn quote_fee_shares(split: &FeeSplit, recipients: &[FeeRecipient]) -> Vec<(Addr, Uint128)> {
+    weighted_shares(split.marketplace, recipients)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quote_mirrors_fee_split() {
+        let split = quote_fee_percentage(10, Uint128::new(1000));
+        assert_eq!(split.marketplace, Uint128::new(100));
+        assert_eq!(split.seller, Uint128::new(900));
+
+        // Fees rounding down to nothing
+        let split = quote_fee_percentage(10, Uint128::new(9));
+        assert_eq!(split, FeeSplit::only_seller(Uint128::new(9)));
+
+        // Fees taking the whole price aren't charged
+        let split = quote_fee_percentage(100, Uint128::new(1000));
+        assert_eq!(split, FeeSplit::only_seller(Uint128::new(1000)));
+
+        let recipients = vec![
+            FeeRecipient {
+                address: Addr::unchecked("treasury"),
+                weight: 3,
+            },
+            FeeRecipient {
+                address: Addr::unchecked("partner"),
+                weight: 1,
+            },
+        ];
+        let split = quote_fee_percentage(20, Uint128::new(1000));
+        assert_eq!(
+            quote_fee_shares(&split, &recipients),
+            vec![
+                (Addr::unchecked("treasury"), Uint128::new(150)),
+                (Addr::unchecked("partner"), Uint128::new(50)),
+            ]
+        );
+    }
+}
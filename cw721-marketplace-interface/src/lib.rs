@@ -0,0 +1,11 @@
+mod contract;
+mod fees;
+#[cfg(any(test, feature = "testing"))]
+pub mod multitest;
+mod variant;
+
+pub use crate::contract::MarketplaceContract;
+pub use crate::fees::{quote_fee_percentage, quote_fee_shares, quote_fees};
+pub use crate::variant::{
+    MarketplaceVariant, OpenMarketplace, PermissionedMarketplace, SingleCollectionMarketplace,
+};
@@ -0,0 +1,126 @@
+//! Multi-test helpers for testing against the marketplaces, with the `testing` feature
+use cosmwasm_std::Addr;
+use cw_multi_test::App;
+
+pub use cw721_marketplace_utils::testing::{
+    bank_query, create_cw20, create_cw721, mint_and_approve, mint_native, mock_app, query,
+    Marketplace,
+};
+
+use crate::contract::MarketplaceContract;
+use crate::variant::{
+    MarketplaceVariant, OpenMarketplace, PermissionedMarketplace, SingleCollectionMarketplace,
+};
+
+/// A marketplace variant which can be run in a multi-test `App`
+pub trait MultiTestVariant: MarketplaceVariant {
+    /// Multi-test wrapper of the marketplace contract
+    type Contract: Marketplace;
+}
+
+impl MultiTestVariant for OpenMarketplace {
+    type Contract = cw721_marketplace::multitest::Swap721;
+}
+
+impl MultiTestVariant for PermissionedMarketplace {
+    type Contract = cw721_marketplace_permissioned::multitest::Swap721;
+}
+
+impl MultiTestVariant for SingleCollectionMarketplace {
+    type Contract = cw721_marketplace_single_collection::multitest::Swap721;
+}
+
+/// Instantiates a marketplace of variant `V` without fees, administered by `owner`,
+/// where `cw721` can be traded; its native denom is `aarch`
+pub fn create_swap<V: MultiTestVariant>(
+    router: &mut App,
+    owner: &Addr,
+    cw721: Addr,
+) -> MarketplaceContract<V> {
+    create_swap_with_fees(router, owner, cw721, 0)
+}
+
+/// Instantiates a marketplace of variant `V` charging `fees` percent of each swap
+pub fn create_swap_with_fees<V: MultiTestVariant>(
+    router: &mut App,
+    owner: &Addr,
+    cw721: Addr,
+    fees: u64,
+) -> MarketplaceContract<V> {
+    let addr = cw721_marketplace_utils::testing::create_swap_with_fees::<V::Contract>(
+        router, owner, cw721, fees,
+    );
+    MarketplaceContract::new(addr)
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::Expiration;
+    use cw_multi_test::Executor;
+
+    use cw721_marketplace_utils::prelude::{MarketConfig, SwapStatus};
+
+    use super::*;
+    use crate::fees::quote_fees;
+
+    // Sale listed, found and bought through the typed helpers
+    fn sale_bought<V: MultiTestVariant>() {
+        let mut app = mock_app();
+
+        let swap_admin = Addr::unchecked("swap_deployer");
+        let cw721_owner = Addr::unchecked("original_owner");
+        let buyer = Addr::unchecked("buyer");
+
+        let nft = create_cw721(&mut app, &cw721_owner);
+        let swap = create_swap_with_fees::<V>(&mut app, &swap_admin, nft.clone(), 10);
+        mint_and_approve(&mut app, swap.addr(), &nft, &cw721_owner, "token1");
+        mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u32));
+
+        let msg = swap
+            .create_sale(
+                &nft,
+                "token1",
+                Uint128::from(500_u32),
+                None,
+                Expiration::Never {},
+            )
+            .unwrap();
+        app.execute(cw721_owner.clone(), msg).unwrap();
+
+        let listings = swap.query_listings(&app.wrap(), None, None).unwrap();
+        assert_eq!(listings.total, 1);
+        let listing = listings.swaps[0].clone();
+        let of_token = swap
+            .query_listings_of_token(&app.wrap(), &nft, "token1", None, None, None)
+            .unwrap();
+        assert_eq!(of_token.swaps, vec![listing.clone()]);
+
+        let config = swap.query_config(&app.wrap()).unwrap();
+        let split = quote_fees(&config, listing.price);
+        app.execute(buyer.clone(), swap.buy(&listing, config.denom()).unwrap())
+            .unwrap();
+
+        let details = swap.query_details(&app.wrap(), &listing.id).unwrap();
+        assert_eq!(details.status, SwapStatus::Filled);
+        assert_eq!(details.filler, Some(buyer));
+        let balance = bank_query(&app, &cw721_owner);
+        assert_eq!(balance.amount, split.seller);
+        assert_eq!(split.seller, Uint128::from(450_u32));
+    }
+
+    #[test]
+    fn test_open_marketplace() {
+        sale_bought::<OpenMarketplace>();
+    }
+
+    #[test]
+    fn test_permissioned_marketplace() {
+        sale_bought::<PermissionedMarketplace>();
+    }
+
+    #[test]
+    fn test_single_collection_marketplace() {
+        sale_bought::<SingleCollectionMarketplace>();
+    }
+}
@@ -0,0 +1,148 @@
+use cosmwasm_std::Addr;
+use serde::Serialize;
+
+use cw721_marketplace_utils::msg::{SwapMsg, SweepMsg};
+use cw721_marketplace_utils::prelude::{MarketConfig, SwapType};
+
+use cw721_marketplace_permissioned::msg as permissioned;
+use cw721_marketplace_single_collection::msg as single_collection;
+
+/// A marketplace contract and its message schemas. Most entry points have the
+/// same json in every marketplace; those which don't are built by the variant
+pub trait MarketplaceVariant {
+    type InstantiateMsg: Serialize;
+    type ExecuteMsg: Serialize;
+    type QueryMsg: Serialize;
+    type Config: MarketConfig;
+
+    /// `ExecuteMsg::Create` of `swap`
+    fn create_msg(swap: SwapMsg) -> Self::ExecuteMsg;
+
+    /// `ExecuteMsg::Sweep` of `sweep`
+    fn sweep_msg(sweep: SweepMsg) -> Self::ExecuteMsg;
+
+    /// `QueryMsg::ListingsOfToken` of `token_id` of collection `cw721`
+    fn listings_of_token_msg(
+        cw721: Addr,
+        token_id: String,
+        swap_type: Option<SwapType>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Self::QueryMsg;
+}
+
+/// `cw721-marketplace`, where any collection can be traded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpenMarketplace;
+
+/// `cw721-marketplace-permissioned`, where the collections of its allowlist can be traded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PermissionedMarketplace;
+
+/// `cw721-marketplace-single-collection`, where a single collection can be traded;
+/// its messages don't name the collection, so the `cw721` of swaps is ignored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SingleCollectionMarketplace;
+
+impl MarketplaceVariant for OpenMarketplace {
+    type InstantiateMsg = cw721_marketplace::msg::InstantiateMsg;
+    type ExecuteMsg = cw721_marketplace::msg::ExecuteMsg;
+    type QueryMsg = cw721_marketplace::msg::QueryMsg;
+    type Config = cw721_marketplace::state::Config;
+
+    fn create_msg(swap: SwapMsg) -> Self::ExecuteMsg {
+        Self::ExecuteMsg::Create(swap)
+    }
+
+    fn sweep_msg(sweep: SweepMsg) -> Self::ExecuteMsg {
+        Self::ExecuteMsg::Sweep(sweep)
+    }
+
+    fn listings_of_token_msg(
+        cw721: Addr,
+        token_id: String,
+        swap_type: Option<SwapType>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Self::QueryMsg {
+        Self::QueryMsg::ListingsOfToken {
+            token_id,
+            cw721,
+            swap_type,
+            page,
+            limit,
+        }
+    }
+}
+
+impl MarketplaceVariant for PermissionedMarketplace {
+    type InstantiateMsg = permissioned::InstantiateMsg;
+    type ExecuteMsg = permissioned::ExecuteMsg;
+    type QueryMsg = permissioned::QueryMsg;
+    type Config = cw721_marketplace_permissioned::state::Config;
+
+    fn create_msg(swap: SwapMsg) -> Self::ExecuteMsg {
+        Self::ExecuteMsg::Create(swap)
+    }
+
+    fn sweep_msg(sweep: SweepMsg) -> Self::ExecuteMsg {
+        Self::ExecuteMsg::Sweep(sweep)
+    }
+
+    fn listings_of_token_msg(
+        cw721: Addr,
+        token_id: String,
+        swap_type: Option<SwapType>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Self::QueryMsg {
+        Self::QueryMsg::ListingsOfToken {
+            token_id,
+            cw721,
+            swap_type,
+            page,
+            limit,
+        }
+    }
+}
+
+impl MarketplaceVariant for SingleCollectionMarketplace {
+    type InstantiateMsg = single_collection::InstantiateMsg;
+    type ExecuteMsg = single_collection::ExecuteMsg;
+    type QueryMsg = single_collection::QueryMsg;
+    type Config = cw721_marketplace_single_collection::state::Config;
+
+    fn create_msg(swap: SwapMsg) -> Self::ExecuteMsg {
+        Self::ExecuteMsg::Create(single_collection::SwapMsg {
+            id: swap.id,
+            payment_token: swap.payment_token,
+            token_id: swap.token_id,
+            expires: swap.expires,
+            price: swap.price,
+            swap_type: swap.swap_type,
+        })
+    }
+
+    fn sweep_msg(sweep: SweepMsg) -> Self::ExecuteMsg {
+        Self::ExecuteMsg::Sweep(single_collection::SweepMsg {
+            max_items: sweep.max_items,
+            max_price_per_item: sweep.max_price_per_item,
+            payment_token: sweep.payment_token,
+        })
+    }
+
+    fn listings_of_token_msg(
+        _cw721: Addr,
+        token_id: String,
+        swap_type: Option<SwapType>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Self::QueryMsg {
+        Self::QueryMsg::ListingsOfToken {
+            token_id,
+            swap_type,
+            page,
+            limit,
+        }
+    }
+}
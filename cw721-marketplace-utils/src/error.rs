@@ -0,0 +1,106 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Hash parse error: {0}")]
+    ParseError(String),
+
+    #[error("Invalid atomic swap id")]
+    InvalidId {},
+
+    #[error("Invalid preimage")]
+    InvalidPreimage {},
+
+    #[error("Invalid hash ({0} chars): must be 64 characters")]
+    InvalidHash(usize),
+
+    #[error("Send some coins to create an atomic swap")]
+    EmptyBalance {},
+
+    #[error("Must send exactly the required funds")]
+    ExactFunds {},
+
+    #[error("Invalid payment token")]
+    InvalidPaymentToken {},
+
+    #[error("Invalid input")]
+    InvalidInput {},
+
+    #[error("Price must be greater than zero")]
+    ZeroPrice {},
+
+    #[error("Price is below the minimum price of {min_price}")]
+    PriceTooLow { min_price: Uint128 },
+
+    #[error("Expiration is more than {max_duration} seconds away")]
+    ExpirationTooLong { max_duration: u64 },
+
+    #[error("Insufficient contract balance")]
+    InsufficientBalance {},
+
+    #[error("Invalid fee recipients")]
+    InvalidFeeRecipients {},
+
+    #[error("No fee recipients configured")]
+    NoFeeRecipients {},
+
+    #[error("No pending admin transfer")]
+    NoPendingAdmin {},
+
+    #[error("Admin can only be changed with ProposeAdmin and AcceptAdmin")]
+    AdminUpdate {},
+
+    #[error("Trading is paused")]
+    Paused {},
+
+    #[error("Address is blocked")]
+    BlockedAddress {},
+
+    #[error("Token is blocked")]
+    BlockedToken {},
+
+    #[error("Batch must contain between 1 and 50 swaps")]
+    InvalidBatchSize {},
+
+    #[error("Listing already requested")]
+    ListingRequested {},
+
+    #[error("Token is rented")]
+    Rented {},
+
+    #[error("Token does not have the required traits")]
+    TraitMismatch {},
+
+    #[error("Max number of hooks reached")]
+    TooManyHooks {},
+
+    #[error("NFT transfer failed on {contract}: {reason}")]
+    NftTransferFailed { contract: String, reason: String },
+
+    #[error("Payment failed on {contract}: {reason}")]
+    PaymentFailed { contract: String, reason: String },
+
+    #[error("Can't migrate from version {version}")]
+    UnsupportedMigration { version: String },
+
+    #[error("A migration is already in progress")]
+    MigrationInProgress {},
+
+    #[error("No migration in progress")]
+    NoMigration {},
+
+    #[error("Unauthorized")]
+    Unauthorized,
+    #[error("Expired atomic swap")]
+    Expired,
+    #[error("Cancelled atomic swap")]
+    Cancelled,
+    #[error("Completed atomic swap")]
+    Completed,
+    #[error("Atomic swap already exists")]
+    AlreadyExists,
+}
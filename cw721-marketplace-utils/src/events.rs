@@ -0,0 +1,488 @@
+//! Typed events of marketplace state changes, emitted next to the `wasm` event
+//! attributes as `wasm-marketplace-<action>` events with a fixed attribute set.
+//! Every event carries a `sequence` number, incremented by one for each event
+//! the marketplace emits, so indexers can detect events they missed
+use cosmwasm_std::{
+    to_json_string, Addr, BlockInfo, Coin, Event, StdResult, Storage, Timestamp, Uint128,
+};
+use cw20::Expiration;
+
+use crate::hooks::HookSubject;
+use crate::msg::BlockedToken;
+use crate::prelude::{CW721Swap, MarketConfig, SwapStatus};
+use crate::roles::Role;
+use crate::state::{expiration_rules, ExpirationRules, SwapIdRules, EVENT_SEQUENCE};
+use crate::{FeeRecipient, FeeSplit};
+
+/// `wasm-marketplace-create`, a swap was created:
+/// - `sequence`
+/// - `swap_id`, `swap_type`, `creator`
+/// - `collection`, `token_id` (omitted for trait offers)
+/// - `payment_token`, the cw20 address or the native denom
+/// - `price`, `expires` (as json)
+/// - `expires_at`, the time of `expires` in seconds (omitted if it never expires)
+pub fn create_event(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    swap: &CW721Swap,
+    denom: &str,
+) -> StdResult<Event> {
+    let expires_at = expires_at(storage, block, &swap.expires)?;
+    let event = marketplace_event(storage, "create")?
+        .add_attribute("swap_id", &swap.id)
+        .add_attribute("swap_type", swap_type(swap)?)
+        .add_attribute("creator", &swap.creator)
+        .add_attribute("collection", &swap.nft_contract);
+    let event = with_token_id(event, swap)
+        .add_attribute("payment_token", payment_token(swap, denom))
+        .add_attribute("price", swap.price)
+        .add_attribute("expires", to_json_string(&swap.expires)?);
+    Ok(with_expires_at(event, expires_at))
+}
+
+/// `wasm-marketplace-sale`, a swap was filled:
+/// - `sequence`
+/// - `swap_id`, `swap_type`
+/// - `buyer`, `seller`
+/// - `collection`, `token_id`
+/// - `payment_token`, the cw20 address or the native denom
+/// - `price`, `fee` (marketplace fee), `royalty`, `seller_amount` (paid to the seller);
+///   royalties aren't paid by the marketplace, so `royalty` is always `0`
+pub fn sale_event(
+    storage: &mut dyn Storage,
+    swap: &CW721Swap,
+    buyer: &Addr,
+    seller: &Addr,
+    denom: &str,
+    split: &FeeSplit,
+) -> StdResult<Event> {
+    Ok(marketplace_event(storage, "sale")?
+        .add_attribute("swap_id", &swap.id)
+        .add_attribute("swap_type", swap_type(swap)?)
+        .add_attribute("buyer", buyer)
+        .add_attribute("seller", seller)
+        .add_attribute("collection", &swap.nft_contract)
+        .add_attribute("token_id", &swap.token_id)
+        .add_attribute("payment_token", payment_token(swap, denom))
+        .add_attribute("price", swap.price)
+        .add_attribute("fee", split.marketplace)
+        .add_attribute("royalty", Uint128::zero())
+        .add_attribute("seller_amount", split.seller))
+}
+
+/// `wasm-marketplace-cancel` if the swap was cancelled, otherwise
+/// `wasm-marketplace-close`; a swap was closed without being filled:
+/// - `sequence`
+/// - `swap_id`, `swap_type`, `creator`
+/// - `collection`, `token_id` (omitted for trait offers)
+/// - `status`, the `SwapStatus` it was closed with
+pub fn close_event(
+    storage: &mut dyn Storage,
+    swap: &CW721Swap,
+    status: &SwapStatus,
+) -> StdResult<Event> {
+    let action = match status {
+        SwapStatus::Cancelled => "cancel",
+        _ => "close",
+    };
+    let event = marketplace_event(storage, action)?
+        .add_attribute("swap_id", &swap.id)
+        .add_attribute("swap_type", swap_type(swap)?)
+        .add_attribute("creator", &swap.creator)
+        .add_attribute("collection", &swap.nft_contract);
+    Ok(with_token_id(event, swap).add_attribute("status", unquoted(to_json_string(status)?)))
+}
+
+/// `wasm-marketplace-update`, the price and expiration of a swap were updated:
+/// - `sequence`
+/// - `swap_id`, `collection`, `token_id` (omitted for trait offers)
+/// - `old_price`, `price`
+/// - `old_payment_token`, `payment_token`, the cw20 address or the native denom
+/// - `old_expires`, `expires` (as json)
+/// - `expires_at`, the time of `expires` in seconds (omitted if it never expires)
+pub fn update_event(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    old: &CW721Swap,
+    swap: &CW721Swap,
+    denom: &str,
+) -> StdResult<Event> {
+    let expires_at = expires_at(storage, block, &swap.expires)?;
+    let event = marketplace_event(storage, "update")?
+        .add_attribute("swap_id", &swap.id)
+        .add_attribute("collection", &swap.nft_contract);
+    let event = with_token_id(event, swap)
+        .add_attribute("old_price", old.price)
+        .add_attribute("price", swap.price)
+        .add_attribute("old_payment_token", payment_token(old, denom))
+        .add_attribute("payment_token", payment_token(swap, denom))
+        .add_attribute("old_expires", to_json_string(&old.expires)?)
+        .add_attribute("expires", to_json_string(&swap.expires)?);
+    Ok(with_expires_at(event, expires_at))
+}
+
+/// `wasm-marketplace-rent`, a rental listing was rented:
+/// - `sequence`
+/// - `swap_id`, `renter`
+/// - `collection`, `token_id`
+/// - `payment_token`, the cw20 address or the native denom
+/// - `amount` (paid for all periods), `rented_until` (as json)
+pub fn rent_event(
+    storage: &mut dyn Storage,
+    swap: &CW721Swap,
+    renter: &Addr,
+    denom: &str,
+    amount: Uint128,
+    rented_until: &Expiration,
+) -> StdResult<Event> {
+    Ok(marketplace_event(storage, "rent")?
+        .add_attribute("swap_id", &swap.id)
+        .add_attribute("renter", renter)
+        .add_attribute("collection", &swap.nft_contract)
+        .add_attribute("token_id", &swap.token_id)
+        .add_attribute("payment_token", payment_token(swap, denom))
+        .add_attribute("amount", amount)
+        .add_attribute("rented_until", to_json_string(rented_until)?))
+}
+
+/// `wasm-marketplace-add-nft` or `wasm-marketplace-remove-nft`,
+/// a collection was added to or removed from the allowlist:
+/// - `sequence`
+/// - `collection`
+/// - `old_allowed`, `allowed`
+pub fn allowlist_event(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    allowed: bool,
+) -> StdResult<Event> {
+    let action = if allowed { "add-nft" } else { "remove-nft" };
+    Ok(marketplace_event(storage, action)?
+        .add_attribute("collection", collection)
+        .add_attribute("old_allowed", (!allowed).to_string())
+        .add_attribute("allowed", allowed.to_string()))
+}
+
+/// `wasm-marketplace-update-config`, the config was updated:
+/// - `sequence`
+/// - `old_denom`, `denom`
+/// - `old_fees`, `fees`
+/// - `old_config`, `config`, the whole config (as json)
+pub fn update_config_event<C: MarketConfig>(
+    storage: &mut dyn Storage,
+    old: &C,
+    config: &C,
+) -> StdResult<Event> {
+    Ok(marketplace_event(storage, "update-config")?
+        .add_attribute("old_denom", old.denom())
+        .add_attribute("denom", config.denom())
+        .add_attribute("old_fees", old.fees().to_string())
+        .add_attribute("fees", config.fees().to_string())
+        .add_attribute("old_config", to_json_string(old)?)
+        .add_attribute("config", to_json_string(config)?))
+}
+
+/// `wasm-marketplace-pause` or `wasm-marketplace-unpause`, a scope was paused or unpaused:
+/// - `sequence`
+/// - `scope`, `all`, a swap type or a collection address
+/// - `old_paused`, `paused`
+pub fn pause_event(
+    storage: &mut dyn Storage,
+    scope: &str,
+    old_paused: bool,
+    paused: bool,
+) -> StdResult<Event> {
+    let action = if paused { "pause" } else { "unpause" };
+    Ok(marketplace_event(storage, action)?
+        .add_attribute("scope", scope)
+        .add_attribute("old_paused", old_paused.to_string())
+        .add_attribute("paused", paused.to_string()))
+}
+
+/// `wasm-marketplace-block` or `wasm-marketplace-unblock`, an address was
+/// blocked or unblocked:
+/// - `sequence`
+/// - `address`
+/// - `old_blocked`, `blocked`
+pub fn block_address_event(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    old_blocked: bool,
+    blocked: bool,
+) -> StdResult<Event> {
+    Ok(block_event(storage, old_blocked, blocked)?.add_attribute("address", address))
+}
+
+/// `wasm-marketplace-block` or `wasm-marketplace-unblock`, a token was
+/// blocked or unblocked:
+/// - `sequence`
+/// - `collection`, `token_id`
+/// - `old_blocked`, `blocked`
+pub fn block_token_event(
+    storage: &mut dyn Storage,
+    token: &BlockedToken,
+    old_blocked: bool,
+    blocked: bool,
+) -> StdResult<Event> {
+    Ok(block_event(storage, old_blocked, blocked)?
+        .add_attribute("collection", &token.cw721)
+        .add_attribute("token_id", &token.token_id))
+}
+
+/// `wasm-marketplace-grant-role` or `wasm-marketplace-revoke-role`,
+/// a role was granted or revoked:
+/// - `sequence`
+/// - `role`, `address`
+/// - `old_granted`, `granted`
+pub fn role_event(
+    storage: &mut dyn Storage,
+    role: &Role,
+    address: &Addr,
+    old_granted: bool,
+    granted: bool,
+) -> StdResult<Event> {
+    let action = if granted { "grant-role" } else { "revoke-role" };
+    Ok(marketplace_event(storage, action)?
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address)
+        .add_attribute("old_granted", old_granted.to_string())
+        .add_attribute("granted", granted.to_string()))
+}
+
+/// `wasm-marketplace-propose-admin`, an admin was proposed:
+/// - `sequence`
+/// - `old_pending_admin` (omitted if none was proposed), `pending_admin`
+pub fn propose_admin_event(
+    storage: &mut dyn Storage,
+    old_pending: Option<&Addr>,
+    pending: &Addr,
+) -> StdResult<Event> {
+    let event = marketplace_event(storage, "propose-admin")?;
+    let event = match old_pending {
+        Some(old) => event.add_attribute("old_pending_admin", old),
+        None => event,
+    };
+    Ok(event.add_attribute("pending_admin", pending))
+}
+
+/// `wasm-marketplace-accept-admin` if the pending admin accepted, otherwise
+/// `wasm-marketplace-renounce-admin`; the admin changed:
+/// - `sequence`
+/// - `old_admin`, `admin` (omitted once renounced)
+pub fn admin_event(
+    storage: &mut dyn Storage,
+    old_admin: Option<&Addr>,
+    admin: Option<&Addr>,
+) -> StdResult<Event> {
+    let action = match admin {
+        Some(_) => "accept-admin",
+        None => "renounce-admin",
+    };
+    let mut event = marketplace_event(storage, action)?;
+    if let Some(old_admin) = old_admin {
+        event = event.add_attribute("old_admin", old_admin);
+    }
+    if let Some(admin) = admin {
+        event = event.add_attribute("admin", admin);
+    }
+    Ok(event)
+}
+
+/// `wasm-marketplace-update-fee-recipients`, the fee recipients were updated:
+/// - `sequence`
+/// - `old_recipients`, `recipients` (as json)
+pub fn fee_recipients_event(
+    storage: &mut dyn Storage,
+    old: &[FeeRecipient],
+    recipients: &[FeeRecipient],
+) -> StdResult<Event> {
+    Ok(marketplace_event(storage, "update-fee-recipients")?
+        .add_attribute("old_recipients", to_json_string(old)?)
+        .add_attribute("recipients", to_json_string(recipients)?))
+}
+
+/// `wasm-marketplace-update-min-price`, the minimum price of a payment token
+/// was updated:
+/// - `sequence`
+/// - `payment_token`, the cw20 address or the native denom
+/// - `old_min_price`, `min_price` (`0` without a minimum)
+pub fn min_price_event(
+    storage: &mut dyn Storage,
+    payment_token: &str,
+    old: Uint128,
+    min_price: Uint128,
+) -> StdResult<Event> {
+    Ok(marketplace_event(storage, "update-min-price")?
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("old_min_price", old)
+        .add_attribute("min_price", min_price))
+}
+
+/// `wasm-marketplace-update-swap-id-rules`, the swap id rules were updated:
+/// - `sequence`
+/// - `old_rules`, `rules` (as json)
+pub fn swap_id_rules_event(
+    storage: &mut dyn Storage,
+    old: &SwapIdRules,
+    rules: &SwapIdRules,
+) -> StdResult<Event> {
+    Ok(marketplace_event(storage, "update-swap-id-rules")?
+        .add_attribute("old_rules", to_json_string(old)?)
+        .add_attribute("rules", to_json_string(rules)?))
+}
+
+/// `wasm-marketplace-update-expiration-rules`, the expiration rules were updated:
+/// - `sequence`
+/// - `old_rules`, `rules` (as json)
+pub fn expiration_rules_event(
+    storage: &mut dyn Storage,
+    old: &ExpirationRules,
+    rules: &ExpirationRules,
+) -> StdResult<Event> {
+    Ok(marketplace_event(storage, "update-expiration-rules")?
+        .add_attribute("old_rules", to_json_string(old)?)
+        .add_attribute("rules", to_json_string(rules)?))
+}
+
+/// `wasm-marketplace-withdraw-fees` or `wasm-marketplace-distribute-fees`,
+/// fees held by the marketplace were paid out:
+/// - `sequence`
+/// - `payment_token`, the cw20 address or the native denom
+/// - `recipients`, the addresses and amounts paid (as json)
+/// - `old_balance`, `balance`, the fees held before and after
+pub fn fee_payout_event(
+    storage: &mut dyn Storage,
+    distributed: bool,
+    payment_token: &str,
+    recipients: &[(Addr, Uint128)],
+    old_balance: Uint128,
+) -> StdResult<Event> {
+    let action = if distributed {
+        "distribute-fees"
+    } else {
+        "withdraw-fees"
+    };
+    let paid: Uint128 = recipients.iter().map(|(_, amount)| amount).sum();
+    Ok(marketplace_event(storage, action)?
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("recipients", to_json_string(recipients)?)
+        .add_attribute("old_balance", old_balance)
+        .add_attribute("balance", old_balance.checked_sub(paid)?))
+}
+
+/// `wasm-marketplace-add-hook` or `wasm-marketplace-remove-hook`,
+/// a hook listener was added or removed:
+/// - `sequence`
+/// - `collection` or `address`, the subject of the hook
+/// - `listener`
+/// - `old_hooked`, `hooked`
+pub fn hook_event(
+    storage: &mut dyn Storage,
+    subject: &HookSubject,
+    listener: &Addr,
+    old_hooked: bool,
+    hooked: bool,
+) -> StdResult<Event> {
+    let action = if hooked { "add-hook" } else { "remove-hook" };
+    let event = marketplace_event(storage, action)?;
+    let event = match subject {
+        HookSubject::Collection(cw721) => event.add_attribute("collection", cw721),
+        HookSubject::Address(addr) => event.add_attribute("address", addr),
+    };
+    Ok(event
+        .add_attribute("listener", listener)
+        .add_attribute("old_hooked", old_hooked.to_string())
+        .add_attribute("hooked", hooked.to_string()))
+}
+
+/// What happened to a listing request of a permissioned marketplace
+pub enum ListingRequestAction {
+    Request,
+    Approve,
+    Reject,
+}
+
+/// `wasm-marketplace-request-listing`, `wasm-marketplace-approve-listing` or
+/// `wasm-marketplace-reject-listing`, a listing request was made or decided:
+/// - `sequence`
+/// - `collection`, `requester`
+/// - `fee`, the escrowed listing fee (omitted if free)
+/// - `old_requested`, `requested`
+pub fn listing_request_event(
+    storage: &mut dyn Storage,
+    action: ListingRequestAction,
+    collection: &Addr,
+    requester: &Addr,
+    fee: &Coin,
+) -> StdResult<Event> {
+    let (action, requested) = match action {
+        ListingRequestAction::Request => ("request-listing", true),
+        ListingRequestAction::Approve => ("approve-listing", false),
+        ListingRequestAction::Reject => ("reject-listing", false),
+    };
+    let event = marketplace_event(storage, action)?
+        .add_attribute("collection", collection)
+        .add_attribute("requester", requester);
+    let event = if fee.amount.is_zero() {
+        event
+    } else {
+        event.add_attribute("fee", fee.to_string())
+    };
+    Ok(event
+        .add_attribute("old_requested", (!requested).to_string())
+        .add_attribute("requested", requested.to_string()))
+}
+
+// Event of `action`, with the next sequence number
+fn marketplace_event(storage: &mut dyn Storage, action: &str) -> StdResult<Event> {
+    let sequence = EVENT_SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+    EVENT_SEQUENCE.save(storage, &sequence)?;
+    Ok(Event::new(format!("marketplace-{action}")).add_attribute("sequence", sequence.to_string()))
+}
+
+fn block_event(storage: &mut dyn Storage, old_blocked: bool, blocked: bool) -> StdResult<Event> {
+    let action = if blocked { "block" } else { "unblock" };
+    Ok(marketplace_event(storage, action)?
+        .add_attribute("old_blocked", old_blocked.to_string())
+        .add_attribute("blocked", blocked.to_string()))
+}
+
+// Trait offers aren't for a token, and attributes can't be empty
+fn with_token_id(event: Event, swap: &CW721Swap) -> Event {
+    if swap.token_id.is_empty() {
+        event
+    } else {
+        event.add_attribute("token_id", &swap.token_id)
+    }
+}
+
+// Height expirations are timed with the block time of the expiration rules
+fn expires_at(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    expires: &Expiration,
+) -> StdResult<Option<Timestamp>> {
+    Ok(expiration_rules(storage)?.timestamp(block, expires))
+}
+
+fn with_expires_at(event: Event, expires_at: Option<Timestamp>) -> Event {
+    match expires_at {
+        Some(time) => event.add_attribute("expires_at", time.seconds().to_string()),
+        None => event,
+    }
+}
+
+fn swap_type(swap: &CW721Swap) -> StdResult<String> {
+    Ok(unquoted(to_json_string(&swap.swap_type)?))
+}
+
+fn payment_token(swap: &CW721Swap, denom: &str) -> String {
+    match &swap.payment_token {
+        Some(token) => token.to_string(),
+        None => denom.to_string(),
+    }
+}
+
+// Json strings of unit enum variants, without their quotes
+fn unquoted(json: String) -> String {
+    json.trim_matches('"').to_string()
+}
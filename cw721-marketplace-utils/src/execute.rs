@@ -0,0 +1,2073 @@
+use std::collections::HashSet;
+
+use cosmwasm_std::{
+    from_json, to_json_binary, to_json_string, to_json_vec, Addr, BankMsg, Coin, CosmosMsg, Deps,
+    DepsMut, Env, Event, MessageInfo, Order, QueryRequest, Response, StdResult, Storage, Uint128,
+    WasmMsg, WasmQuery,
+};
+
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+use cw721::OwnerOfResponse;
+use cw_storage_plus::Bound;
+
+use crate::events::{
+    admin_event, block_address_event, block_token_event, create_event, expiration_rules_event,
+    fee_payout_event, fee_recipients_event, hook_event, min_price_event, pause_event,
+    propose_admin_event, rent_event, role_event, sale_event, swap_id_rules_event,
+    update_config_event, update_event,
+};
+use crate::hooks::{hooks_of, offer_hooks, HookSubject};
+use crate::payment::{
+    check_sent_required_payment, check_sent_required_payment_exact, fee_split,
+    handle_swap_transfers, nft_transfer, payment_transfers, query_name_owner,
+};
+use crate::policy::CollectionPolicy;
+use crate::prelude::{
+    weighted_shares, CW721Swap, FeeRecipient, Rental, Role, SwapStatus, SwapType, TraitReader,
+};
+use crate::settlement::settlement_submsgs;
+use crate::traits::has_traits;
+use crate::FeeSplit;
+
+use crate::error::ContractError;
+use crate::msg::{
+    BatchCreateResponse, BlocklistMsg, CancelMsg, CounterMsg, CreateResponse, DistributeFeesMsg,
+    FillTraitOfferMsg, FinishSwapMsg, HookMsg, PauseScope, RentMsg, RentalMsg, SwapMsg, SweepMsg,
+    SweepResponse, SweptSwap, TraitOfferMsg, UpdateMsg, UpdatePaymentTokenMsg, WithdrawMsg,
+};
+use crate::state::{
+    address_blocked, check_expiration, check_not_blocked, check_price, close_swap, config_item,
+    countered_offer, expiration_rules, fee_recipients, has_role, invalidate_counters, is_admin,
+    is_assigned_id, is_paused, next_swap_id, save_counter, save_rental, save_swap,
+    save_trait_offer, swap_id_rules, token_blocked, ExpirationRules, MarketConfig, SwapIdRules,
+    ADDRESS_HOOKS, ARCHIVE, BLOCKED_ADDRESSES, BLOCKED_TOKENS, COLLECTION_HOOKS, EXPIRATION_RULES,
+    FEE_RECIPIENTS, MIN_PRICES, PAUSE, PAUSED_CW721, PENDING_ADMIN, RENTALS, ROLES, SALE_PRICES,
+    SWAPS, SWAP_ID_RULES, TRAIT_OFFERS,
+};
+
+// Max number of marketplace fee recipients
+pub const MAX_FEE_RECIPIENTS: usize = 10;
+
+// Max number of swaps in a batch message
+pub const MAX_BATCH_SIZE: usize = 50;
+
+// Max number of listings a sweep looks at, bought or skipped
+pub const MAX_SWEEP_SCAN: usize = 200;
+
+// Max number of traits a trait offer can require
+pub const MAX_TRAITS: usize = 20;
+
+// Max number of listeners subscribed to a collection or address
+pub const MAX_HOOKS: usize = 10;
+
+// Number of swaps looked at by `DelistBlocked` by default, and at most
+pub const DEFAULT_DELIST_SCAN: u32 = 100;
+pub const MAX_DELIST_SCAN: u32 = 300;
+
+pub fn execute_create<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msg: SwapMsg,
+) -> Result<Response, ContractError> {
+    // If expired, revert
+    if msg.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    // If no cw721 permission, revert
+    if !policy.allows(deps.storage, &msg.cw721) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // If trading is paused, revert
+    if is_paused(deps.storage, &msg.swap_type, &msg.cw721)? {
+        return Err(ContractError::Paused {});
+    }
+
+    // If sender or token are blocklisted, revert
+    check_not_blocked(deps.storage, &[&info.sender], &msg.cw721, &msg.token_id)?;
+
+    let has_payment_token = msg.payment_token.is_some();
+    // SwapType::Sale
+    if msg.swap_type == SwapType::Sale {
+        let owner = query_name_owner(&msg.token_id, &msg.cw721, &deps)?;
+        if owner.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+    // SwapType::Offer
+    } else if msg.swap_type == SwapType::Offer && !has_payment_token {
+        return Err(ContractError::InvalidPaymentToken {});
+    // SwapType::Counter, SwapType::Rental and SwapType::TraitOffer
+    // (only created with `Counter`, `ListRental` and `CreateTraitOffer`)
+    } else if matches!(
+        msg.swap_type,
+        SwapType::Counter | SwapType::Rental | SwapType::TraitOffer
+    ) {
+        return Err(ContractError::InvalidInput {});
+    }
+    check_price(
+        deps.storage,
+        config.denom(),
+        &msg.payment_token,
+        msg.price,
+        &msg.swap_type,
+    )?;
+    let expires = check_expiration(deps.storage, &env.block, &msg.swap_type, msg.expires)?;
+    let id = assign_swap_id(deps.storage, msg.id)?;
+    let swap = CW721Swap {
+        id: id.clone(),
+        creator: info.sender,
+        nft_contract: msg.cw721,
+        payment_token: msg.payment_token,
+        token_id: msg.token_id,
+        expires,
+        price: msg.price,
+        swap_type: msg.swap_type,
+    };
+
+    save_swap(deps.storage, &swap)?;
+    let event = create_event(deps.storage, &env.block, &swap, config.denom())?;
+
+    // Listeners of the collection and of the token owner are notified of offers
+    let hooks = if swap.swap_type == SwapType::Offer {
+        let owner = query_name_owner(&swap.token_id, &swap.nft_contract, &deps)
+            .ok()
+            .map(|res| Addr::unchecked(res.owner));
+        offer_hooks(deps.storage, &swap, owner)?
+    } else {
+        vec![]
+    };
+
+    let payment_token: String = if has_payment_token {
+        swap.payment_token.unwrap().to_string()
+    } else {
+        config.denom().to_string()
+    };
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_event(event)
+        .add_attribute("action", "create")
+        .add_attribute("swap_id", &id)
+        .add_attribute("token_id", swap.token_id)
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("price", swap.price)
+        .set_data(to_json_binary(&CreateResponse { id })?))
+}
+
+// Client supplied ids must follow the id rules, not use the prefix of assigned ids, and
+// not exist yet (unmodifiable swaps), nor have been used by a closed swap; otherwise the
+// next id of the swap counter is assigned
+fn assign_swap_id(storage: &mut dyn Storage, id: Option<String>) -> Result<String, ContractError> {
+    match id {
+        Some(id) => {
+            if !swap_id_rules(storage)?.allows(&id) || is_assigned_id(&id) {
+                return Err(ContractError::InvalidId {});
+            }
+            if SWAPS.has(storage, &id) {
+                return Err(ContractError::AlreadyExists {});
+            }
+            if let Some(archived) = ARCHIVE.may_load(storage, &id)? {
+                return Err(match archived.status {
+                    SwapStatus::Filled => ContractError::Completed {},
+                    _ => ContractError::Cancelled {},
+                });
+            }
+            Ok(id)
+        }
+        None => Ok(next_swap_id(storage)?),
+    }
+}
+
+pub fn execute_update<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    msg: UpdateMsg,
+) -> Result<Response, ContractError> {
+    let swap = updatable_swap(deps.storage, &env, &info.sender, &msg.id)?;
+    check_price(
+        deps.storage,
+        config.denom(),
+        &swap.payment_token,
+        msg.price,
+        &swap.swap_type,
+    )?;
+    let expires = check_expiration(deps.storage, &env.block, &swap.swap_type, msg.expires)?;
+
+    // For security reasons, creator, nft_contract, token_id,
+    // payment_token and swap_type should not be updatable
+    // E.g. only price and expiration can be modified
+    // (payment_token is changed along with the price by `UpdatePaymentToken`)
+    let old = swap.clone();
+    let swap = CW721Swap {
+        id: swap.id,
+        creator: swap.creator,
+        nft_contract: swap.nft_contract,
+        payment_token: swap.payment_token,
+        token_id: swap.token_id,
+        expires,
+        price: msg.price,
+        swap_type: swap.swap_type,
+    };
+    // Replace legacy swap with updated swap
+    save_swap(deps.storage, &swap)?;
+    let event = update_event(deps.storage, &env.block, &old, &swap, config.denom())?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "update")
+        .add_attribute("swap_id", &msg.id)
+        .add_attribute("token_id", &swap.token_id))
+}
+
+/// Changes the payment token of a swap along with its price, keeping its id.
+/// Offers are paid from the creator's allowance, which must cover the new
+/// price, and the counter offers made to them are invalidated
+pub fn execute_update_payment_token<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    msg: UpdatePaymentTokenMsg,
+) -> Result<Response, ContractError> {
+    let swap = updatable_swap(deps.storage, &env, &info.sender, &msg.id)?;
+    match (&swap.swap_type, &msg.payment_token) {
+        // Counter offers are paid in the token of the offer they counter
+        (SwapType::Counter, _) => return Err(ContractError::InvalidInput {}),
+        (SwapType::Offer | SwapType::TraitOffer, None) => {
+            return Err(ContractError::InvalidPaymentToken {})
+        }
+        _ => {}
+    }
+    check_price(
+        deps.storage,
+        config.denom(),
+        &msg.payment_token,
+        msg.price,
+        &swap.swap_type,
+    )?;
+    // No funds are held in escrow, offers are paid from an allowance at settlement
+    if let (SwapType::Offer | SwapType::TraitOffer, Some(token)) =
+        (&swap.swap_type, &msg.payment_token)
+    {
+        check_cw20_payment(deps.as_ref(), &env, token, &swap.creator, msg.price)?;
+    }
+
+    let old = swap.clone();
+    let swap = CW721Swap {
+        payment_token: msg.payment_token,
+        price: msg.price,
+        ..swap
+    };
+    save_swap(deps.storage, &swap)?;
+    let event = update_event(deps.storage, &env.block, &old, &swap, config.denom())?;
+    // Counter offers are priced in the old payment token
+    let events = if swap.swap_type == SwapType::Offer && old.payment_token != swap.payment_token {
+        invalidate_counters(deps.storage, &env.block, &swap.id)?
+    } else {
+        vec![]
+    };
+
+    let payment_token: String = match &swap.payment_token {
+        Some(token) => token.to_string(),
+        None => config.denom().to_string(),
+    };
+    Ok(Response::new()
+        .add_event(event)
+        .add_events(events)
+        .add_attribute("action", "update_payment_token")
+        .add_attribute("swap_id", &msg.id)
+        .add_attribute("token_id", &swap.token_id)
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("price", swap.price))
+}
+
+// Swap `id`, if it can be updated by `sender`: its creator, while
+// it's not expired and trading isn't paused
+fn updatable_swap(
+    storage: &dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    id: &str,
+) -> Result<CW721Swap, ContractError> {
+    let swap = SWAPS.load(storage, id)?;
+
+    // Only creator can update swap
+    if *sender != swap.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // If expired, revert
+    if swap.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    // If trading is paused, revert
+    if is_paused(storage, &swap.swap_type, &swap.nft_contract)? {
+        return Err(ContractError::Paused {});
+    }
+    Ok(swap)
+}
+
+pub fn execute_finish<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msg: FinishSwapMsg,
+) -> Result<Response, ContractError> {
+    let swap = finishable_swap(deps.storage, &msg.id)?;
+    settle_swap(deps, env, info, config, policy, swap)
+}
+
+/// Swap `id`, if it can be settled with `Finish`
+pub fn finishable_swap(storage: &dyn Storage, id: &str) -> Result<CW721Swap, ContractError> {
+    let swap = SWAPS.load(storage, id)?;
+    // Rentals are rented with `Rent`, and trait offers filled with `FillTraitOffer`
+    if matches!(swap.swap_type, SwapType::Rental | SwapType::TraitOffer) {
+        return Err(ContractError::InvalidInput {});
+    }
+    Ok(swap)
+}
+
+// Fee split and transfers of a swap that can be settled
+pub struct Settlement {
+    pub split: FeeSplit,
+    pub transfers: Vec<CosmosMsg>,
+}
+
+/// Checks `swap` can be settled by `sender` with `funds`, and returns the
+/// transfers settling it: the NFT transfer first, then the seller payout and
+/// the fees. Shared by `Finish`, `FillTraitOffer` and `SimulateFinish`
+pub fn check_settlement<C: MarketConfig>(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    funds: &[Coin],
+    config: &C,
+    policy: &impl CollectionPolicy,
+    swap: &CW721Swap,
+) -> Result<Settlement, ContractError> {
+    let fee_recipients = fee_recipients(deps.storage)?;
+    // If trading is paused, revert
+    if is_paused(deps.storage, &swap.swap_type, &swap.nft_contract)? {
+        return Err(ContractError::Paused {});
+    }
+    // If either party or the token are blocklisted, revert
+    check_not_blocked(
+        deps.storage,
+        &[sender, &swap.creator],
+        &swap.nft_contract,
+        &swap.token_id,
+    )?;
+    // If expired, revert
+    if swap.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    // If no cw721 permission, revert
+    if !policy.allows(deps.storage, &swap.nft_contract) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // If swapping for native `aarch`
+    // check payment conditions satisfied
+    if swap.payment_token.is_none() {
+        let required_payment = Coin {
+            denom: config.denom().to_string(),
+            amount: swap.price,
+        };
+        check_sent_required_payment(funds, Some(required_payment))?;
+
+        // Native aarch offers not allowed
+        if matches!(swap.swap_type, SwapType::Offer | SwapType::TraitOffer) {
+            return Err(ContractError::InvalidInput {});
+        }
+    }
+
+    // Calculate fee split; swaps listed for free before zero prices
+    // were rejected settle as free transfers when no funds are sent
+    let split = if swap.payment_token.is_none() {
+        let paid = funds
+            .iter()
+            .find(|coin| coin.denom == config.denom())
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+
+        fee_split(config, paid).unwrap_or(FeeSplit::only_seller(paid))
+    } else {
+        fee_split(config, swap.price).unwrap_or(FeeSplit::only_seller(swap.price))
+    };
+
+    // Offers are paid by their creator, other swaps by the sender
+    let (seller, buyer) = match swap.swap_type {
+        SwapType::Offer | SwapType::TraitOffer => {
+            let owner_of: OwnerOfResponse =
+                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: swap.nft_contract.to_string(),
+                    msg: to_json_binary(&cw721::Cw721QueryMsg::OwnerOf {
+                        token_id: swap.token_id.clone(),
+                        include_expired: None,
+                    })?,
+                }))?;
+
+            if owner_of.owner != *sender {
+                return Err(ContractError::Unauthorized {});
+            }
+            (sender, &swap.creator)
+        }
+        SwapType::Sale => (&swap.creator, sender),
+        SwapType::Counter => {
+            // Only the offerer can accept a counter offer
+            let offer = countered_offer(deps.storage, &swap.id)?;
+            if offer.creator != *sender {
+                return Err(ContractError::Unauthorized {});
+            }
+            (&swap.creator, sender)
+        }
+        // Rentals are rented with `Rent`
+        SwapType::Rental => return Err(ContractError::InvalidInput {}),
+    };
+
+    // cw20 payments are taken from the buyer's allowance, which must cover the price
+    if let Some(token) = &swap.payment_token {
+        check_cw20_payment(deps, env, token, buyer, swap.price)?;
+    }
+
+    let mut transfers = handle_swap_transfers(
+        env.clone(),
+        seller,
+        buyer,
+        swap.clone(),
+        config.denom().to_string(),
+        split.clone(),
+        &fee_recipients,
+    )?;
+    // Free swaps only transfer the NFT, as zero amount transfers fail
+    if split.seller.is_zero() && split.marketplace.is_zero() {
+        transfers.truncate(1);
+    }
+    Ok(Settlement { split, transfers })
+}
+
+// Fails like the cw20 transfer of the payment would, if the allowance
+// or the balance of `owner` is less than `amount`
+fn check_cw20_payment(
+    deps: Deps,
+    env: &Env,
+    token: &Addr,
+    owner: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let allowance: cw20::AllowanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Allowance {
+            owner: owner.to_string(),
+            spender: env.contract.address.to_string(),
+        },
+    )?;
+    let balance: BalanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Balance {
+            address: owner.to_string(),
+        },
+    )?;
+    let reason = if allowance.allowance < amount {
+        format!("allowance of {} is less than {amount}", allowance.allowance)
+    } else if balance.balance < amount {
+        format!("balance of {} is less than {amount}", balance.balance)
+    } else {
+        return Ok(());
+    };
+    Err(ContractError::PaymentFailed {
+        contract: token.to_string(),
+        reason,
+    })
+}
+
+// Transfers the token and payment of `swap`, closing it as filled
+// by the sender and invalidating the other swaps of its token
+fn settle_swap<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    swap: CW721Swap,
+) -> Result<Response, ContractError> {
+    let block = env.block.clone();
+    let Settlement {
+        split,
+        transfers: transfer_results,
+    } = check_settlement(
+        deps.as_ref(),
+        &env,
+        &info.sender,
+        &info.funds,
+        config,
+        policy,
+        &swap,
+    )?;
+
+    // Close the swap, and all other swaps for this token_id
+    // (as they're no longer valid)
+    let mut events = close_swap(
+        deps.storage,
+        &block,
+        &swap,
+        SwapStatus::Filled,
+        Some(&info.sender),
+    )?;
+    let swap_data = swap.clone();
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+    for (id, swap) in swaps?.iter() {
+        if swap.token_id == swap_data.token_id && swap.nft_contract == swap_data.nft_contract {
+            // Counter offers are closed along with their offer
+            if !SWAPS.has(deps.storage, id) {
+                continue;
+            }
+            events.extend(close_swap(
+                deps.storage,
+                &block,
+                swap,
+                SwapStatus::Invalidated,
+                None,
+            )?);
+        }
+    }
+    let (buyer, seller) = match swap.swap_type {
+        SwapType::Offer | SwapType::TraitOffer => (&swap.creator, &info.sender),
+        _ => (&info.sender, &swap.creator),
+    };
+    events.push(sale_event(
+        deps.storage,
+        &swap,
+        buyer,
+        seller,
+        config.denom(),
+        &split,
+    )?);
+
+    // Transfers are replied to, so a failed transfer names the contract that failed
+    let transfers = settlement_submsgs(deps.storage, &swap, transfer_results)?;
+
+    let payment_token: String = if let Some(token) = swap.payment_token {
+        token.to_string()
+    } else {
+        config.denom().to_string()
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "finish")
+        .add_attribute("swap_id", swap.id)
+        .add_attribute("token_id", swap.token_id)
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("price", swap.price)
+        .add_events(events)
+        .add_submessages(transfers))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CancelMsg,
+) -> Result<Response, ContractError> {
+    let swap = SWAPS.load(deps.storage, &msg.id)?;
+    if info.sender != swap.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Escrowed rental tokens are returned once they're no longer rented
+    let mut res = Response::new();
+    if swap.swap_type == SwapType::Rental {
+        if RENTALS.load(deps.storage, &swap.id)?.is_rented(&env.block) {
+            return Err(ContractError::Rented {});
+        }
+        res = res.add_message(nft_transfer(
+            &swap.nft_contract,
+            &swap.creator,
+            &swap.token_id,
+        )?);
+    }
+
+    let events = close_swap(deps.storage, &env.block, &swap, SwapStatus::Cancelled, None)?;
+
+    Ok(res
+        .add_events(events)
+        .add_attribute("action", "cancel")
+        .add_attribute("swap_id", msg.id))
+}
+
+pub fn execute_list_rental<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msg: RentalMsg,
+) -> Result<Response, ContractError> {
+    // If expired, revert
+    if msg.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    // If no cw721 permission, revert
+    if !policy.allows(deps.storage, &msg.cw721) {
+        return Err(ContractError::Unauthorized {});
+    }
+    // If trading is paused, revert
+    if is_paused(deps.storage, &SwapType::Rental, &msg.cw721)? {
+        return Err(ContractError::Paused {});
+    }
+    // If sender or token are blocklisted, revert
+    check_not_blocked(deps.storage, &[&info.sender], &msg.cw721, &msg.token_id)?;
+    if msg.period == 0 || msg.max_periods == 0 {
+        return Err(ContractError::InvalidInput {});
+    }
+    check_price(
+        deps.storage,
+        config.denom(),
+        &msg.payment_token,
+        msg.price,
+        &SwapType::Rental,
+    )?;
+    let expires = check_expiration(deps.storage, &env.block, &SwapType::Rental, msg.expires)?;
+    let owner = query_name_owner(&msg.token_id, &msg.cw721, &deps)?;
+    if owner.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let id = assign_swap_id(deps.storage, msg.id)?;
+    let swap = CW721Swap {
+        id: id.clone(),
+        creator: info.sender,
+        nft_contract: msg.cw721,
+        payment_token: msg.payment_token,
+        token_id: msg.token_id,
+        expires,
+        price: msg.price,
+        swap_type: SwapType::Rental,
+    };
+    let rental = Rental {
+        period: msg.period,
+        max_periods: msg.max_periods,
+        renter: None,
+        rented_until: None,
+    };
+    save_rental(deps.storage, &swap, &rental)?;
+    let event = create_event(deps.storage, &env.block, &swap, config.denom())?;
+
+    // The token is held in escrow until the rental listing is cancelled
+    let escrow = nft_transfer(&swap.nft_contract, &env.contract.address, &swap.token_id)?;
+
+    let payment_token: String = if let Some(token) = &swap.payment_token {
+        token.to_string()
+    } else {
+        config.denom().to_string()
+    };
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "list_rental")
+        .add_attribute("swap_id", &id)
+        .add_attribute("token_id", swap.token_id)
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("price", swap.price)
+        .add_attribute("period", rental.period.to_string())
+        .add_message(escrow)
+        .set_data(to_json_binary(&CreateResponse { id })?))
+}
+
+pub fn execute_rent<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msg: RentMsg,
+) -> Result<Response, ContractError> {
+    let swap = SWAPS.load(deps.storage, &msg.id)?;
+    if swap.swap_type != SwapType::Rental {
+        return Err(ContractError::InvalidInput {});
+    }
+    // If trading is paused, revert
+    if is_paused(deps.storage, &swap.swap_type, &swap.nft_contract)? {
+        return Err(ContractError::Paused {});
+    }
+    // If either party or the token are blocklisted, revert
+    check_not_blocked(
+        deps.storage,
+        &[&info.sender, &swap.creator],
+        &swap.nft_contract,
+        &swap.token_id,
+    )?;
+    // If expired, revert
+    if swap.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    // If no cw721 permission, revert
+    if !policy.allows(deps.storage, &swap.nft_contract) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut rental = RENTALS.load(deps.storage, &swap.id)?;
+    if rental.is_rented(&env.block) {
+        return Err(ContractError::Rented {});
+    }
+    if msg.periods == 0 || msg.periods > rental.max_periods {
+        return Err(ContractError::InvalidInput {});
+    }
+    let price = swap
+        .price
+        .checked_mul(Uint128::from(msg.periods))
+        .map_err(|_| ContractError::InvalidInput {})?;
+    let duration = rental
+        .period
+        .checked_mul(msg.periods as u64)
+        .ok_or(ContractError::InvalidInput {})?;
+
+    // Native rentals are paid with exactly their price, free ones without funds
+    if swap.payment_token.is_none() {
+        if price.is_zero() {
+            if !info.funds.is_empty() {
+                return Err(ContractError::ExactFunds {});
+            }
+        } else {
+            check_sent_required_payment_exact(
+                &info.funds,
+                Some(Coin {
+                    denom: config.denom().to_string(),
+                    amount: price,
+                }),
+            )?;
+        }
+    }
+    let payments = if price.is_zero() {
+        vec![]
+    } else {
+        let split = fee_split(config, price).unwrap_or(FeeSplit::only_seller(price));
+        payment_transfers(
+            env.clone(),
+            &info.sender,
+            &swap.creator,
+            &swap.payment_token,
+            config.denom().to_string(),
+            split,
+            &fee_recipients(deps.storage)?,
+        )?
+    };
+
+    let rented_until = Expiration::AtTime(env.block.time.plus_seconds(duration));
+    rental.renter = Some(info.sender.clone());
+    rental.rented_until = Some(rented_until);
+    RENTALS.save(deps.storage, &swap.id, &rental)?;
+    let event = rent_event(
+        deps.storage,
+        &swap,
+        &info.sender,
+        config.denom(),
+        price,
+        &rented_until,
+    )?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "rent")
+        .add_attribute("swap_id", swap.id)
+        .add_attribute("token_id", swap.token_id)
+        .add_attribute("renter", info.sender)
+        .add_attribute("price", price)
+        .add_attribute("rented_until", rented_until.to_string())
+        .add_messages(payments))
+}
+
+pub fn execute_counter<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msg: CounterMsg,
+) -> Result<Response, ContractError> {
+    let offer = SWAPS.load(deps.storage, &msg.offer_id)?;
+    if offer.swap_type != SwapType::Offer {
+        return Err(ContractError::InvalidInput {});
+    }
+    // If either offer or counter offer are expired, revert
+    if offer.is_expired(&env.block) || msg.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    // If no cw721 permission, revert
+    if !policy.allows(deps.storage, &offer.nft_contract) {
+        return Err(ContractError::Unauthorized {});
+    }
+    // If trading is paused, revert
+    if is_paused(deps.storage, &SwapType::Counter, &offer.nft_contract)? {
+        return Err(ContractError::Paused {});
+    }
+    // If either party or the token are blocklisted, revert
+    check_not_blocked(
+        deps.storage,
+        &[&info.sender, &offer.creator],
+        &offer.nft_contract,
+        &offer.token_id,
+    )?;
+    // Only the token owner can counter an offer
+    let owner = query_name_owner(&offer.token_id, &offer.nft_contract, &deps)?;
+    if owner.owner != info.sender || offer.creator == info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_price(
+        deps.storage,
+        config.denom(),
+        &offer.payment_token,
+        msg.price,
+        &SwapType::Counter,
+    )?;
+    let expires = check_expiration(deps.storage, &env.block, &SwapType::Counter, msg.expires)?;
+
+    let id = assign_swap_id(deps.storage, msg.id)?;
+    let counter = CW721Swap {
+        id: id.clone(),
+        creator: info.sender,
+        nft_contract: offer.nft_contract,
+        payment_token: offer.payment_token,
+        token_id: offer.token_id,
+        expires,
+        price: msg.price,
+        swap_type: SwapType::Counter,
+    };
+    save_counter(deps.storage, &counter, &offer.id)?;
+    let event = create_event(deps.storage, &env.block, &counter, config.denom())?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "counter")
+        .add_attribute("swap_id", &id)
+        .add_attribute("offer_id", offer.id)
+        .add_attribute("token_id", counter.token_id)
+        .add_attribute("price", counter.price)
+        .set_data(to_json_binary(&CreateResponse { id })?))
+}
+
+pub fn execute_create_trait_offer<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msg: TraitOfferMsg,
+) -> Result<Response, ContractError> {
+    // If expired, revert
+    if msg.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    // If no cw721 permission, revert
+    if !policy.allows(deps.storage, &msg.cw721) {
+        return Err(ContractError::Unauthorized {});
+    }
+    // If trading is paused, revert
+    if is_paused(deps.storage, &SwapType::TraitOffer, &msg.cw721)? {
+        return Err(ContractError::Paused {});
+    }
+    // If sender is blocklisted, revert
+    if address_blocked(deps.storage, &info.sender) {
+        return Err(ContractError::BlockedAddress {});
+    }
+    // Offers are paid in cw20 tokens
+    if msg.payment_token.is_none() {
+        return Err(ContractError::InvalidPaymentToken {});
+    }
+    if msg.traits.len() > MAX_TRAITS {
+        return Err(ContractError::InvalidInput {});
+    }
+    check_price(
+        deps.storage,
+        config.denom(),
+        &msg.payment_token,
+        msg.price,
+        &SwapType::TraitOffer,
+    )?;
+    let expires = check_expiration(deps.storage, &env.block, &SwapType::TraitOffer, msg.expires)?;
+
+    let id = assign_swap_id(deps.storage, msg.id)?;
+    let swap = CW721Swap {
+        id: id.clone(),
+        creator: info.sender,
+        nft_contract: msg.cw721,
+        payment_token: msg.payment_token,
+        token_id: String::new(), // Set to the token the offer is filled with
+        expires,
+        price: msg.price,
+        swap_type: SwapType::TraitOffer,
+    };
+    save_trait_offer(deps.storage, &swap, &msg.traits)?;
+    let event = create_event(deps.storage, &env.block, &swap, config.denom())?;
+
+    let payment_token: String = match &swap.payment_token {
+        Some(token) => token.to_string(),
+        None => config.denom().to_string(),
+    };
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "create_trait_offer")
+        .add_attribute("swap_id", &id)
+        .add_attribute("payment_token", payment_token)
+        .add_attribute("price", swap.price)
+        .add_attribute("traits", msg.traits.len().to_string())
+        .set_data(to_json_binary(&CreateResponse { id })?))
+}
+
+pub fn execute_fill_trait_offer<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    reader: &impl TraitReader,
+    msg: FillTraitOfferMsg,
+) -> Result<Response, ContractError> {
+    let trait_offer = SWAPS.load(deps.storage, &msg.id)?;
+    if trait_offer.swap_type != SwapType::TraitOffer {
+        return Err(ContractError::InvalidInput {});
+    }
+    // If trading is paused, revert
+    if is_paused(
+        deps.storage,
+        &SwapType::TraitOffer,
+        &trait_offer.nft_contract,
+    )? {
+        return Err(ContractError::Paused {});
+    }
+
+    // The token must have every required trait at fill time
+    let required = TRAIT_OFFERS.load(deps.storage, &trait_offer.id)?;
+    let traits = reader.traits(&deps.querier, &trait_offer.nft_contract, &msg.token_id)?;
+    if !has_traits(&traits, &required) {
+        return Err(ContractError::TraitMismatch {});
+    }
+
+    // Settled as an offer for the token, which checks the sender owns it,
+    // and archived with the token it was filled with
+    let offer = CW721Swap {
+        token_id: msg.token_id,
+        ..trait_offer
+    };
+    let res = settle_swap(deps, env, info, config, policy, offer)?;
+
+    Ok(res.add_attribute("trait_offer", "true"))
+}
+
+pub fn execute_decline_counter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    let counter = SWAPS.load(deps.storage, &id)?;
+    if counter.swap_type != SwapType::Counter {
+        return Err(ContractError::InvalidInput {});
+    }
+    // Only the offerer can decline a counter offer
+    let offer = countered_offer(deps.storage, &id)?;
+    if info.sender != offer.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let events = close_swap(
+        deps.storage,
+        &env.block,
+        &counter,
+        SwapStatus::Cancelled,
+        None,
+    )?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("action", "decline_counter")
+        .add_attribute("swap_id", id)
+        .add_attribute("offer_id", offer.id))
+}
+
+pub fn execute_distribute_fees<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    config: &C,
+    msg: DistributeFeesMsg,
+    escrowed: Uint128,
+) -> Result<Response, ContractError> {
+    let fee_recipients = fee_recipients(deps.storage)?;
+    if fee_recipients.is_empty() {
+        return Err(ContractError::NoFeeRecipients {});
+    }
+
+    // Distribute the fees held by the contract
+    let balance: Uint128 = if let Some(token) = &msg.payment_token {
+        let res: BalanceResponse = deps.querier.query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        res.balance
+    } else {
+        // Native funds held in escrow are not marketplace fees
+        deps.querier
+            .query_balance(&env.contract.address, config.denom())?
+            .amount
+            .checked_sub(escrowed)
+            .unwrap_or_default()
+    };
+    if balance.is_zero() {
+        return Err(ContractError::InsufficientBalance {});
+    }
+
+    let mut transfers: Vec<CosmosMsg> = vec![];
+    let mut paid: Vec<(Addr, Uint128)> = vec![];
+    for (recipient, amount) in weighted_shares(balance, &fee_recipients) {
+        if amount.is_zero() {
+            continue;
+        }
+        paid.push((recipient.clone(), amount));
+        let transfer: CosmosMsg = if let Some(token) = &msg.payment_token {
+            WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }
+            .into()
+        } else {
+            BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: config.denom().to_string(),
+                    amount,
+                }],
+            }
+            .into()
+        };
+        transfers.push(transfer);
+    }
+
+    let denom: String = if let Some(token) = msg.payment_token {
+        token.to_string()
+    } else {
+        config.denom().to_string()
+    };
+    let event = fee_payout_event(deps.storage, true, &denom, &paid, balance)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "distribute_fees")
+        .add_attribute("amount", balance)
+        .add_attribute("denom", denom)
+        .add_messages(transfers))
+}
+
+pub fn execute_update_config<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    config_update: C,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, config, &info.sender, Role::FeeManager) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config_update.admin() != config.admin() {
+        return Err(ContractError::AdminUpdate {});
+    }
+    // Fee managers can only update the fees
+    if !is_admin(config, &info.sender) {
+        let mut expected = config_item::<C>().load(deps.storage)?;
+        expected.set_fees(config_update.fees());
+        if to_json_vec(&expected)? != to_json_vec(&config_update)? {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    config_item::<C>().save(deps.storage, &config_update)?;
+    let event = update_config_event(deps.storage, config, &config_update)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "update_config"))
+}
+
+#[allow(clippy::unnecessary_unwrap)]
+pub fn execute_withdraw_fees<C: MarketConfig>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    msg: WithdrawMsg,
+    escrowed: Uint128,
+) -> Result<Response, ContractError> {
+    let denom = msg.denom;
+    let amount = msg.amount;
+
+    // Fees are paid to the sender, fee managers pay out with `DistributeFees`
+    if !is_admin(config, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (transfer_result, available, payment_token) = if msg.payment_token.is_none() {
+        // Native funds held in escrow can't be withdrawn
+        let available = deps
+            .querier
+            .query_balance(&env.contract.address, &denom)?
+            .amount
+            .checked_sub(escrowed)
+            .unwrap_or_default();
+        if amount > available {
+            return Err(ContractError::InsufficientBalance {});
+        }
+
+        let bank_transfer_msg = BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: ([Coin {
+                denom: denom.clone(),
+                amount,
+            }])
+            .to_vec(),
+        };
+
+        let bank_transfer: CosmosMsg = cosmwasm_std::CosmosMsg::Bank(bank_transfer_msg);
+        (bank_transfer, available, denom.clone())
+    } else {
+        let token = msg.payment_token.unwrap();
+        let res: BalanceResponse = deps.querier.query_wasm_smart(
+            &token,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        if amount > res.balance {
+            return Err(ContractError::InsufficientBalance {});
+        }
+
+        let cw20_transfer_msg = Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        };
+
+        let cw20_transfer: CosmosMsg = cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&cw20_transfer_msg)?,
+            funds: vec![],
+        });
+        (cw20_transfer, res.balance, token.to_string())
+    };
+    let event = fee_payout_event(
+        deps.storage,
+        false,
+        &payment_token,
+        &[(info.sender, amount)],
+        available,
+    )?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "withdraw")
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("denom", denom)
+        .add_message(transfer_result))
+}
+
+pub fn execute_update_fee_recipients<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    recipients: Vec<FeeRecipient>,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, config, &info.sender, Role::FeeManager) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // An empty list means fees are retained by the contract
+    if recipients.len() > MAX_FEE_RECIPIENTS {
+        return Err(ContractError::InvalidFeeRecipients {});
+    }
+    let mut unique = HashSet::new();
+    for recipient in recipients.iter() {
+        deps.api.addr_validate(recipient.address.as_str())?;
+        if recipient.weight == 0 || !unique.insert(recipient.address.clone()) {
+            return Err(ContractError::InvalidFeeRecipients {});
+        }
+    }
+
+    let old = fee_recipients(deps.storage)?;
+    FEE_RECIPIENTS.save(deps.storage, &recipients)?;
+    let event = fee_recipients_event(deps.storage, &old, &recipients)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "update_fee_recipients")
+        .add_attribute("recipients", recipients.len().to_string()))
+}
+
+pub fn execute_propose_admin<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    admin: Addr,
+) -> Result<Response, ContractError> {
+    if !is_admin(config, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let admin = deps.api.addr_validate(admin.as_str())?;
+    let old_pending = PENDING_ADMIN.may_load(deps.storage)?;
+    PENDING_ADMIN.save(deps.storage, &admin)?;
+    let event = propose_admin_event(deps.storage, old_pending.as_ref(), &admin)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "propose_admin")
+        .add_attribute("pending_admin", admin))
+}
+
+pub fn execute_accept_admin<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    mut config: C,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+
+    if pending != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let old_admin = config.admin().cloned();
+    config.set_admin(Some(pending.clone()));
+    config_item::<C>().save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+    let event = admin_event(deps.storage, old_admin.as_ref(), Some(&pending))?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "accept_admin")
+        .add_attribute("admin", info.sender))
+}
+
+pub fn execute_renounce_admin<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    mut config: C,
+) -> Result<Response, ContractError> {
+    if !is_admin(&config, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Irreversible; granted roles are kept
+    config.set_admin(None);
+    config_item::<C>().save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+    let event = admin_event(deps.storage, Some(&info.sender), None)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "renounce_admin"))
+}
+
+pub fn execute_grant_role<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    role: Role,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    if !is_admin(config, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(address.as_str())?;
+    let old_granted = ROLES.has(deps.storage, (role.as_str(), &address));
+    ROLES.save(deps.storage, (role.as_str(), &address), &())?;
+    let event = role_event(deps.storage, &role, &address, old_granted, true)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn execute_revoke_role<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    role: Role,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    if !is_admin(config, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+    let event = role_event(deps.storage, &role, &address, true, false)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn execute_pause<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    scope: PauseScope,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, config, &info.sender, Role::Pauser) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    let (scope_attr, old_paused): (String, bool) = match scope {
+        PauseScope::All => {
+            let old_paused = pause.all;
+            pause.all = true;
+            ("all".to_string(), old_paused)
+        }
+        PauseScope::SwapType(swap_type) => {
+            let attr = format!("{:?}", swap_type);
+            let old_paused = pause.swap_types.contains(&swap_type);
+            if !old_paused {
+                pause.swap_types.push(swap_type);
+            }
+            (attr, old_paused)
+        }
+        PauseScope::Collection(cw721) => {
+            let cw721 = deps.api.addr_validate(cw721.as_str())?;
+            let old_paused = PAUSED_CW721.has(deps.storage, cw721.as_str());
+            PAUSED_CW721.save(deps.storage, cw721.as_str(), &())?;
+            (cw721.to_string(), old_paused)
+        }
+    };
+    PAUSE.save(deps.storage, &pause)?;
+    let event = pause_event(deps.storage, &scope_attr, old_paused, true)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "pause")
+        .add_attribute("scope", scope_attr))
+}
+
+pub fn execute_unpause<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    scope: PauseScope,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, config, &info.sender, Role::Pauser) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    let (scope_attr, old_paused): (String, bool) = match scope {
+        PauseScope::All => {
+            let old_paused = pause.all;
+            pause.all = false;
+            ("all".to_string(), old_paused)
+        }
+        PauseScope::SwapType(swap_type) => {
+            let attr = format!("{:?}", swap_type);
+            let old_paused = pause.swap_types.contains(&swap_type);
+            pause.swap_types.retain(|t| *t != swap_type);
+            (attr, old_paused)
+        }
+        PauseScope::Collection(cw721) => {
+            let old_paused = PAUSED_CW721.has(deps.storage, cw721.as_str());
+            PAUSED_CW721.remove(deps.storage, cw721.as_str());
+            (cw721.to_string(), old_paused)
+        }
+    };
+    PAUSE.save(deps.storage, &pause)?;
+    let event = pause_event(deps.storage, &scope_attr, old_paused, false)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "unpause")
+        .add_attribute("scope", scope_attr))
+}
+
+pub fn execute_delist_blocked(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_DELIST_SCAN);
+    if limit == 0 || limit > MAX_DELIST_SCAN {
+        return Err(ContractError::InvalidInput {});
+    }
+    let limit = limit as usize;
+
+    // One more swap is read to know if this is the last page
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let mut swaps = SWAPS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<(String, CW721Swap)>>>()?;
+    let done = swaps.len() <= limit;
+    swaps.truncate(limit);
+    let last_id = swaps.last().map(|(id, _)| id.clone());
+
+    // Remove all swaps created by blocked addresses or of blocked tokens,
+    // escrowed rental tokens are returned to their owners
+    let mut delisted: Vec<String> = vec![];
+    let mut returned: Vec<CosmosMsg> = vec![];
+    let mut events: Vec<Event> = vec![];
+    for (id, swap) in swaps.into_iter() {
+        // Counter offers are closed along with their offer
+        if !SWAPS.has(deps.storage, &id) {
+            continue;
+        }
+        if address_blocked(deps.storage, &swap.creator)
+            || token_blocked(deps.storage, &swap.nft_contract, &swap.token_id)
+        {
+            if swap.swap_type == SwapType::Rental {
+                returned.push(nft_transfer(
+                    &swap.nft_contract,
+                    &swap.creator,
+                    &swap.token_id,
+                )?);
+            }
+            events.extend(close_swap(
+                deps.storage,
+                &env.block,
+                &swap,
+                SwapStatus::Invalidated,
+                None,
+            )?);
+            delisted.push(id);
+        }
+    }
+
+    // Next page starts after `last_id`
+    let mut res = Response::new()
+        .add_messages(returned)
+        .add_events(events)
+        .add_attribute("action", "delist_blocked")
+        .add_attribute("done", done.to_string());
+    if !delisted.is_empty() {
+        res = res.add_attribute("swap_ids", delisted.join(","));
+    }
+    if let Some(last_id) = last_id {
+        res = res.add_attribute("last_id", last_id);
+    }
+
+    Ok(res)
+}
+
+pub fn execute_add_hook<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    msg: HookMsg,
+) -> Result<Response, ContractError> {
+    if !can_manage_hooks(deps.storage, config, &info.sender, &msg.subject) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if hooks_of(deps.storage, &msg.subject)?.len() >= MAX_HOOKS {
+        return Err(ContractError::TooManyHooks {});
+    }
+    let listener = deps.api.addr_validate(msg.listener.as_str())?;
+
+    let old_hooked = match &msg.subject {
+        HookSubject::Collection(cw721) => {
+            let old_hooked = COLLECTION_HOOKS.has(deps.storage, (cw721, &listener));
+            COLLECTION_HOOKS.save(deps.storage, (cw721, &listener), &())?;
+            old_hooked
+        }
+        HookSubject::Address(addr) => {
+            let old_hooked = ADDRESS_HOOKS.has(deps.storage, (addr, &listener));
+            ADDRESS_HOOKS.save(deps.storage, (addr, &listener), &())?;
+            old_hooked
+        }
+    };
+    let event = hook_event(deps.storage, &msg.subject, &listener, old_hooked, true)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "add_hook")
+        .add_attribute("listener", listener))
+}
+
+pub fn execute_remove_hook<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    msg: HookMsg,
+) -> Result<Response, ContractError> {
+    // Listeners can unsubscribe themselves
+    if info.sender != msg.listener
+        && !can_manage_hooks(deps.storage, config, &info.sender, &msg.subject)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let old_hooked = match &msg.subject {
+        HookSubject::Collection(cw721) => {
+            let old_hooked = COLLECTION_HOOKS.has(deps.storage, (cw721, &msg.listener));
+            COLLECTION_HOOKS.remove(deps.storage, (cw721, &msg.listener));
+            old_hooked
+        }
+        HookSubject::Address(addr) => {
+            let old_hooked = ADDRESS_HOOKS.has(deps.storage, (addr, &msg.listener));
+            ADDRESS_HOOKS.remove(deps.storage, (addr, &msg.listener));
+            old_hooked
+        }
+    };
+    let event = hook_event(deps.storage, &msg.subject, &msg.listener, old_hooked, false)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "remove_hook")
+        .add_attribute("listener", msg.listener))
+}
+
+// Collection hooks are managed by the curator, address hooks by the address
+fn can_manage_hooks<C: MarketConfig>(
+    storage: &dyn Storage,
+    config: &C,
+    sender: &Addr,
+    subject: &HookSubject,
+) -> bool {
+    match subject {
+        HookSubject::Collection(_) => has_role(storage, config, sender, Role::Curator),
+        HookSubject::Address(addr) => sender == addr,
+    }
+}
+
+pub fn execute_block<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    msg: BlocklistMsg,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, config, &info.sender, Role::Curator) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut events = vec![];
+    for address in msg.addresses.iter() {
+        let address = deps.api.addr_validate(address.as_str())?;
+        let old_blocked = BLOCKED_ADDRESSES.has(deps.storage, &address);
+        BLOCKED_ADDRESSES.save(deps.storage, &address, &())?;
+        events.push(block_address_event(
+            deps.storage,
+            &address,
+            old_blocked,
+            true,
+        )?);
+    }
+    for token in msg.tokens.iter() {
+        let key = (&token.cw721, token.token_id.as_str());
+        let old_blocked = BLOCKED_TOKENS.has(deps.storage, key);
+        BLOCKED_TOKENS.save(deps.storage, key, &())?;
+        events.push(block_token_event(deps.storage, token, old_blocked, true)?);
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("action", "block")
+        .add_attribute("addresses", msg.addresses.len().to_string())
+        .add_attribute("tokens", msg.tokens.len().to_string()))
+}
+
+pub fn execute_unblock<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    msg: BlocklistMsg,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, config, &info.sender, Role::Curator) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut events = vec![];
+    for address in msg.addresses.iter() {
+        let old_blocked = BLOCKED_ADDRESSES.has(deps.storage, address);
+        BLOCKED_ADDRESSES.remove(deps.storage, address);
+        events.push(block_address_event(
+            deps.storage,
+            address,
+            old_blocked,
+            false,
+        )?);
+    }
+    for token in msg.tokens.iter() {
+        let key = (&token.cw721, token.token_id.as_str());
+        let old_blocked = BLOCKED_TOKENS.has(deps.storage, key);
+        BLOCKED_TOKENS.remove(deps.storage, key);
+        events.push(block_token_event(deps.storage, token, old_blocked, false)?);
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("action", "unblock")
+        .add_attribute("addresses", msg.addresses.len().to_string())
+        .add_attribute("tokens", msg.tokens.len().to_string()))
+}
+
+pub fn execute_update_swap_id_rules<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    rules: SwapIdRules,
+) -> Result<Response, ContractError> {
+    if !is_admin(config, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if rules.max_length == 0 {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    let old = swap_id_rules(deps.storage)?;
+    SWAP_ID_RULES.save(deps.storage, &rules)?;
+    let event = swap_id_rules_event(deps.storage, &old, &rules)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "update_swap_id_rules")
+        .add_attribute("max_length", rules.max_length.to_string()))
+}
+
+pub fn execute_update_expiration_rules<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    rules: ExpirationRules,
+) -> Result<Response, ContractError> {
+    if !is_admin(config, &info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_expiration_rules(&rules)?;
+
+    let old = expiration_rules(deps.storage)?;
+    EXPIRATION_RULES.save(deps.storage, &rules)?;
+    let event = expiration_rules_event(deps.storage, &old, &rules)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "update_expiration_rules")
+        .add_attribute("rules", to_json_string(&rules)?))
+}
+
+/// Durations and the block time must be positive
+pub fn check_expiration_rules(rules: &ExpirationRules) -> Result<(), ContractError> {
+    let durations = [
+        rules.max_listing_duration,
+        rules.max_offer_duration,
+        rules.default_duration,
+    ];
+    if rules.block_time == 0 || durations.contains(&Some(0)) {
+        return Err(ContractError::InvalidInput {});
+    }
+    Ok(())
+}
+
+pub fn execute_update_min_price<C: MarketConfig>(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: &C,
+    payment_token: Option<Addr>,
+    min_price: Uint128,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, config, &info.sender, Role::FeeManager) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token = match &payment_token {
+        Some(token) => token.to_string(),
+        None => config.denom().to_string(),
+    };
+    // A zero minimum price removes the minimum
+    let old = MIN_PRICES
+        .may_load(deps.storage, &token)?
+        .unwrap_or_default();
+    if min_price.is_zero() {
+        MIN_PRICES.remove(deps.storage, &token);
+    } else {
+        MIN_PRICES.save(deps.storage, &token, &min_price)?;
+    }
+    let event = min_price_event(deps.storage, &token, old, min_price)?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "update_min_price")
+        .add_attribute("payment_token", token)
+        .add_attribute("min_price", min_price))
+}
+
+pub fn execute_batch_create<C: MarketConfig>(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msgs: Vec<SwapMsg>,
+) -> Result<Response, ContractError> {
+    check_batch_size(msgs.len())?;
+
+    let mut ids: Vec<String> = vec![];
+    let mut responses: Vec<Response> = vec![];
+    for msg in msgs {
+        let res = execute_create(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            config,
+            policy,
+            msg,
+        )?;
+        if let Some(data) = &res.data {
+            let created: CreateResponse = from_json(data)?;
+            ids.push(created.id);
+        }
+        responses.push(res);
+    }
+
+    Ok(batch_response("batch_create", responses)
+        .set_data(to_json_binary(&BatchCreateResponse { ids })?))
+}
+
+pub fn execute_batch_update<C: MarketConfig>(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    msgs: Vec<UpdateMsg>,
+) -> Result<Response, ContractError> {
+    check_batch_size(msgs.len())?;
+
+    let responses = msgs
+        .into_iter()
+        .map(|msg| execute_update(deps.branch(), env.clone(), info.clone(), config, msg))
+        .collect::<Result<Vec<Response>, ContractError>>()?;
+
+    Ok(batch_response("batch_update", responses))
+}
+
+pub fn execute_batch_cancel(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    check_batch_size(ids.len())?;
+
+    let responses = ids
+        .into_iter()
+        .map(|id| execute_cancel(deps.branch(), env.clone(), info.clone(), CancelMsg { id }))
+        .collect::<Result<Vec<Response>, ContractError>>()?;
+
+    Ok(batch_response("batch_cancel", responses))
+}
+
+pub fn execute_batch_finish<C: MarketConfig>(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    ids: Vec<String>,
+) -> Result<Response, ContractError> {
+    check_batch_size(ids.len())?;
+
+    // Native sales are paid for with a single payment covering all their prices,
+    // each swap is then settled with exactly its own price
+    let swaps = ids
+        .iter()
+        .map(|id| SWAPS.load(deps.storage, id))
+        .collect::<Result<Vec<CW721Swap>, _>>()?;
+    let native_price = |swap: &CW721Swap| -> Option<Uint128> {
+        (swap.payment_token.is_none() && swap.swap_type == SwapType::Sale).then_some(swap.price)
+    };
+    let total = swaps
+        .iter()
+        .filter_map(native_price)
+        .try_fold(Uint128::zero(), |total, price| total.checked_add(price))
+        .map_err(|_| ContractError::InvalidInput {})?;
+    if total.is_zero() {
+        if !info.funds.is_empty() {
+            return Err(ContractError::ExactFunds {});
+        }
+    } else {
+        if info.funds.len() != 1 {
+            return Err(ContractError::ExactFunds {});
+        }
+        check_sent_required_payment_exact(
+            &info.funds,
+            Some(Coin {
+                denom: config.denom().to_string(),
+                amount: total,
+            }),
+        )?;
+    }
+
+    let mut responses: Vec<Response> = vec![];
+    for swap in swaps {
+        let funds = match native_price(&swap) {
+            Some(price) => vec![Coin {
+                denom: config.denom().to_string(),
+                amount: price,
+            }],
+            None => vec![],
+        };
+        let item_info = MessageInfo {
+            sender: info.sender.clone(),
+            funds,
+        };
+        let msg = FinishSwapMsg { id: swap.id };
+        responses.push(execute_finish(
+            deps.branch(),
+            env.clone(),
+            item_info,
+            config,
+            policy,
+            msg,
+        )?);
+    }
+
+    Ok(batch_response("batch_finish", responses))
+}
+
+pub fn execute_sweep<C: MarketConfig>(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    msg: SweepMsg,
+) -> Result<Response, ContractError> {
+    check_batch_size(msg.max_items as usize)?;
+    // If no cw721 permission, revert
+    if !policy.allows(deps.storage, &msg.cw721) {
+        return Err(ContractError::Unauthorized {});
+    }
+    // If trading is paused, revert
+    if is_paused(deps.storage, &SwapType::Sale, &msg.cw721)? {
+        return Err(ContractError::Paused {});
+    }
+    // If sender is blocklisted, revert
+    if address_blocked(deps.storage, &info.sender) {
+        return Err(ContractError::BlockedAddress {});
+    }
+
+    // Native listings are paid from the funds sent, and unspent funds are refunded;
+    // cw20 listings are paid from the sender's allowance
+    let mut budget: Uint128 = if let Some(token) = &msg.payment_token {
+        if !info.funds.is_empty() {
+            return Err(ContractError::InvalidPaymentToken {});
+        }
+        let allowance: cw20::AllowanceResponse = deps.querier.query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Allowance {
+                owner: info.sender.to_string(),
+                spender: env.contract.address.to_string(),
+            },
+        )?;
+        let balance: BalanceResponse = deps.querier.query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: info.sender.to_string(),
+            },
+        )?;
+        allowance.allowance.min(balance.balance)
+    } else {
+        if info.funds.iter().any(|coin| coin.denom != config.denom()) {
+            return Err(ContractError::InvalidPaymentToken {});
+        }
+        info.funds
+            .iter()
+            .map(|coin| coin.amount)
+            .fold(Uint128::zero(), |total, amount| total + amount)
+    };
+    let sent = budget;
+
+    // Cheapest listings first
+    let candidates = SALE_PRICES
+        .sub_prefix(&msg.cw721)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(MAX_SWEEP_SCAN)
+        .collect::<StdResult<Vec<(u128, String)>>>()?;
+
+    let mut bought: Vec<SweptSwap> = vec![];
+    let mut responses: Vec<Response> = vec![];
+    for (price, id) in candidates {
+        if bought.len() >= msg.max_items as usize || price > msg.max_price_per_item.u128() {
+            break;
+        }
+        let price = Uint128::from(price);
+        if price > budget {
+            break;
+        }
+        // Finishing a swap removes the other swaps of its token
+        let swap = match SWAPS.may_load(deps.storage, &id)? {
+            Some(swap) => swap,
+            None => continue,
+        };
+        if swap.payment_token != msg.payment_token
+            || swap.creator == info.sender
+            || !sweep_fillable(&deps, &env, &swap)
+        {
+            continue;
+        }
+
+        let funds = if swap.payment_token.is_none() {
+            // Zero priced native listings can't be settled
+            if price.is_zero() {
+                continue;
+            }
+            vec![Coin {
+                denom: config.denom().to_string(),
+                amount: price,
+            }]
+        } else {
+            vec![]
+        };
+        let item_info = MessageInfo {
+            sender: info.sender.clone(),
+            funds,
+        };
+        // Swaps which can't be finished (e.g. expired or blocked) are skipped,
+        // `execute_finish` fails before changing any state
+        let finish = FinishSwapMsg {
+            id: swap.id.clone(),
+        };
+        let res = match execute_finish(
+            deps.branch(),
+            env.clone(),
+            item_info,
+            config,
+            policy,
+            finish,
+        ) {
+            Ok(res) => res,
+            Err(_) => continue,
+        };
+        budget -= price;
+        bought.push(SweptSwap {
+            id: swap.id,
+            token_id: swap.token_id,
+            price,
+        });
+        responses.push(res);
+    }
+
+    let mut res =
+        batch_response("sweep", responses).set_data(to_json_binary(&SweepResponse { bought })?);
+    // Refund unspent native funds
+    if msg.payment_token.is_none() && !budget.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: config.denom().to_string(),
+                amount: budget,
+            }],
+        });
+    }
+    Ok(res.add_attribute("spent", sent - budget))
+}
+
+// Whether the creator of a sale still owns its token,
+// and the marketplace is still approved to transfer it
+fn sweep_fillable(deps: &DepsMut, env: &Env, swap: &CW721Swap) -> bool {
+    let owner = match query_name_owner(&swap.token_id, &swap.nft_contract, deps) {
+        Ok(res) => res.owner,
+        Err(_) => return false,
+    };
+    if owner != swap.creator {
+        return false;
+    }
+    let approval: StdResult<cw721::ApprovalResponse> = deps.querier.query_wasm_smart(
+        &swap.nft_contract,
+        &cw721::Cw721QueryMsg::Approval {
+            token_id: swap.token_id.clone(),
+            spender: env.contract.address.to_string(),
+            include_expired: None,
+        },
+    );
+    if approval.is_ok() {
+        return true;
+    }
+    // Otherwise the marketplace may be an operator of the owner
+    let operators: StdResult<cw721::OperatorsResponse> = deps.querier.query_wasm_smart(
+        &swap.nft_contract,
+        &cw721::Cw721QueryMsg::AllOperators {
+            owner,
+            include_expired: None,
+            start_after: None,
+            limit: None,
+        },
+    );
+    operators.is_ok_and(|res| {
+        res.operators
+            .iter()
+            .any(|approval| approval.spender == env.contract.address.as_str())
+    })
+}
+
+fn check_batch_size(size: usize) -> Result<(), ContractError> {
+    if size == 0 || size > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize {});
+    }
+    Ok(())
+}
+
+// Combines the responses of a batch, each item is
+// attributed by an event named after its action
+fn batch_response(action: &str, responses: Vec<Response>) -> Response {
+    let mut res = Response::new()
+        .add_attribute("action", action)
+        .add_attribute("count", responses.len().to_string());
+    for item in responses {
+        let item_action = item
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "action")
+            .map(|attr| attr.value.clone())
+            .unwrap_or_else(|| action.to_string());
+        res = res
+            .add_event(Event::new(item_action).add_attributes(item.attributes))
+            .add_events(item.events)
+            .add_submessages(item.messages);
+    }
+    res
+}
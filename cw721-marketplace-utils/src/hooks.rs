@@ -0,0 +1,110 @@
+//! Registry of listeners notified of marketplace activity, modeled on cw-hooks.
+//! Hooks are fire-and-forget: failed hooks are caught in `reply` and never
+//! revert the action that triggered them
+use cosmwasm_std::{
+    to_json_binary, Addr, Order, Reply, Response, StdResult, Storage, SubMsg, SubMsgResult,
+    Uint128, WasmMsg,
+};
+use cw20::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::CW721Swap;
+use crate::state::{ADDRESS_HOOKS, COLLECTION_HOOKS};
+
+// Reply id of hook submessages
+pub const HOOK_REPLY_ID: u64 = 1;
+
+// Gas a hook can use before it's considered failed
+pub const HOOK_GAS_LIMIT: u64 = 300_000;
+
+// What listeners subscribe to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookSubject {
+    /// Activity on any token of a collection
+    Collection(Addr),
+    /// Activity on tokens owned by an address
+    Address(Addr),
+}
+
+// Message executed on listeners
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookExecuteMsg {
+    OfferHook(OfferHookMsg),
+}
+
+// An offer was made on a token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OfferHookMsg {
+    pub swap_id: String,
+    pub cw721: Addr,
+    pub token_id: String,
+    pub owner: Option<Addr>, // `None` if the token owner couldn't be queried
+    pub bidder: Addr,
+    pub payment_token: Option<Addr>,
+    pub price: Uint128,
+    pub expires: Expiration,
+}
+
+/// Listeners subscribed to `subject`
+pub fn hooks_of(storage: &dyn Storage, subject: &HookSubject) -> StdResult<Vec<Addr>> {
+    let map = match subject {
+        HookSubject::Collection(addr) => COLLECTION_HOOKS.prefix(addr),
+        HookSubject::Address(addr) => ADDRESS_HOOKS.prefix(addr),
+    };
+    map.keys(storage, None, None, Order::Ascending).collect()
+}
+
+/// Notifications of `offer` to the listeners of its collection and of the token owner
+pub fn offer_hooks(
+    storage: &dyn Storage,
+    offer: &CW721Swap,
+    owner: Option<Addr>,
+) -> StdResult<Vec<SubMsg>> {
+    let mut listeners = hooks_of(
+        storage,
+        &HookSubject::Collection(offer.nft_contract.clone()),
+    )?;
+    if let Some(owner) = &owner {
+        for listener in hooks_of(storage, &HookSubject::Address(owner.clone()))? {
+            if !listeners.contains(&listener) {
+                listeners.push(listener);
+            }
+        }
+    }
+
+    let msg = to_json_binary(&HookExecuteMsg::OfferHook(OfferHookMsg {
+        swap_id: offer.id.clone(),
+        cw721: offer.nft_contract.clone(),
+        token_id: offer.token_id.clone(),
+        owner,
+        bidder: offer.creator.clone(),
+        payment_token: offer.payment_token.clone(),
+        price: offer.price,
+        expires: offer.expires,
+    }))?;
+    Ok(listeners
+        .into_iter()
+        .map(|listener| {
+            let execute = WasmMsg::Execute {
+                contract_addr: listener.to_string(),
+                msg: msg.clone(),
+                funds: vec![],
+            };
+            SubMsg::reply_on_error(execute, HOOK_REPLY_ID).with_gas_limit(HOOK_GAS_LIMIT)
+        })
+        .collect())
+}
+
+/// Reply to a failed hook, the failure is recorded and otherwise ignored
+pub fn hook_failed(msg: Reply) -> Response {
+    let error = match msg.result {
+        SubMsgResult::Err(err) => err,
+        SubMsgResult::Ok(_) => String::new(),
+    };
+    Response::new()
+        .add_attribute("action", "hook_failed")
+        .add_attribute("error", error)
+}
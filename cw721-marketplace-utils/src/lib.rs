@@ -1,14 +1,47 @@
-use cosmwasm_std::{Uint128, Uint256};
+use cosmwasm_std::{Addr, Uint128, Uint256};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+mod error;
+pub mod events;
+pub mod execute;
+pub mod hooks;
+pub mod migrate;
+pub mod msg;
+pub mod payment;
+pub mod policy;
+pub mod query;
+mod roles;
+pub mod settlement;
+pub mod state;
 mod swap;
-mod query;
+// Tests carried over from the marketplace contracts keep their original style
+#[cfg(feature = "testing")]
+#[allow(
+    clippy::clone_on_copy,
+    clippy::let_and_return,
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::unnecessary_mut_passed,
+    clippy::useless_conversion
+)]
+pub mod testing;
+pub mod traits;
+
+pub use crate::error::ContractError;
 
 pub mod prelude {
-    pub use crate::swap::{CW721Swap, SwapType};
-    pub use crate::query::{PageResult, ListResponse, DetailsResponse};
     pub use crate::fee_percentage;
+    pub use crate::policy::{Allowlist, CollectionPolicy, Open, Single};
+    pub use crate::query::{
+        DetailsResponse, ListResponse, Negotiation, NegotiationsResponse, PageResult,
+        SimulateFinishResponse, SimulatedTransfer, TransferType,
+    };
+    pub use crate::roles::Role;
+    pub use crate::state::{Config, MarketConfig};
+    pub use crate::swap::{ArchivedSwap, CW721Swap, Rental, SwapStatus, SwapType};
+    pub use crate::traits::{OnchainMetadata, Trait, TraitReader};
+    pub use crate::{weighted_shares, FeeRecipient};
 }
 
 // Fee split result
@@ -22,27 +55,60 @@ impl FeeSplit {
     pub fn only_seller(amount: Uint128) -> Self {
         Self {
             marketplace: Uint128::zero(),
-            seller: amount
+            seller: amount,
         }
     }
 }
 
+// Marketplace fee recipient and its relative share of collected fees
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeRecipient {
+    pub address: Addr,
+    pub weight: u64,
+}
+
 pub fn fee_percentage(amount: Uint128, share_percent: u64) -> Uint128 {
     // Allocate extra space for the two decimal places
     let amount = Uint256::from_uint128(amount) * Uint256::from_u128(100);
 
     // Get percentage and divide by 10 ** 4 (both decimal spots added up)
     let fee = (amount * Uint256::from(share_percent))
-        .checked_div(Uint256::from(10000u16)).unwrap_or(Uint256::zero());
+        .checked_div(Uint256::from(10000u16))
+        .unwrap_or(Uint256::zero());
 
     // We can safely unwrap since we've tested against u128::MAX
     fee.try_into().unwrap()
 }
 
+/// Splits `amount` between `recipients` proportionally to their weights.
+/// Each share is rounded down; the remaining dust goes to the first recipient
+/// so the shares always add up to `amount`.
+pub fn weighted_shares(amount: Uint128, recipients: &[FeeRecipient]) -> Vec<(Addr, Uint128)> {
+    let total_weight: u64 = recipients.iter().map(|r| r.weight).sum();
+    if total_weight == 0 {
+        return vec![];
+    }
+
+    let mut shares: Vec<(Addr, Uint128)> = recipients
+        .iter()
+        .map(|r| {
+            let share = Uint256::from_uint128(amount) * Uint256::from(r.weight)
+                / Uint256::from(total_weight);
+            // A share can never exceed `amount`, so it fits in a Uint128
+            (r.address.clone(), share.try_into().unwrap())
+        })
+        .collect();
+
+    let distributed: Uint128 = shares.iter().map(|s| s.1).sum();
+    shares[0].1 += amount - distributed;
+
+    shares
+}
+
 #[cfg(test)]
 mod test {
-    use cosmwasm_std::Uint128;
-    use crate::fee_percentage;
+    use crate::{fee_percentage, weighted_shares, FeeRecipient};
+    use cosmwasm_std::{Addr, Uint128};
 
     #[test]
     fn fee_percentage_overflow() {
@@ -72,4 +138,37 @@ mod test {
         assert_eq!(fee_percentage(Uint128::zero(), 10), Uint128::zero());
         assert_eq!(fee_percentage(Uint128::zero(), 100), Uint128::zero());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn weighted_shares_dust() {
+        let recipients = vec![
+            FeeRecipient {
+                address: Addr::unchecked("treasury"),
+                weight: 70,
+            },
+            FeeRecipient {
+                address: Addr::unchecked("partner"),
+                weight: 30,
+            },
+        ];
+
+        // Exact split
+        let shares = weighted_shares(Uint128::new(1000), &recipients);
+        assert_eq!(shares[0], (Addr::unchecked("treasury"), Uint128::new(700)));
+        assert_eq!(shares[1], (Addr::unchecked("partner"), Uint128::new(300)));
+
+        // Dust goes to the first recipient
+        let shares = weighted_shares(Uint128::new(11), &recipients);
+        assert_eq!(shares[0].1, Uint128::new(8));
+        assert_eq!(shares[1].1, Uint128::new(3));
+
+        // Largest amount doesn't overflow
+        let shares = weighted_shares(Uint128::MAX, &recipients);
+        assert_eq!(shares[0].1 + shares[1].1, Uint128::MAX);
+
+        // Nothing to split
+        let shares = weighted_shares(Uint128::zero(), &recipients);
+        assert!(shares.iter().all(|s| s.1.is_zero()));
+        assert!(weighted_shares(Uint128::new(10), &[]).is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! State migrations of deployed marketplaces. Swaps are migrated in batches
+//! so large `SWAPS` maps don't exceed the gas limit of a single migration;
+//! `MigrateMsg::Continue` migrates the next batch until all swaps are done.
+//! Swaps that never expire are capped once expiration rules are set, see
+//! `ExpirationRules::never_duration`
+use cosmwasm_std::{Addr, BlockInfo, Event, Order, Response, StdResult, Storage};
+use cw2::{get_contract_version, set_contract_version};
+use cw20::Expiration;
+use cw_storage_plus::{Bound, Item};
+use schemars::JsonSchema;
+use semver::Version;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::events::update_event;
+use crate::execute::check_expiration_rules;
+use crate::msg::MigrateMsg;
+use crate::prelude::CW721Swap;
+use crate::prelude::MarketConfig;
+use crate::state::{
+    config_item, expiration_rules, reindex_swap, save_swap, ExpirationRules, CONFIG_KEY,
+    EXPIRATION_RULES, MIGRATION, SWAPS,
+};
+
+pub const DEFAULT_MIGRATION_BATCH: u32 = 100;
+pub const MAX_MIGRATION_BATCH: u32 = 1000;
+
+// Config stored by v0.1.x marketplaces trading more than one
+// collection, whose admin role couldn't be renounced
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ConfigV0_1 {
+    pub admin: Addr,
+    pub denom: String,
+    pub fees: u64,
+}
+
+// Progress of a batched swap migration
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrationProgress {
+    pub from_version: String,
+    pub last_id: Option<String>, // Last migrated swap, `None` until the first batch
+    pub migrated: u64,
+}
+
+/// Migrates marketplace `name` to `version` along the upgrade path of `msg`;
+/// `from_v0_1` converts the v0.1.x config `Old` to the current config `C`
+pub fn migrate_marketplace<Old, C>(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    name: &str,
+    version: &str,
+    msg: MigrateMsg,
+    from_v0_1: impl FnOnce(Old) -> C,
+) -> Result<Response, ContractError>
+where
+    Old: Serialize + DeserializeOwned,
+    C: MarketConfig,
+{
+    let stored = get_contract_version(storage)?;
+    if stored.contract != name {
+        return Err(ContractError::InvalidInput {});
+    }
+    let is_v0_1 = stored.version.starts_with("0.1.");
+
+    match msg {
+        MigrateMsg::FromV0_1 {
+            batch_size,
+            expiration_rules,
+        } => {
+            if !is_v0_1 {
+                return Err(ContractError::UnsupportedMigration {
+                    version: stored.version,
+                });
+            }
+            let config: Old = Item::new(CONFIG_KEY).load(storage)?;
+            config_item::<C>().save(storage, &from_v0_1(config))?;
+            save_expiration_rules(storage, expiration_rules)?;
+            set_contract_version(storage, name, version)?;
+            start_migration(storage, &stored.version)?;
+            migrate_swaps::<C>(storage, block, batch_size)
+        }
+        MigrateMsg::Continue { batch_size } => migrate_swaps::<C>(storage, block, batch_size),
+        MigrateMsg::Upgrade {
+            expiration_rules,
+            batch_size,
+        } => {
+            // v0.1.x state must be migrated with `FromV0_1`
+            if is_v0_1 || !is_older(&stored.version, version) {
+                return Err(ContractError::UnsupportedMigration {
+                    version: stored.version,
+                });
+            }
+            set_contract_version(storage, name, version)?;
+            // Swaps are only migrated to cap their expirations
+            if save_expiration_rules(storage, expiration_rules)? {
+                start_migration(storage, &stored.version)?;
+                return migrate_swaps::<C>(storage, block, batch_size);
+            }
+            Ok(Response::new()
+                .add_attribute("action", "migrate")
+                .add_attribute("from_version", stored.version)
+                .add_attribute("done", "true"))
+        }
+    }
+}
+
+// Whether `stored` is an older version than `version`, compared as semver
+// versions (0.9.x is older than 0.10.0); unparsable versions are never older
+fn is_older(stored: &str, version: &str) -> bool {
+    match (Version::parse(stored), Version::parse(version)) {
+        (Ok(stored), Ok(version)) => stored < version,
+        _ => false,
+    }
+}
+
+// Saves the expiration rules of a migration, if any
+fn save_expiration_rules(
+    storage: &mut dyn Storage,
+    rules: Option<ExpirationRules>,
+) -> Result<bool, ContractError> {
+    match rules {
+        Some(rules) => {
+            check_expiration_rules(&rules)?;
+            EXPIRATION_RULES.save(storage, &rules)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Starts migrating the swaps of a marketplace deployed at `from_version`
+pub fn start_migration(storage: &mut dyn Storage, from_version: &str) -> Result<(), ContractError> {
+    if MIGRATION.may_load(storage)?.is_some() {
+        return Err(ContractError::MigrationInProgress {});
+    }
+    let progress = MigrationProgress {
+        from_version: from_version.to_string(),
+        last_id: None,
+        migrated: 0,
+    };
+    Ok(MIGRATION.save(storage, &progress)?)
+}
+
+/// Errors while a migration of the swaps is in progress, swaps can't be
+/// used until they are all migrated
+pub fn check_no_migration(storage: &dyn Storage) -> Result<(), ContractError> {
+    if MIGRATION.may_load(storage)?.is_some() {
+        return Err(ContractError::MigrationInProgress {});
+    }
+    Ok(())
+}
+
+/// Migrates the next `batch_size` swaps of a marketplace with config `C`,
+/// ending the migration after the last one
+pub fn migrate_swaps<C: MarketConfig>(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    batch_size: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut progress = MIGRATION
+        .may_load(storage)?
+        .ok_or(ContractError::NoMigration {})?;
+    let limit = batch_size.unwrap_or(DEFAULT_MIGRATION_BATCH);
+    if limit == 0 || limit > MAX_MIGRATION_BATCH {
+        return Err(ContractError::InvalidInput {});
+    }
+    let limit = limit as usize;
+
+    // One more swap is read to know if this is the last batch
+    let start = progress.last_id.as_deref().map(Bound::exclusive);
+    let mut swaps = SWAPS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let done = swaps.len() <= limit;
+    swaps.truncate(limit);
+
+    // Swaps have the same layout since v0.1.x, only their indexes are rebuilt
+    let rules = expiration_rules(storage)?;
+    let config = config_item::<C>().load(storage)?;
+    let mut events = vec![];
+    for (_, swap) in &swaps {
+        reindex_swap(storage, swap)?;
+        if let Some(event) = cap_expiration(storage, block, &rules, swap, config.denom())? {
+            events.push(event);
+        }
+    }
+    progress.migrated += swaps.len() as u64;
+    progress.last_id = swaps.pop().map(|(id, _)| id).or(progress.last_id);
+    if done {
+        MIGRATION.remove(storage);
+    } else {
+        MIGRATION.save(storage, &progress)?;
+    }
+
+    let capped = events.len();
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", progress.from_version)
+        .add_attribute("migrated", progress.migrated.to_string())
+        .add_attribute("capped", capped.to_string())
+        .add_attribute("done", done.to_string()))
+}
+
+// Swaps that never expire expire after the default duration of their type,
+// from the migration on; returns the update event of a capped swap
+fn cap_expiration(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    rules: &ExpirationRules,
+    swap: &CW721Swap,
+    denom: &str,
+) -> StdResult<Option<Event>> {
+    let (Expiration::Never {}, Some(duration)) =
+        (&swap.expires, rules.never_duration(&swap.swap_type))
+    else {
+        return Ok(None);
+    };
+    let capped = CW721Swap {
+        expires: Expiration::AtTime(block.time.plus_seconds(duration)),
+        ..swap.clone()
+    };
+    save_swap(storage, &capped)?;
+    Ok(Some(update_event(storage, block, swap, &capped, denom)?))
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::testing::{mock_env, MockStorage};
+    use cw2::{get_contract_version, set_contract_version};
+
+    use crate::msg::MigrateMsg;
+    use crate::state::{config_item, Config};
+
+    use super::{migrate_marketplace, ConfigV0_1};
+
+    const NAME: &str = "crates.io:archid-marketplace";
+
+    fn upgrade(storage: &mut MockStorage, version: &str) -> Result<(), String> {
+        migrate_marketplace(
+            storage,
+            &mock_env().block,
+            NAME,
+            version,
+            MigrateMsg::Upgrade {
+                expiration_rules: None,
+                batch_size: None,
+            },
+            |config: ConfigV0_1| Config {
+                admin: Some(config.admin),
+                denom: config.denom,
+                fees: config.fees,
+            },
+        )
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn upgrade_compares_semver_versions() {
+        let mut storage = MockStorage::new();
+        set_contract_version(&mut storage, NAME, "0.9.3").unwrap();
+        config_item::<Config>()
+            .save(
+                &mut storage,
+                &Config {
+                    admin: None,
+                    denom: "aarch".to_string(),
+                    fees: 0,
+                },
+            )
+            .unwrap();
+
+        upgrade(&mut storage, "0.10.0").unwrap();
+        assert_eq!(get_contract_version(&storage).unwrap().version, "0.10.0");
+
+        // Neither downgrades nor same version upgrades
+        assert_eq!(
+            upgrade(&mut storage, "0.9.4"),
+            Err("Can't migrate from version 0.10.0".to_string())
+        );
+        assert_eq!(
+            upgrade(&mut storage, "0.10.0"),
+            Err("Can't migrate from version 0.10.0".to_string())
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! Messages of the open marketplace; the permissioned and single collection
+//! marketplaces reuse its building blocks in their own message schemas
+use crate::hooks::HookSubject;
+use crate::prelude::{
+    DetailsResponse, FeeRecipient, ListResponse, NegotiationsResponse, PageResult, Rental, Role,
+    SimulateFinishResponse, SwapType, Trait,
+};
+use crate::state::{Config, ExpirationRules, SwapIdRules};
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw20::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: Addr,
+    pub denom: String,
+    pub fee_percentage: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    // Swap entry points
+    Create(SwapMsg),
+    Finish(FinishSwapMsg),
+    Cancel(CancelMsg),
+    Update(UpdateMsg),
+    UpdatePaymentToken(UpdatePaymentTokenMsg),
+    BatchCreate(Vec<SwapMsg>),
+    BatchFinish(Vec<String>),
+    BatchCancel(Vec<String>),
+    BatchUpdate(Vec<UpdateMsg>),
+    Sweep(SweepMsg),
+    Counter(CounterMsg),
+    DeclineCounter {
+        id: String,
+    },
+    ListRental(RentalMsg),
+    Rent(RentMsg),
+    CreateTraitOffer(TraitOfferMsg),
+    FillTraitOffer(FillTraitOfferMsg),
+
+    // Hook entry points
+    AddHook(HookMsg),
+    RemoveHook(HookMsg),
+
+    // Permissionless entry points
+    DistributeFees(DistributeFeesMsg),
+    /// Delists swaps of blocked addresses and tokens, looking
+    /// at `limit` swaps after `start_after` in id order
+    DelistBlocked {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // Admin entry points
+    UpdateConfig {
+        config: Config,
+    },
+    Withdraw(WithdrawMsg),
+    UpdateFeeRecipients {
+        recipients: Vec<FeeRecipient>,
+    },
+    ProposeAdmin {
+        admin: Addr,
+    },
+    AcceptAdmin {},
+    RenounceAdmin {},
+    GrantRole {
+        role: Role,
+        address: Addr,
+    },
+    RevokeRole {
+        role: Role,
+        address: Addr,
+    },
+    Pause {
+        scope: PauseScope,
+    },
+    Unpause {
+        scope: PauseScope,
+    },
+    Block(BlocklistMsg),
+    Unblock(BlocklistMsg),
+    UpdateSwapIdRules {
+        rules: SwapIdRules,
+    },
+    /// Sets the minimum price of swaps paid in `payment_token`, or in the
+    /// native denom if `None`; a zero `min_price` removes the minimum
+    UpdateMinPrice {
+        payment_token: Option<Addr>,
+        min_price: Uint128,
+    },
+    /// Sets the maximum durations of listings and offers, and the
+    /// duration given to swaps created without an expiration
+    UpdateExpirationRules {
+        rules: ExpirationRules,
+    },
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CancelMsg {
+    pub id: String,
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UpdateMsg {
+    pub id: String,
+    pub expires: Expiration,
+    pub price: Uint128,
+}
+// Changes the payment token of a swap, priced in the new token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UpdatePaymentTokenMsg {
+    pub id: String,
+    pub payment_token: Option<Addr>, // `None` for the native denom
+    pub price: Uint128,
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapMsg {
+    #[serde(default)]
+    pub id: Option<String>, // If `None` the contract assigns the next id of its swap counter
+    pub cw721: Addr,
+    pub payment_token: Option<Addr>, // Optional cw20 address; if `None` create swap for `aarch`
+    pub token_id: String,
+    pub expires: Expiration,
+    pub price: Uint128,
+    pub swap_type: SwapType,
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FinishSwapMsg {
+    pub id: String,
+}
+
+// Token owner's reply to offer `offer_id`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CounterMsg {
+    #[serde(default)]
+    pub id: Option<String>, // If `None` the contract assigns the next id of its swap counter
+    pub offer_id: String,
+    pub expires: Expiration,
+    pub price: Uint128,
+}
+
+// Lists a token for rent, the token is held in escrow until the listing is cancelled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RentalMsg {
+    #[serde(default)]
+    pub id: Option<String>, // If `None` the contract assigns the next id of its swap counter
+    pub cw721: Addr,
+    pub payment_token: Option<Addr>, // Optional cw20 address; if `None` rent for `aarch`
+    pub token_id: String,
+    pub expires: Expiration, // Until when the token can be rented
+    pub price: Uint128,      // Price per period
+    pub period: u64,         // Seconds
+    pub max_periods: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RentMsg {
+    pub id: String,
+    pub periods: u32,
+}
+
+// Offer for any token of `cw721` with all of `traits`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TraitOfferMsg {
+    #[serde(default)]
+    pub id: Option<String>, // If `None` the contract assigns the next id of its swap counter
+    pub cw721: Addr,
+    pub payment_token: Option<Addr>, // Required cw20 address, as for offers
+    pub expires: Expiration,
+    pub price: Uint128,
+    pub traits: Vec<Trait>,
+}
+
+// Token owner sells `token_id` to trait offer `id`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FillTraitOfferMsg {
+    pub id: String,
+    pub token_id: String,
+}
+
+// Subscribes `listener` to `subject`, collections are managed by the curator
+// and addresses by themselves; listeners can always unsubscribe themselves
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HookMsg {
+    pub subject: HookSubject,
+    pub listener: Addr,
+}
+
+// Buys the cheapest listings of a collection
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SweepMsg {
+    pub cw721: Addr,
+    pub max_items: u32,
+    pub max_price_per_item: Uint128,
+    pub payment_token: Option<Addr>, // Optional cw20 address; if `None` buy listings priced in `aarch`
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawMsg {
+    pub amount: Uint128,
+    pub denom: String,
+    pub payment_token: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributeFeesMsg {
+    pub payment_token: Option<Addr>, // Optional cw20 address; if `None` distribute `aarch`
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlocklistMsg {
+    #[serde(default)]
+    pub addresses: Vec<Addr>,
+    #[serde(default)]
+    pub tokens: Vec<BlockedToken>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlockedToken {
+    pub cw721: Addr,
+    pub token_id: String,
+}
+
+// What `Pause` and `Unpause` apply to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseScope {
+    All,
+    SwapType(SwapType),
+    Collection(Addr),
+}
+
+// Data of `Create` responses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreateResponse {
+    pub id: String,
+}
+
+// Data of `BatchCreate` responses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchCreateResponse {
+    pub ids: Vec<String>,
+}
+
+// Data of `Sweep` responses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SweepResponse {
+    pub bought: Vec<SweptSwap>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SweptSwap {
+    pub id: String,
+    pub token_id: String,
+    pub price: Uint128,
+}
+
+// Current user of a rented token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserResponse {
+    pub user: Option<Addr>,
+    pub expires: Option<Expiration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PausedResponse {
+    pub all: bool,
+    pub swap_types: Vec<SwapType>,
+    pub collections: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Get all swaps (enumerable)
+    #[returns(ListResponse)]
+    List {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Count total `SwapType::Offer` or `SwapType::Sale`
+    #[returns(u128)]
+    GetTotal { swap_type: Option<SwapType> },
+    /// Get all swaps of type `SwapType::Offer`
+    #[returns(PageResult)]
+    GetOffers {
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Get all swaps of type `SwapType::Sale`
+    #[returns(PageResult)]
+    GetListings {
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Get all listings for a token of type `Swap::Sale` and `Swap::Offer`
+    /// or both (`None`)
+    #[returns(PageResult)]
+    ListingsOfToken {
+        token_id: String,
+        cw721: Addr,
+        swap_type: Option<SwapType>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Get all offers for a token, each with the counter offers made to it
+    #[returns(NegotiationsResponse)]
+    NegotiationsOfToken {
+        token_id: String,
+        cw721: Addr,
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Query the renter of a token, if it's currently rented
+    #[returns(UserResponse)]
+    UserOf { token_id: String, cw721: Addr },
+    /// Query the terms and renter of a rental
+    #[returns(Rental)]
+    Rental { id: String },
+    /// Query the traits a token needs to fill a trait offer
+    #[returns(Vec<Trait>)]
+    Traits { id: String },
+    /// Get the listeners subscribed to a collection or address
+    #[returns(Vec<Addr>)]
+    Hooks { subject: HookSubject },
+    /// Show all swaps created by a specific address
+    /// Defaults to SwapType::Sale if no `swap_type`
+    #[returns(PageResult)]
+    SwapsOf {
+        address: Addr,
+        swap_type: Option<SwapType>,
+        cw721: Option<Addr>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Show all swaps of a given price range
+    #[returns(PageResult)]
+    SwapsByPrice {
+        min: Option<Uint128>,
+        max: Option<Uint128>,
+        swap_type: Option<SwapType>,
+        cw721: Option<Addr>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Show all swaps of a given denom (contract address)
+    /// Defaults to ARCH if no contract is sent
+    #[returns(PageResult)]
+    SwapsByDenom {
+        payment_token: Option<Addr>,
+        swap_type: Option<SwapType>,
+        cw721: Option<Addr>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Show all cw20 swaps, or all ARCH swaps
+    #[returns(PageResult)]
+    SwapsByPaymentType {
+        cw20: bool,
+        swap_type: Option<SwapType>,
+        cw721: Option<Addr>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the details of the named swap, error if not created.
+    #[returns(DetailsResponse)]
+    Details { id: String },
+
+    /// Runs the checks of finishing the named swap by `sender` with `funds`,
+    /// returning the error it would fail with, or the transfers it would make
+    #[returns(SimulateFinishResponse)]
+    SimulateFinish {
+        id: String,
+        sender: Addr,
+        funds: Vec<Coin>,
+    },
+
+    /// Query Config (useful for determining parameters for ExecuteMsg::UpdateConfig)
+    #[returns(Config)]
+    Config {},
+
+    /// Query marketplace fee recipients and their weights
+    #[returns(Vec<FeeRecipient>)]
+    FeeRecipients {},
+
+    /// Query the address proposed as next admin, if any
+    #[returns(Option<Addr>)]
+    PendingAdmin {},
+
+    /// Get all addresses granted a role (enumerable)
+    #[returns(Vec<Addr>)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Query what trading is currently paused for
+    #[returns(PausedResponse)]
+    Paused {},
+
+    /// Get all blocked addresses (enumerable)
+    #[returns(Vec<Addr>)]
+    BlockedAddresses {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Get all blocked tokens (enumerable)
+    #[returns(Vec<BlockedToken>)]
+    BlockedTokens {
+        start_after: Option<BlockedToken>,
+        limit: Option<u32>,
+    },
+
+    /// Query the rules client supplied swap ids must follow
+    #[returns(SwapIdRules)]
+    SwapIdRules {},
+
+    /// Query the bounds of swap expirations
+    #[returns(ExpirationRules)]
+    ExpirationRules {},
+
+    /// Query the minimum price of swaps paid in `payment_token`,
+    /// or in the native denom if `None`
+    #[returns(Uint128)]
+    MinPrice { payment_token: Option<Addr> },
+}
+
+// Upgrade paths; large swap maps are migrated in batches of
+// `batch_size` swaps (100 by default), see `migrate`. Setting
+// `expiration_rules` caps the swaps that never expire
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMsg {
+    /// Migrate the state of a v0.1.x marketplace, and its first batch of swaps
+    FromV0_1 {
+        batch_size: Option<u32>,
+        #[serde(default)]
+        expiration_rules: Option<ExpirationRules>,
+    },
+    /// Migrate the next batch of swaps of a migration in progress
+    Continue { batch_size: Option<u32> },
+    /// Upgrade from an older version with the same state layout; with
+    /// `expiration_rules`, swaps are then migrated in batches
+    Upgrade {
+        #[serde(default)]
+        expiration_rules: Option<ExpirationRules>,
+        #[serde(default)]
+        batch_size: Option<u32>,
+    },
+}
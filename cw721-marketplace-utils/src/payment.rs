@@ -2,31 +2,14 @@ use cosmwasm_std::{
     from_json, to_json_binary, Addr, BalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg, DepsMut,
     Env, QueryRequest, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
 };
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
 
 use cw20::Cw20ExecuteMsg;
-use cw721::OwnerOfResponse;
-use cw721_base::QueryMsg as Cw721QueryMsg;
-use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension};
-
-use cw721_marketplace_utils::{fee_percentage, prelude::CW721Swap, FeeSplit};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
 
 use crate::error::ContractError;
-use crate::state::CONFIG;
-
-// Default and Max page sizes for paginated queries
-const MAX_LIMIT: u32 = 100;
-const DEFAULT_LIMIT: u32 = 10;
-
-// Pagination query pagaination parameters for filtered swap queries
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct PageParams {
-    pub start: usize,
-    pub end: usize,
-    pub page: u32,
-    pub total: u128,
-}
+use crate::prelude::{weighted_shares, CW721Swap, FeeRecipient};
+use crate::state::MarketConfig;
+use crate::{fee_percentage, FeeSplit};
 
 // Read utils
 pub fn query_name_owner(
@@ -40,61 +23,12 @@ pub fn query_name_owner(
     };
     let req = QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: cw721.to_string(),
-        msg: to_json_binary(&query_msg).unwrap(),
+        msg: to_json_binary(&query_msg)?,
     });
     let res: OwnerOfResponse = deps.querier.query(&req)?;
     Ok(res)
 }
 
-pub fn calculate_page_params(
-    page: Option<u32>,
-    limit: Option<u32>,
-    total_results: u32,
-) -> Result<PageParams, StdError> {
-    let page: u32 = page.unwrap_or(0_u32);
-    let mut limit: u32 = limit.unwrap_or(DEFAULT_LIMIT);
-    // Calculate dynamic limit and last page size
-    if total_results < limit {
-        limit = total_results;
-    } else if limit < DEFAULT_LIMIT {
-        limit = DEFAULT_LIMIT;
-    } else if limit > MAX_LIMIT {
-        limit = MAX_LIMIT;
-    }
-    let modulo = if total_results > 0 {
-        total_results % limit
-    } else {
-        0
-    };
-    let last_page = if total_results == 0 {
-        0
-    } else if modulo > 0 {
-        total_results / limit
-    } else {
-        total_results / limit - 1
-    };
-    let page_size: u32 = if page == last_page {
-        match modulo {
-            0 => limit,
-            _ => modulo,
-        }
-    } else {
-        limit
-    };
-
-    // Results
-    let start = (page * limit) as usize;
-    let end = (start as u32 + page_size) as usize;
-    let res = PageParams {
-        start,
-        end,
-        page,
-        total: total_results as u128,
-    };
-
-    Ok(res)
-}
-
 pub fn check_sent_required_payment(
     sent: &[Coin],
     required: Option<Coin>,
@@ -144,10 +78,10 @@ pub fn check_sent_required_payment_exact(
 pub fn check_contract_balance_ok(
     env: Env,
     deps: &DepsMut,
+    config: &impl MarketConfig,
     required: Coin,
 ) -> Result<(), ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let required_denom: String = config.denom;
+    let required_denom: String = config.denom().to_string();
     if required.denom != required_denom {
         return Err(ContractError::InsufficientBalance {});
     }
@@ -181,17 +115,60 @@ pub fn handle_swap_transfers(
     details: CW721Swap,
     denom: String,
     fee_split: FeeSplit,
+    fee_recipients: &[FeeRecipient],
+) -> StdResult<Vec<CosmosMsg>> {
+    let cw721_callback = nft_transfer(&details.nft_contract, nft_receiver, &details.token_id)?;
+
+    let mut msgs = vec![cw721_callback];
+    msgs.extend(payment_transfers(
+        env,
+        nft_receiver,
+        nft_sender,
+        &details.payment_token,
+        denom,
+        fee_split,
+        fee_recipients,
+    )?);
+    Ok(msgs)
+}
+
+/// Transfer of `token_id` of `cw721` from the marketplace, or from an
+/// owner that approved the marketplace, to `recipient`
+pub fn nft_transfer(cw721: &Addr, recipient: &Addr, token_id: &str) -> StdResult<CosmosMsg> {
+    let nft_transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: token_id.to_string(),
+    };
+
+    Ok(WasmMsg::Execute {
+        contract_addr: cw721.to_string(),
+        msg: to_json_binary(&nft_transfer_msg)?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// Payment of `fee_split` from `payer` to `payee` and the marketplace, either
+/// from the payer's cw20 allowance or from the native funds sent to the contract
+pub fn payment_transfers(
+    env: Env,
+    payer: &Addr,
+    payee: &Addr,
+    payment_token: &Option<Addr>,
+    denom: String,
+    fee_split: FeeSplit,
+    fee_recipients: &[FeeRecipient],
 ) -> StdResult<Vec<CosmosMsg>> {
     // cw20 swap
-    let payment_callback: CosmosMsg = if details.payment_token.is_some() {
+    let payment_callback: CosmosMsg = if let Some(token) = payment_token {
         let token_transfer_msg = Cw20ExecuteMsg::TransferFrom {
-            owner: nft_receiver.to_string(),
-            recipient: nft_sender.to_string(),
+            owner: payer.to_string(),
+            recipient: payee.to_string(),
             amount: fee_split.seller,
         };
 
         let cw20_callback: CosmosMsg = WasmMsg::Execute {
-            contract_addr: details.payment_token.clone().unwrap().into(),
+            contract_addr: token.to_string(),
             msg: to_json_binary(&token_transfer_msg)?,
             funds: vec![],
         }
@@ -200,12 +177,12 @@ pub fn handle_swap_transfers(
     // aarch swap
     } else {
         let payment_funds = ([Coin {
-            denom,
+            denom: denom.clone(),
             amount: fee_split.seller,
         }])
         .to_vec();
         let aarch_transfer_msg = BankMsg::Send {
-            to_address: nft_sender.to_string(),
+            to_address: payee.to_string(),
             amount: payment_funds,
         };
 
@@ -213,16 +190,18 @@ pub fn handle_swap_transfers(
         aarch_callback
     };
 
+    // Fees are retained by the contract unless fee recipients are configured
     let market_callback: Option<CosmosMsg> =
-        if details.payment_token.is_some() && fee_split.marketplace.u128() > 0 {
+        if payment_token.is_some() && fee_split.marketplace.u128() > 0 && fee_recipients.is_empty()
+        {
             let token_transfer_msg = Cw20ExecuteMsg::TransferFrom {
-                owner: nft_receiver.to_string(),
+                owner: payer.to_string(),
                 recipient: env.contract.address.to_string(),
                 amount: fee_split.marketplace,
             };
 
             let cw20_callback: CosmosMsg = WasmMsg::Execute {
-                contract_addr: details.payment_token.clone().unwrap().into(),
+                contract_addr: payment_token.clone().unwrap().into(),
                 msg: to_json_binary(&token_transfer_msg)?,
                 funds: vec![],
             }
@@ -232,29 +211,68 @@ pub fn handle_swap_transfers(
             None
         };
 
-    let nft_transfer_msg = Cw721ExecuteMsg::<Extension>::TransferNft {
-        recipient: nft_receiver.to_string(),
-        token_id: details.token_id.clone(),
-    };
-
-    let cw721_callback: CosmosMsg = WasmMsg::Execute {
-        contract_addr: details.nft_contract.to_string(),
-        msg: to_json_binary(&nft_transfer_msg)?,
-        funds: vec![],
-    }
-    .into();
-
-    let mut msgs = vec![cw721_callback, payment_callback];
+    let mut msgs = vec![payment_callback];
     if let Some(fees) = market_callback {
         msgs.push(fees);
     }
 
+    // Fee recipients are paid directly
+    if !fee_recipients.is_empty() {
+        for (recipient, amount) in weighted_shares(fee_split.marketplace, fee_recipients) {
+            if amount.is_zero() {
+                continue;
+            }
+            msgs.push(fee_transfer(
+                payment_token,
+                payer,
+                &recipient,
+                &denom,
+                amount,
+            )?);
+        }
+    }
+
     Ok(msgs)
 }
 
-pub fn fee_split(deps: &DepsMut, swap_price: Uint128) -> Result<FeeSplit, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let marketplace: Uint128 = fee_percentage(swap_price, config.fees);
+// Pays a fee share either from the buyer's cw20 allowance,
+// or from the native funds sent to the contract
+fn fee_transfer(
+    payment_token: &Option<Addr>,
+    payer: &Addr,
+    recipient: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    let msg: CosmosMsg = if let Some(token) = payment_token {
+        WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: payer.to_string(),
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into()
+    } else {
+        BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount,
+            }],
+        }
+        .into()
+    };
+    Ok(msg)
+}
+
+pub fn fee_split(
+    config: &impl MarketConfig,
+    swap_price: Uint128,
+) -> Result<FeeSplit, ContractError> {
+    let marketplace: Uint128 = fee_percentage(swap_price, config.fees());
     if marketplace.u128() >= swap_price.into() {
         return Err(ContractError::InvalidInput {});
     }
@@ -0,0 +1,48 @@
+use cosmwasm_std::{Addr, Storage};
+
+use crate::state::cw721_allowed;
+
+/// Rules for which cw721 collections can be traded in a marketplace
+pub trait CollectionPolicy {
+    /// Whether swaps of `cw721` can be created and finished
+    fn allows(&self, storage: &dyn Storage, cw721: &Addr) -> bool;
+
+    /// Collection swap queries are limited to, given the
+    /// collection filter of the query (if any)
+    fn scope(&self, requested: Option<Addr>) -> Option<Addr> {
+        requested
+    }
+}
+
+/// Any cw721 collection can be traded (`cw721-marketplace`)
+pub struct Open;
+
+impl CollectionPolicy for Open {
+    fn allows(&self, _storage: &dyn Storage, _cw721: &Addr) -> bool {
+        true
+    }
+}
+
+/// Only collections curated by the admin can be traded (`cw721-marketplace-permissioned`)
+pub struct Allowlist;
+
+impl CollectionPolicy for Allowlist {
+    fn allows(&self, storage: &dyn Storage, cw721: &Addr) -> bool {
+        cw721_allowed(storage, cw721)
+    }
+}
+
+/// Only the configured collection can be traded (`cw721-marketplace-single-collection`)
+pub struct Single {
+    pub cw721: Addr,
+}
+
+impl CollectionPolicy for Single {
+    fn allows(&self, _storage: &dyn Storage, cw721: &Addr) -> bool {
+        *cw721 == self.cw721
+    }
+
+    fn scope(&self, _requested: Option<Addr>) -> Option<Addr> {
+        Some(self.cw721.clone())
+    }
+}
@@ -1,8 +1,27 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw20::Expiration;
+use cosmwasm_std::{
+    from_json, Addr, BankMsg, Coin, CosmosMsg, Deps, Env, Order, StdError, StdResult, Uint128,
+    WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Expiration};
+use cw721::Cw721ExecuteMsg;
+use cw_storage_plus::Bound;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use crate::prelude::{CW721Swap, SwapType};
+
+use crate::execute::{check_settlement, finishable_swap, Settlement};
+use crate::hooks::{hooks_of, HookSubject};
+use crate::msg::{BlockedToken, PausedResponse, UserResponse};
+use crate::policy::CollectionPolicy;
+use crate::prelude::{CW721Swap, FeeRecipient, Rental, Role, SwapStatus, SwapType, Trait};
+use crate::state::{
+    all_swap_ids, counters_of, expiration_rules, fee_recipients, min_price, swap_id_rules,
+    token_user, ExpirationRules, MarketConfig, SwapIdRules, ARCHIVE, BLOCKED_ADDRESSES,
+    BLOCKED_TOKENS, PAUSE, PAUSED_CW721, PENDING_ADMIN, RENTALS, ROLES, SWAPS, TRAIT_OFFERS,
+};
+
+// Default and Max page sizes for paginated queries
+pub const MAX_LIMIT: u32 = 100;
+pub const DEFAULT_LIMIT: u32 = 10;
 
 // Pagination query result format for filtered swap queries
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -28,4 +47,734 @@ pub struct DetailsResponse {
     pub expires: Expiration,
     pub price: Uint128,
     pub swap_type: SwapType,
-}
\ No newline at end of file
+    pub status: SwapStatus,
+    pub filler: Option<Addr>, // Address that filled the swap, if `Filled`
+    pub close_height: Option<u64>, // Block height the swap was closed at, if closed
+}
+
+// Offer on a token and the counter offers made to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Negotiation {
+    pub offer: CW721Swap,
+    pub counters: Vec<CW721Swap>,
+}
+
+// Pagination query result format for negotiation threads
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NegotiationsResponse {
+    pub negotiations: Vec<Negotiation>,
+    pub page: u32,
+    pub total: u128,
+}
+
+// What a transfer of a finished swap pays for
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferType {
+    Nft,
+    SellerPayout,
+    Fee,
+    // Royalties and refunds aren't paid by finished swaps yet; overpayments go to the seller
+    Royalty,
+    Refund,
+}
+
+// A transfer a finished swap would make, of `amount` of a cw20 or native
+// `token`, or of the NFT `token_id` of the cw721 `token`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatedTransfer {
+    pub transfer_type: TransferType,
+    pub recipient: Addr,
+    pub token: String,
+    pub amount: Uint128,
+    pub token_id: Option<String>,
+}
+
+// Outcome of finishing a swap: the error it fails with, or its transfers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateFinishResponse {
+    pub error: Option<String>,
+    pub transfers: Vec<SimulatedTransfer>,
+}
+
+// Pagination query pagaination parameters for filtered swap queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PageParams {
+    pub start: usize,
+    pub end: usize,
+    pub page: u32,
+    pub total: u128,
+}
+
+#[allow(clippy::manual_clamp)]
+pub fn calculate_page_params(
+    page: Option<u32>,
+    limit: Option<u32>,
+    total_results: u32,
+) -> Result<PageParams, StdError> {
+    let page: u32 = page.unwrap_or(0_u32);
+    let mut limit: u32 = limit.unwrap_or(DEFAULT_LIMIT);
+    // Calculate dynamic limit and last page size
+    if total_results < limit {
+        limit = total_results;
+    } else if limit < DEFAULT_LIMIT {
+        limit = DEFAULT_LIMIT;
+    } else if limit > MAX_LIMIT {
+        limit = MAX_LIMIT;
+    }
+    let modulo = if total_results > 0 {
+        total_results % limit
+    } else {
+        0
+    };
+    let last_page = if total_results == 0 {
+        0
+    } else if modulo > 0 {
+        total_results / limit
+    } else {
+        total_results / limit - 1
+    };
+    let page_size: u32 = if page == last_page {
+        match modulo {
+            0 => limit,
+            _ => modulo,
+        }
+    } else {
+        limit
+    };
+
+    // Results
+    let start = (page * limit) as usize;
+    let end = (start as u32 + page_size) as usize;
+    let res = PageParams {
+        start,
+        end,
+        page,
+        total: total_results as u128,
+    };
+
+    Ok(res)
+}
+
+// Whether `swap` is of the collection queries are limited to, if any
+fn in_scope(swap: &CW721Swap, scope: &Option<Addr>) -> bool {
+    match scope {
+        Some(cw721) => swap.nft_contract == *cw721,
+        None => true,
+    }
+}
+
+/// Details of an active swap, or of a closed swap and how it was closed
+pub fn query_details(deps: Deps, env: Env, id: String) -> StdResult<DetailsResponse> {
+    let (swap, status, filler, close_height) = match SWAPS.may_load(deps.storage, &id)? {
+        // Expired swaps are only closed once cancelled or invalidated
+        Some(swap) => {
+            let status = if swap.is_expired(&env.block) {
+                SwapStatus::Expired
+            } else {
+                SwapStatus::Active
+            };
+            (swap, status, None, None)
+        }
+        None => {
+            let archived = ARCHIVE.load(deps.storage, &id)?;
+            let height = Some(archived.close_height);
+            (archived.swap, archived.status, archived.filler, height)
+        }
+    };
+    let details = DetailsResponse {
+        creator: swap.creator,
+        contract: swap.nft_contract,
+        payment_token: swap.payment_token,
+        token_id: swap.token_id,
+        expires: swap.expires,
+        price: swap.price,
+        swap_type: swap.swap_type,
+        status,
+        filler,
+        close_height,
+    };
+    Ok(details)
+}
+
+/// Runs the checks of `Finish` for swap `id` sent by `sender` with `funds`,
+/// without settling the swap
+pub fn query_simulate_finish<C: MarketConfig>(
+    deps: Deps,
+    env: Env,
+    config: &C,
+    policy: &impl CollectionPolicy,
+    id: String,
+    sender: Addr,
+    funds: Vec<Coin>,
+) -> StdResult<SimulateFinishResponse> {
+    let settlement = finishable_swap(deps.storage, &id).and_then(|swap| {
+        check_settlement(deps, &env, &sender, &funds, config, policy, &swap)
+            .map(|settlement| (swap, settlement))
+    });
+    let response = match settlement {
+        Ok((swap, settlement)) => SimulateFinishResponse {
+            error: None,
+            transfers: simulated_transfers(&env, &swap, config.denom(), settlement)?,
+        },
+        Err(err) => SimulateFinishResponse {
+            error: Some(err.to_string()),
+            transfers: vec![],
+        },
+    };
+    Ok(response)
+}
+
+// Transfers of a settlement, in the order of `check_settlement`
+fn simulated_transfers(
+    env: &Env,
+    swap: &CW721Swap,
+    denom: &str,
+    settlement: Settlement,
+) -> StdResult<Vec<SimulatedTransfer>> {
+    let mut transfers = vec![];
+    for (i, msg) in settlement.transfers.into_iter().enumerate() {
+        let transfer_type = match i {
+            0 => TransferType::Nft,
+            1 => TransferType::SellerPayout,
+            _ => TransferType::Fee,
+        };
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if transfer_type == TransferType::Nft => {
+                if let Cw721ExecuteMsg::TransferNft {
+                    recipient,
+                    token_id,
+                } = from_json(&msg)?
+                {
+                    transfers.push(SimulatedTransfer {
+                        transfer_type,
+                        recipient: Addr::unchecked(recipient),
+                        token: contract_addr,
+                        amount: Uint128::one(),
+                        token_id: Some(token_id),
+                    });
+                }
+            }
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                if let Cw20ExecuteMsg::TransferFrom {
+                    recipient, amount, ..
+                } = from_json(&msg)?
+                {
+                    transfers.push(SimulatedTransfer {
+                        transfer_type,
+                        recipient: Addr::unchecked(recipient),
+                        token: contract_addr,
+                        amount,
+                        token_id: None,
+                    });
+                }
+            }
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                for coin in amount {
+                    transfers.push(SimulatedTransfer {
+                        transfer_type: transfer_type.clone(),
+                        recipient: Addr::unchecked(&to_address),
+                        token: coin.denom,
+                        amount: coin.amount,
+                        token_id: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Native fees are kept by the marketplace unless fee recipients are configured
+    let paid: Uint128 = transfers
+        .iter()
+        .filter(|transfer| transfer.transfer_type == TransferType::Fee)
+        .map(|transfer| transfer.amount)
+        .sum();
+    let retained = settlement.split.marketplace.saturating_sub(paid);
+    if !retained.is_zero() {
+        let token = match &swap.payment_token {
+            Some(token) => token.to_string(),
+            None => denom.to_string(),
+        };
+        transfers.push(SimulatedTransfer {
+            transfer_type: TransferType::Fee,
+            recipient: env.contract.address.clone(),
+            token,
+            amount: retained,
+            token_id: None,
+        });
+    }
+    Ok(transfers)
+}
+
+pub fn query_user_of(
+    deps: Deps,
+    env: Env,
+    cw721: Addr,
+    token_id: String,
+) -> StdResult<UserResponse> {
+    let user = token_user(deps.storage, &env.block, &cw721, &token_id)?;
+    Ok(UserResponse {
+        user: user.as_ref().map(|(user, _)| user.clone()),
+        expires: user.map(|(_, expires)| expires),
+    })
+}
+
+pub fn query_rental(deps: Deps, id: String) -> StdResult<Rental> {
+    RENTALS.load(deps.storage, &id)
+}
+
+pub fn query_traits(deps: Deps, id: String) -> StdResult<Vec<Trait>> {
+    TRAIT_OFFERS.load(deps.storage, &id)
+}
+
+pub fn query_hooks(deps: Deps, subject: HookSubject) -> StdResult<Vec<Addr>> {
+    hooks_of(deps.storage, &subject)
+}
+
+pub fn query_list(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+
+    Ok(ListResponse {
+        swaps: all_swap_ids(deps.storage, start, limit)?,
+    })
+}
+
+pub fn query_swap_total(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    side: Option<SwapType>,
+) -> StdResult<u128> {
+    let scope = policy.scope(None);
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    let results: Vec<CW721Swap> = if let Some(swap_type) = side {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| item.swap_type == swap_type && in_scope(item, &scope))
+            .collect()
+    } else {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| in_scope(item, &scope))
+            .collect()
+    };
+
+    Ok(results.len() as u128)
+}
+
+pub fn query_swaps(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    side: SwapType,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<PageResult> {
+    let scope = policy.scope(None);
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    let results: Vec<CW721Swap> = swaps
+        .unwrap()
+        .into_iter()
+        .map(|t| t.1)
+        .filter(|item| item.swap_type == side && in_scope(item, &scope))
+        .collect();
+
+    let paging: PageParams = calculate_page_params(page, limit, results.len() as u32)?;
+    let res = PageResult {
+        swaps: results[paging.start..paging.end].to_vec(),
+        page: paging.page,
+        total: paging.total,
+    };
+
+    Ok(res)
+}
+
+pub fn query_swaps_of_token(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    token_id: String,
+    cw721: Option<Addr>,
+    side: Option<SwapType>,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<PageResult> {
+    let scope = policy.scope(cw721);
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    let results: Vec<CW721Swap> = if let Some(swap_type) = side {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| {
+                item.token_id == token_id && in_scope(item, &scope) && item.swap_type == swap_type
+            })
+            .collect()
+    } else {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| item.token_id == token_id && in_scope(item, &scope))
+            .collect()
+    };
+
+    let paging: PageParams = calculate_page_params(page, limit, results.len() as u32)?;
+    let res = PageResult {
+        swaps: results[paging.start..paging.end].to_vec(),
+        page: paging.page,
+        total: paging.total,
+    };
+
+    Ok(res)
+}
+
+pub fn query_negotiations_of_token(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    token_id: String,
+    cw721: Option<Addr>,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<NegotiationsResponse> {
+    let scope = policy.scope(cw721);
+    let offers: Vec<CW721Swap> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|t| t.1))
+        .filter(|item| match item {
+            Ok(swap) => {
+                swap.token_id == token_id
+                    && in_scope(swap, &scope)
+                    && swap.swap_type == SwapType::Offer
+            }
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<CW721Swap>>>()?;
+
+    let paging: PageParams = calculate_page_params(page, limit, offers.len() as u32)?;
+    let negotiations = offers[paging.start..paging.end]
+        .iter()
+        .map(|offer| {
+            Ok(Negotiation {
+                offer: offer.clone(),
+                counters: counters_of(deps.storage, &offer.id)?,
+            })
+        })
+        .collect::<StdResult<Vec<Negotiation>>>()?;
+
+    Ok(NegotiationsResponse {
+        negotiations,
+        page: paging.page,
+        total: paging.total,
+    })
+}
+
+pub fn query_swaps_by_creator(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    address: Addr,
+    swap_type: Option<SwapType>,
+    cw721: Option<Addr>,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<PageResult> {
+    let side: SwapType = swap_type.unwrap_or(SwapType::Sale);
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    let results: Vec<CW721Swap> = if let Some(contract) = policy.scope(cw721) {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| {
+                item.creator == address && item.swap_type == side && item.nft_contract == contract
+            })
+            .collect()
+    } else {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| item.creator == address && item.swap_type == side)
+            .collect()
+    };
+
+    let paging: PageParams = calculate_page_params(page, limit, results.len() as u32)?;
+    let res = PageResult {
+        swaps: results[paging.start..paging.end].to_vec(),
+        page: paging.page,
+        total: paging.total,
+    };
+
+    Ok(res)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_swaps_by_price(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    min: Option<Uint128>,
+    max: Option<Uint128>,
+    swap_type: Option<SwapType>,
+    cw721: Option<Addr>,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<PageResult> {
+    let min: Uint128 = min.unwrap_or(Uint128::from(0_u32));
+    let side: SwapType = swap_type.unwrap_or(SwapType::Sale);
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    // With Max range filter
+    let mut results: Vec<CW721Swap> = if let Some(max_value) = max {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| {
+                item.price.u128() >= min.u128()
+                    && item.price.u128() <= max_value.u128()
+                    && item.swap_type == side
+            })
+            .collect()
+    } else {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| item.price.u128() >= min.u128() && item.swap_type == side)
+            .collect()
+    };
+
+    // If limited to a collection scope
+    results = if let Some(contract) = policy.scope(cw721) {
+        results
+            .into_iter()
+            .filter(|item| item.nft_contract == contract)
+            .collect()
+    } else {
+        results
+    };
+
+    let paging: PageParams = calculate_page_params(page, limit, results.len() as u32)?;
+    let res = PageResult {
+        swaps: results[paging.start..paging.end].to_vec(),
+        page: paging.page,
+        total: paging.total,
+    };
+
+    Ok(res)
+}
+
+pub fn query_swaps_by_denom(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    payment_token: Option<Addr>,
+    swap_type: Option<SwapType>,
+    cw721: Option<Addr>,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<PageResult> {
+    let side: SwapType = swap_type.unwrap_or(SwapType::Sale);
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    // Requested cw20 denom
+    let mut results: Vec<CW721Swap> = if let Some(token_addr) = payment_token {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| {
+                item.payment_token.clone().unwrap() == token_addr && item.swap_type == side
+            })
+            .collect()
+    // Native ARCH denom
+    } else {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| item.payment_token.is_none() && item.swap_type == side)
+            .collect()
+    };
+
+    // If limited to a collection scope
+    results = if let Some(contract) = policy.scope(cw721) {
+        results
+            .into_iter()
+            .filter(|item| item.nft_contract == contract)
+            .collect()
+    } else {
+        results
+    };
+
+    let paging: PageParams = calculate_page_params(page, limit, results.len() as u32)?;
+    let res = PageResult {
+        swaps: results[paging.start..paging.end].to_vec(),
+        page: paging.page,
+        total: paging.total,
+    };
+
+    Ok(res)
+}
+
+pub fn query_swaps_by_payment_type(
+    deps: Deps,
+    policy: &impl CollectionPolicy,
+    cw20: bool,
+    swap_type: Option<SwapType>,
+    cw721: Option<Addr>,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<PageResult> {
+    let side: SwapType = swap_type.unwrap_or(SwapType::Sale);
+    let swaps: Result<Vec<(String, CW721Swap)>, cosmwasm_std::StdError> = SWAPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    // cw20 swap
+    let mut results: Vec<CW721Swap> = if cw20 {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| item.payment_token.is_some() && item.swap_type == side)
+            .collect()
+    // ARCH swap
+    } else {
+        swaps
+            .unwrap()
+            .into_iter()
+            .map(|t| t.1)
+            .filter(|item| item.payment_token.is_none() && item.swap_type == side)
+            .collect()
+    };
+
+    // If limited to a collection scope
+    results = if let Some(contract) = policy.scope(cw721) {
+        results
+            .into_iter()
+            .filter(|item| item.nft_contract == contract)
+            .collect()
+    } else {
+        results
+    };
+
+    let paging: PageParams = calculate_page_params(page, limit, results.len() as u32)?;
+    let res = PageResult {
+        swaps: results[paging.start..paging.end].to_vec(),
+        page: paging.page,
+        total: paging.total,
+    };
+
+    Ok(res)
+}
+
+pub fn query_fee_recipients(deps: Deps) -> StdResult<Vec<FeeRecipient>> {
+    fee_recipients(deps.storage)
+}
+
+pub fn query_paused(deps: Deps) -> StdResult<PausedResponse> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    let collections: StdResult<Vec<String>> = PAUSED_CW721
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect();
+
+    Ok(PausedResponse {
+        all: pause.all,
+        swap_types: pause.swap_types,
+        collections: collections?.into_iter().map(Addr::unchecked).collect(),
+    })
+}
+
+pub fn query_blocked_addresses(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    BLOCKED_ADDRESSES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn query_blocked_tokens(
+    deps: Deps,
+    start_after: Option<BlockedToken>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BlockedToken>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|t| Bound::exclusive((&t.cw721, t.token_id.as_str())));
+
+    BLOCKED_TOKENS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| key.map(|(cw721, token_id)| BlockedToken { cw721, token_id }))
+        .collect()
+}
+
+pub fn query_swap_id_rules(deps: Deps) -> StdResult<SwapIdRules> {
+    swap_id_rules(deps.storage)
+}
+
+pub fn query_expiration_rules(deps: Deps) -> StdResult<ExpirationRules> {
+    expiration_rules(deps.storage)
+}
+
+pub fn query_min_price(
+    deps: Deps,
+    config: &impl MarketConfig,
+    payment_token: Option<Addr>,
+) -> StdResult<Uint128> {
+    min_price(deps.storage, config.denom(), &payment_token)
+}
+
+pub fn query_pending_admin(deps: Deps) -> StdResult<Option<Addr>> {
+    PENDING_ADMIN.may_load(deps.storage)
+}
+
+pub fn query_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    ROLES
+        .prefix(role.as_str())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
@@ -0,0 +1,24 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Permissions that can be delegated by the marketplace admin
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Can update the config fees, minimum prices and fee recipients
+    FeeManager,
+    /// Can add and remove allowed collections
+    Curator,
+    /// Can pause and unpause trading
+    Pauser,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::FeeManager => "fee_manager",
+            Role::Curator => "curator",
+            Role::Pauser => "pauser",
+        }
+    }
+}
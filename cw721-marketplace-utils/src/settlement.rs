@@ -0,0 +1,136 @@
+//! Settlement transfers of finished swaps, dispatched as submessages so
+//! a failed transfer reverts the swap with an error naming the contract
+//! that failed. A swap is only closed once all of its transfers succeed
+use cosmwasm_std::{
+    BankMsg, CosmosMsg, DepsMut, Order, Reply, Response, StdResult, Storage, SubMsg, SubMsgResult,
+    WasmMsg,
+};
+use cw_storage_plus::Bound;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::hooks::HOOK_REPLY_ID;
+use crate::prelude::CW721Swap;
+use crate::state::PENDING_TRANSFERS;
+
+// Reply ids of settlement transfers start after the hook reply id
+const FIRST_TRANSFER_REPLY_ID: u64 = HOOK_REPLY_ID + 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferKind {
+    Nft,
+    Payment,
+}
+
+impl TransferKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferKind::Nft => "nft",
+            TransferKind::Payment => "payment",
+        }
+    }
+}
+
+// A settlement transfer awaiting its reply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingTransfer {
+    pub swap_id: String,
+    pub kind: TransferKind,
+    pub contract: String, // cw721 or cw20 address, or native denom
+}
+
+/// Settlement `transfers` of `swap` as submessages replied to with `settlement_reply`,
+/// on error, and always for the last one, which cleans up the swap's pending transfers
+pub fn settlement_submsgs(
+    storage: &mut dyn Storage,
+    swap: &CW721Swap,
+    transfers: Vec<CosmosMsg>,
+) -> StdResult<Vec<SubMsg>> {
+    // Transfers of a swap have consecutive reply ids
+    let first_id = PENDING_TRANSFERS
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(FIRST_TRANSFER_REPLY_ID, |id| id + 1);
+    let last_id = first_id + transfers.len() as u64 - 1;
+    transfers
+        .into_iter()
+        .zip(first_id..)
+        .map(|(msg, id)| {
+            let (kind, contract) = match &msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. })
+                    if *contract_addr == swap.nft_contract.as_str() =>
+                {
+                    (TransferKind::Nft, contract_addr.clone())
+                }
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+                    (TransferKind::Payment, contract_addr.clone())
+                }
+                CosmosMsg::Bank(BankMsg::Send { amount, .. }) => {
+                    let denoms: Vec<&str> = amount.iter().map(|coin| coin.denom.as_str()).collect();
+                    (TransferKind::Payment, denoms.join(","))
+                }
+                _ => (TransferKind::Payment, String::new()),
+            };
+            let pending = PendingTransfer {
+                swap_id: swap.id.clone(),
+                kind,
+                contract,
+            };
+            PENDING_TRANSFERS.save(storage, id, &pending)?;
+            Ok(if id == last_id {
+                SubMsg::reply_always(msg, id)
+            } else {
+                SubMsg::reply_on_error(msg, id)
+            })
+        })
+        .collect()
+}
+
+/// Whether reply `id` is for a settlement transfer
+pub fn is_settlement_reply(storage: &dyn Storage, id: u64) -> bool {
+    PENDING_TRANSFERS.has(storage, id)
+}
+
+/// Reverts the swap with the transfer that failed, or once
+/// the last transfer of the swap succeeds, removes its transfers
+pub fn settlement_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let transfer = PENDING_TRANSFERS.load(deps.storage, msg.id)?;
+
+    if let SubMsgResult::Err(reason) = msg.result {
+        return Err(match transfer.kind {
+            TransferKind::Nft => ContractError::NftTransferFailed {
+                contract: transfer.contract,
+                reason,
+            },
+            TransferKind::Payment => ContractError::PaymentFailed {
+                contract: transfer.contract,
+                reason,
+            },
+        });
+    }
+
+    let settled = PENDING_TRANSFERS
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(msg.id)),
+            Order::Descending,
+        )
+        .take_while(|item| {
+            item.as_ref()
+                .map_or(true, |(_, pending)| pending.swap_id == transfer.swap_id)
+        })
+        .map(|item| item.map(|(id, _)| id))
+        .collect::<StdResult<Vec<u64>>>()?;
+    for id in settled.iter() {
+        PENDING_TRANSFERS.remove(deps.storage, *id);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "settle")
+        .add_attribute("swap_id", transfer.swap_id)
+        .add_attribute("transfers", settled.len().to_string()))
+}
@@ -0,0 +1,556 @@
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, BlockInfo, Event, Order, StdResult, Storage, Timestamp, Uint128};
+use cw20::Expiration;
+use cw_storage_plus::{Bound, Item, Map};
+
+use crate::error::ContractError;
+use crate::events::close_event;
+use crate::migrate::MigrationProgress;
+use crate::prelude::{
+    ArchivedSwap, CW721Swap, FeeRecipient, Rental, Role, SwapStatus, SwapType, Trait,
+};
+use crate::settlement::PendingTransfer;
+
+/// Config fields shared by all marketplaces, each marketplace
+/// stores its own config type under the same storage key
+pub trait MarketConfig: Serialize + DeserializeOwned {
+    fn admin(&self) -> Option<&Addr>;
+    fn set_admin(&mut self, admin: Option<Addr>);
+    fn denom(&self) -> &str;
+    fn fees(&self) -> u64;
+    fn set_fees(&mut self, fees: u64);
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    pub admin: Option<Addr>, // `None` once the admin role has been renounced
+    pub denom: String,
+    pub fees: u64,
+}
+
+impl MarketConfig for Config {
+    fn admin(&self) -> Option<&Addr> {
+        self.admin.as_ref()
+    }
+
+    fn set_admin(&mut self, admin: Option<Addr>) {
+        self.admin = admin;
+    }
+
+    fn denom(&self) -> &str {
+        &self.denom
+    }
+
+    fn fees(&self) -> u64 {
+        self.fees
+    }
+
+    fn set_fees(&mut self, fees: u64) {
+        self.fees = fees;
+    }
+}
+
+/// Config storage of marketplace config type `C`
+pub fn config_item<C: MarketConfig>() -> Item<'static, C> {
+    Item::new(CONFIG_KEY)
+}
+
+/// Whether `addr` is the marketplace admin
+pub fn is_admin(config: &impl MarketConfig, addr: &Addr) -> bool {
+    config.admin() == Some(addr)
+}
+
+/// Whether `addr` is the marketplace admin or has been granted `role`
+pub fn has_role(
+    storage: &dyn Storage,
+    config: &impl MarketConfig,
+    addr: &Addr,
+    role: Role,
+) -> bool {
+    is_admin(config, addr) || ROLES.has(storage, (role.as_str(), addr))
+}
+
+// Trading halted by the pauser, either for the
+// whole marketplace or only for some swap types
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PauseState {
+    pub all: bool,
+    pub swap_types: Vec<SwapType>,
+}
+
+/// Whether trading `swap_type` swaps of `cw721` is paused,
+/// counter offers and trait offers are also paused with offers
+pub fn is_paused(storage: &dyn Storage, swap_type: &SwapType, cw721: &Addr) -> StdResult<bool> {
+    let pause = PAUSE.may_load(storage)?.unwrap_or_default();
+    Ok(pause.all
+        || pause.swap_types.contains(swap_type)
+        || (matches!(swap_type, SwapType::Counter | SwapType::TraitOffer)
+            && pause.swap_types.contains(&SwapType::Offer))
+        || PAUSED_CW721.has(storage, cw721.as_str()))
+}
+
+// Characters allowed in client supplied swap ids
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IdCharset {
+    /// Printable ascii characters, except whitespace
+    Printable,
+    /// Ascii letters, digits, `-` and `_`
+    UrlSafe,
+    /// Ascii letters and digits
+    Alphanumeric,
+}
+
+impl IdCharset {
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            IdCharset::Printable => c.is_ascii_graphic(),
+            IdCharset::UrlSafe => c.is_ascii_alphanumeric() || c == '-' || c == '_',
+            IdCharset::Alphanumeric => c.is_ascii_alphanumeric(),
+        }
+    }
+}
+
+// Rules client supplied swap ids must follow;
+// ids assigned by the contract are prefixed with `ASSIGNED_ID_PREFIX` instead
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SwapIdRules {
+    pub max_length: u32,
+    pub charset: IdCharset,
+}
+
+impl Default for SwapIdRules {
+    fn default() -> Self {
+        SwapIdRules {
+            max_length: 64,
+            charset: IdCharset::Printable,
+        }
+    }
+}
+
+impl SwapIdRules {
+    pub fn allows(&self, id: &str) -> bool {
+        !id.is_empty()
+            && id.len() <= self.max_length as usize
+            && id.chars().all(|c| self.charset.contains(c))
+    }
+}
+
+/// Configured swap id rules; defaults apply until the admin updates them
+pub fn swap_id_rules(storage: &dyn Storage) -> StdResult<SwapIdRules> {
+    Ok(SWAP_ID_RULES.may_load(storage)?.unwrap_or_default())
+}
+
+// Bounds of swap expirations, in seconds; expirations are unbounded until the admin sets them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ExpirationRules {
+    pub max_listing_duration: Option<u64>, // Sales and rental listings
+    pub max_offer_duration: Option<u64>,   // Offers, counter offers and trait offers
+    pub default_duration: Option<u64>, // Replaces `Never` expirations, capped to the max duration
+    pub block_time: u64,               // Estimated seconds per block, to time height expirations
+}
+
+impl Default for ExpirationRules {
+    fn default() -> Self {
+        ExpirationRules {
+            max_listing_duration: None,
+            max_offer_duration: None,
+            default_duration: None,
+            block_time: 6,
+        }
+    }
+}
+
+impl ExpirationRules {
+    pub fn max_duration(&self, swap_type: &SwapType) -> Option<u64> {
+        match swap_type {
+            SwapType::Sale | SwapType::Rental => self.max_listing_duration,
+            SwapType::Offer | SwapType::Counter | SwapType::TraitOffer => self.max_offer_duration,
+        }
+    }
+
+    /// Duration of `swap_type` swaps created without an expiration
+    /// (`Never`); `None` if they can never expire
+    pub fn never_duration(&self, swap_type: &SwapType) -> Option<u64> {
+        match (self.default_duration, self.max_duration(swap_type)) {
+            (Some(default), Some(max)) => Some(default.min(max)),
+            (default, max) => default.or(max),
+        }
+    }
+
+    /// Time of `expires`, estimating the time of a height from the current
+    /// `block` and the block time; `None` for `Never`
+    pub fn timestamp(&self, block: &BlockInfo, expires: &Expiration) -> Option<Timestamp> {
+        let now = block.time.seconds();
+        let seconds = match expires {
+            Expiration::AtTime(time) => return Some(*time),
+            Expiration::AtHeight(height) if *height >= block.height => {
+                now.saturating_add((height - block.height).saturating_mul(self.block_time))
+            }
+            Expiration::AtHeight(height) => {
+                now.saturating_sub((block.height - height).saturating_mul(self.block_time))
+            }
+            Expiration::Never {} => return None,
+        };
+        // Timestamps are nanoseconds, far heights are capped to the last representable time
+        Some(Timestamp::from_nanos(seconds.saturating_mul(1_000_000_000)))
+    }
+}
+
+/// Configured expiration rules; defaults apply until the admin updates them
+pub fn expiration_rules(storage: &dyn Storage) -> StdResult<ExpirationRules> {
+    Ok(EXPIRATION_RULES.may_load(storage)?.unwrap_or_default())
+}
+
+/// Expiration of a `swap_type` swap created or updated with `expires`:
+/// `Never` is replaced by the default duration, and later expirations
+/// than the max duration of the swap type are rejected
+pub fn check_expiration(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    swap_type: &SwapType,
+    expires: Expiration,
+) -> Result<Expiration, ContractError> {
+    let rules = expiration_rules(storage)?;
+    if let Expiration::Never {} = expires {
+        return Ok(match rules.never_duration(swap_type) {
+            Some(duration) => Expiration::AtTime(block.time.plus_seconds(duration)),
+            None => expires,
+        });
+    }
+    if let (Some(max_duration), Some(time)) = (
+        rules.max_duration(swap_type),
+        rules.timestamp(block, &expires),
+    ) {
+        if time.seconds().saturating_sub(block.time.seconds()) > max_duration {
+            return Err(ContractError::ExpirationTooLong { max_duration });
+        }
+    }
+    Ok(expires)
+}
+
+/// Minimum price of swaps paid in cw20 `payment_token`, or in native
+/// `denom` if `None`; zero unless configured
+pub fn min_price(
+    storage: &dyn Storage,
+    denom: &str,
+    payment_token: &Option<Addr>,
+) -> StdResult<Uint128> {
+    let token = match payment_token {
+        Some(token) => token.as_str(),
+        None => denom,
+    };
+    Ok(MIN_PRICES.may_load(storage, token)?.unwrap_or_default())
+}
+
+/// Fails if `price` is zero, or below the minimum price of its payment token.
+/// Zero priced rentals are free, and only need to meet a configured minimum
+pub fn check_price(
+    storage: &dyn Storage,
+    denom: &str,
+    payment_token: &Option<Addr>,
+    price: Uint128,
+    swap_type: &SwapType,
+) -> Result<(), ContractError> {
+    if price.is_zero() && *swap_type != SwapType::Rental {
+        return Err(ContractError::ZeroPrice {});
+    }
+    let min_price = min_price(storage, denom, payment_token)?;
+    if price < min_price {
+        return Err(ContractError::PriceTooLow { min_price });
+    }
+    Ok(())
+}
+
+// Prefix of the ids assigned by the swap counter, which client supplied ids can't start with
+pub const ASSIGNED_ID_PREFIX: &str = "~";
+
+/// Assigns the next id of the swap counter, skipping ids already taken
+pub fn next_swap_id(storage: &mut dyn Storage) -> StdResult<String> {
+    let mut count = SWAP_COUNT.may_load(storage)?.unwrap_or_default();
+    let id = loop {
+        count += 1;
+        let id = format!("{ASSIGNED_ID_PREFIX}{count}");
+        if !SWAPS.has(storage, &id) && !ARCHIVE.has(storage, &id) {
+            break id;
+        }
+    };
+    SWAP_COUNT.save(storage, &count)?;
+    Ok(id)
+}
+
+/// Whether `id` is in the namespace of the swap counter
+pub fn is_assigned_id(id: &str) -> bool {
+    id.starts_with(ASSIGNED_ID_PREFIX)
+}
+
+/// Whether `addr` is blocklisted
+pub fn address_blocked(storage: &dyn Storage, addr: &Addr) -> bool {
+    BLOCKED_ADDRESSES.has(storage, addr)
+}
+
+/// Whether `token_id` of `cw721` is blocklisted
+pub fn token_blocked(storage: &dyn Storage, cw721: &Addr, token_id: &str) -> bool {
+    BLOCKED_TOKENS.has(storage, (cw721, token_id))
+}
+
+/// Reverts if any of `addresses` or `token_id` of `cw721` are blocklisted
+pub fn check_not_blocked(
+    storage: &dyn Storage,
+    addresses: &[&Addr],
+    cw721: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    if addresses.iter().any(|addr| address_blocked(storage, addr)) {
+        return Err(ContractError::BlockedAddress {});
+    }
+    if token_blocked(storage, cw721, token_id) {
+        return Err(ContractError::BlockedToken {});
+    }
+    Ok(())
+}
+
+/// Wrapper for checking cw721 validity in allowlisted marketplaces
+pub fn cw721_allowed(storage: &dyn Storage, addr: &Addr) -> bool {
+    ALLOWED_CW721.has(storage, addr.as_str())
+}
+
+/// Configured fee recipients; empty if fees are retained by the contract
+pub fn fee_recipients(storage: &dyn Storage) -> StdResult<Vec<FeeRecipient>> {
+    Ok(FEE_RECIPIENTS.may_load(storage)?.unwrap_or_default())
+}
+
+/// Saves `swap`, indexing it by price if it's a sale
+pub fn save_swap(storage: &mut dyn Storage, swap: &CW721Swap) -> StdResult<()> {
+    if let Some(existing) = SWAPS.may_load(storage, &swap.id)? {
+        unindex_swap(storage, &existing);
+    }
+    if swap.swap_type == SwapType::Sale {
+        SALE_PRICES.save(
+            storage,
+            (&swap.nft_contract, swap.price.u128(), swap.id.as_str()),
+            &(),
+        )?;
+    }
+    SWAPS.save(storage, &swap.id, swap)
+}
+
+/// Closes `swap` with `status`, moving it to `ARCHIVE` and removing its index
+/// entries; closing an offer also invalidates the counter offers made to it.
+/// Swaps invalidated after their expiration are archived as `Expired`.
+/// Returns the close events of the swaps closed without being filled
+pub fn close_swap(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    swap: &CW721Swap,
+    status: SwapStatus,
+    filler: Option<&Addr>,
+) -> StdResult<Vec<Event>> {
+    let mut events = vec![];
+    unindex_swap(storage, swap);
+    match swap.swap_type {
+        SwapType::Offer => events.extend(invalidate_counters(storage, block, &swap.id)?),
+        SwapType::Counter => {
+            if let Some(offer_id) = COUNTER_OFFERS.may_load(storage, &swap.id)? {
+                OFFER_COUNTERS.remove(storage, (&offer_id, &swap.id));
+            }
+            COUNTER_OFFERS.remove(storage, &swap.id);
+        }
+        SwapType::Rental => {
+            RENTALS.remove(storage, &swap.id);
+            TOKEN_RENTALS.remove(storage, (&swap.nft_contract, &swap.token_id));
+        }
+        SwapType::TraitOffer => TRAIT_OFFERS.remove(storage, &swap.id),
+        SwapType::Sale => {}
+    }
+    let status = archive_swap(storage, block, swap, status, filler)?;
+    SWAPS.remove(storage, &swap.id);
+    // Filled swaps are reported by their sale event
+    if status != SwapStatus::Filled {
+        events.push(close_event(storage, swap, &status)?);
+    }
+    Ok(events)
+}
+
+/// Closes the counter offers made to offer `offer_id` as invalidated,
+/// returning their close events
+pub fn invalidate_counters(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    offer_id: &str,
+) -> StdResult<Vec<Event>> {
+    let counters = OFFER_COUNTERS
+        .prefix(offer_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    let mut events = vec![];
+    for id in counters {
+        let counter = SWAPS.load(storage, &id)?;
+        let status = archive_swap(storage, block, &counter, SwapStatus::Invalidated, None)?;
+        events.push(close_event(storage, &counter, &status)?);
+        SWAPS.remove(storage, &id);
+        COUNTER_OFFERS.remove(storage, &id);
+        OFFER_COUNTERS.remove(storage, (offer_id, &id));
+    }
+    Ok(events)
+}
+
+fn archive_swap(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    swap: &CW721Swap,
+    status: SwapStatus,
+    filler: Option<&Addr>,
+) -> StdResult<SwapStatus> {
+    let status = match status {
+        SwapStatus::Invalidated if swap.is_expired(block) => SwapStatus::Expired,
+        status => status,
+    };
+    let archived = ArchivedSwap {
+        swap: swap.clone(),
+        status: status.clone(),
+        filler: filler.cloned(),
+        close_height: block.height,
+    };
+    ARCHIVE.save(storage, &swap.id, &archived)?;
+    Ok(status)
+}
+
+/// Rebuilds the index entries of `swap`, for swaps stored before they were indexed
+pub fn reindex_swap(storage: &mut dyn Storage, swap: &CW721Swap) -> StdResult<()> {
+    match swap.swap_type {
+        SwapType::Sale => SALE_PRICES.save(
+            storage,
+            (&swap.nft_contract, swap.price.u128(), swap.id.as_str()),
+            &(),
+        ),
+        SwapType::Counter => match COUNTER_OFFERS.may_load(storage, &swap.id)? {
+            Some(offer_id) => OFFER_COUNTERS.save(storage, (&offer_id, &swap.id), &()),
+            None => Ok(()),
+        },
+        SwapType::Rental if RENTALS.has(storage, &swap.id) => {
+            TOKEN_RENTALS.save(storage, (&swap.nft_contract, &swap.token_id), &swap.id)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn unindex_swap(storage: &mut dyn Storage, swap: &CW721Swap) {
+    if swap.swap_type == SwapType::Sale {
+        SALE_PRICES.remove(
+            storage,
+            (&swap.nft_contract, swap.price.u128(), swap.id.as_str()),
+        );
+    }
+}
+
+/// Saves counter offer `counter`, linked to the offer it replies to
+pub fn save_counter(
+    storage: &mut dyn Storage,
+    counter: &CW721Swap,
+    offer_id: &str,
+) -> StdResult<()> {
+    COUNTER_OFFERS.save(storage, &counter.id, &offer_id.to_string())?;
+    OFFER_COUNTERS.save(storage, (offer_id, &counter.id), &())?;
+    save_swap(storage, counter)
+}
+
+/// Saves rental listing `swap` and its terms
+pub fn save_rental(storage: &mut dyn Storage, swap: &CW721Swap, rental: &Rental) -> StdResult<()> {
+    RENTALS.save(storage, &swap.id, rental)?;
+    TOKEN_RENTALS.save(storage, (&swap.nft_contract, &swap.token_id), &swap.id)?;
+    save_swap(storage, swap)
+}
+
+/// Saves trait offer `swap` and the traits a token needs to fill it
+pub fn save_trait_offer(
+    storage: &mut dyn Storage,
+    swap: &CW721Swap,
+    traits: &[Trait],
+) -> StdResult<()> {
+    TRAIT_OFFERS.save(storage, &swap.id, &traits.to_vec())?;
+    save_swap(storage, swap)
+}
+
+/// Renter of `token_id` of `cw721` at `block`, and when their rental expires
+pub fn token_user(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    cw721: &Addr,
+    token_id: &str,
+) -> StdResult<Option<(Addr, Expiration)>> {
+    let rental = match TOKEN_RENTALS.may_load(storage, (cw721, token_id))? {
+        Some(id) => RENTALS.load(storage, &id)?,
+        None => return Ok(None),
+    };
+    if !rental.is_rented(block) {
+        return Ok(None);
+    }
+    Ok(rental.renter.zip(rental.rented_until))
+}
+
+/// The offer counter offer `id` replies to
+pub fn countered_offer(storage: &dyn Storage, id: &str) -> StdResult<CW721Swap> {
+    let offer_id = COUNTER_OFFERS.load(storage, id)?;
+    SWAPS.load(storage, &offer_id)
+}
+
+/// Counter offers made to offer `offer_id`
+pub fn counters_of(storage: &dyn Storage, offer_id: &str) -> StdResult<Vec<CW721Swap>> {
+    OFFER_COUNTERS
+        .prefix(offer_id)
+        .keys(storage, None, None, Order::Ascending)
+        .map(|id| SWAPS.load(storage, &id?))
+        .collect()
+}
+
+pub fn all_swap_ids<'a>(
+    storage: &dyn Storage,
+    start: Option<Bound<'a, &'a str>>,
+    limit: usize,
+) -> StdResult<Vec<String>> {
+    SWAPS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub const CONFIG_KEY: &str = "config";
+pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
+pub const SWAPS: Map<&str, CW721Swap> = Map::new("cw721_swap");
+// Closed swaps by id, see `close_swap`
+pub const ARCHIVE: Map<&str, ArchivedSwap> = Map::new("swap_archive");
+// Sales by collection and price, see `save_swap` and `close_swap`
+pub const SALE_PRICES: Map<(&Addr, u128, &str), ()> = Map::new("sale_prices");
+// Counter offers and the offers they reply to, see `save_counter`
+pub const COUNTER_OFFERS: Map<&str, String> = Map::new("counter_offers");
+pub const OFFER_COUNTERS: Map<(&str, &str), ()> = Map::new("offer_counters");
+// Rental terms by swap id, and rental swap ids by token, see `save_rental`
+pub const RENTALS: Map<&str, Rental> = Map::new("rentals");
+pub const TOKEN_RENTALS: Map<(&Addr, &str), String> = Map::new("token_rentals");
+// Required traits of trait offers by swap id, see `save_trait_offer`
+pub const TRAIT_OFFERS: Map<&str, Vec<Trait>> = Map::new("trait_offers");
+// Settlement transfers awaiting their reply by reply id, see `settlement`
+pub const PENDING_TRANSFERS: Map<u64, PendingTransfer> = Map::new("pending_transfers");
+// Hook listeners by collection and by token owner, see `hooks`
+pub const COLLECTION_HOOKS: Map<(&Addr, &Addr), ()> = Map::new("collection_hooks");
+pub const ADDRESS_HOOKS: Map<(&Addr, &Addr), ()> = Map::new("address_hooks");
+pub const ALLOWED_CW721: Map<&str, ()> = Map::new("allowed_cw721");
+pub const PENDING_ADMIN: Item<Addr> = Item::new("pending_admin");
+pub const ROLES: Map<(&str, &Addr), ()> = Map::new("roles");
+pub const PAUSE: Item<PauseState> = Item::new("pause");
+pub const PAUSED_CW721: Map<&str, ()> = Map::new("paused_cw721");
+pub const BLOCKED_ADDRESSES: Map<&Addr, ()> = Map::new("blocked_addresses");
+pub const BLOCKED_TOKENS: Map<(&Addr, &str), ()> = Map::new("blocked_tokens");
+pub const FEE_RECIPIENTS: Item<Vec<FeeRecipient>> = Item::new("fee_recipients");
+pub const SWAP_COUNT: Item<u64> = Item::new("swap_count");
+pub const SWAP_ID_RULES: Item<SwapIdRules> = Item::new("swap_id_rules");
+pub const EXPIRATION_RULES: Item<ExpirationRules> = Item::new("expiration_rules");
+// Minimum prices, by native denom or cw20 address
+pub const MIN_PRICES: Map<&str, Uint128> = Map::new("min_prices");
+// Progress of a batched swap migration, see `migrate`
+pub const MIGRATION: Item<MigrationProgress> = Item::new("migration");
+// Sequence number of the last marketplace event, see `events`
+pub const EVENT_SEQUENCE: Item<u64> = Item::new("event_sequence");
@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum SwapType {
     Offer,
-    Sale
+    Sale,
+    Counter,    // Token owner's reply to an `Offer`, only the offerer can finish it
+    Rental,     // Escrowed token rented out for `price` per period, see `Rental`
+    TraitOffer, // Offer for any token of a collection with the required traits, see `Trait`
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -21,8 +24,48 @@ pub struct CW721Swap {
     pub swap_type: SwapType,
 }
 
+// Lifecycle status of a swap; only `Active` swaps are in `SWAPS`,
+// closed swaps are archived with how they were closed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapStatus {
+    Active,
+    Filled,      // Finished by the `filler` of its `ArchivedSwap`
+    Cancelled,   // Cancelled by its creator, or a counter offer declined by the offerer
+    Expired,     // Closed after its expiration
+    Invalidated, // Closed as another swap of its token was filled, or delisted when blocked
+}
+
+// A closed swap, see `close_swap`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ArchivedSwap {
+    pub swap: CW721Swap,
+    pub status: SwapStatus,
+    pub filler: Option<Addr>,
+    pub close_height: u64,
+}
+
+// Terms and current renter of a `SwapType::Rental` swap
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Rental {
+    pub period: u64, // Seconds
+    pub max_periods: u32,
+    pub renter: Option<Addr>,
+    pub rented_until: Option<Expiration>,
+}
+
+impl Rental {
+    /// Whether the token is rented out at `block`
+    pub fn is_rented(&self, block: &BlockInfo) -> bool {
+        match &self.rented_until {
+            Some(until) => self.renter.is_some() && !until.is_expired(block),
+            None => false,
+        }
+    }
+}
+
 impl CW721Swap {
     pub fn is_expired(&self, block: &BlockInfo) -> bool {
         self.expires.is_expired(block)
     }
-}
\ No newline at end of file
+}
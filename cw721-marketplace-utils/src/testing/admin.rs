@@ -0,0 +1,210 @@
+use cosmwasm_std::{from_json, to_json_string, Addr, Uint128};
+use cw_multi_test::Executor;
+use serde::Serialize;
+
+use crate::prelude::{FeeRecipient, MarketConfig, Role};
+
+use crate::msg::{ExecuteMsg, QueryMsg, WithdrawMsg};
+use crate::testing::{create_swap, mock_app, query, Marketplace};
+
+// `UpdateConfig` entry point, generic over the marketplace config
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+enum UpdateConfigMsg<C> {
+    UpdateConfig { config: C },
+}
+
+// Admin is transferred in two steps
+pub fn test_admin_transfer<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let new_admin = Addr::unchecked("new_admin");
+    let stranger = Addr::unchecked("stranger");
+
+    let swap = create_swap::<M>(&mut app, &swap_admin, Addr::unchecked("nft"));
+
+    // Admin can't be changed through UpdateConfig
+    let mut config: M::Config = query(&mut app, swap.clone(), QueryMsg::Config {}).unwrap();
+    config.set_admin(Some(new_admin.clone()));
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &UpdateConfigMsg::UpdateConfig { config },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Admin can only be changed with ProposeAdmin and AcceptAdmin"
+    );
+
+    // Only admin can propose a new admin
+    let propose_msg = ExecuteMsg::ProposeAdmin {
+        admin: new_admin.clone(),
+    };
+    let err = app
+        .execute_contract(stranger.clone(), swap.clone(), &propose_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(swap_admin.clone(), swap.clone(), &propose_msg, &[])
+        .unwrap();
+    let pending: Option<Addr> = query(&mut app, swap.clone(), QueryMsg::PendingAdmin {}).unwrap();
+    assert_eq!(pending, Some(new_admin.clone()));
+
+    // Only the proposed admin can accept
+    let err = app
+        .execute_contract(
+            stranger.clone(),
+            swap.clone(),
+            &ExecuteMsg::AcceptAdmin {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(
+        new_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::AcceptAdmin {},
+        &[],
+    )
+    .unwrap();
+    let config: M::Config = query(&mut app, swap.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(config.admin(), Some(&new_admin));
+    let pending: Option<Addr> = query(&mut app, swap.clone(), QueryMsg::PendingAdmin {}).unwrap();
+    assert_eq!(pending, None);
+
+    // Previous admin lost its permissions
+    let err = app
+        .execute_contract(swap_admin.clone(), swap.clone(), &propose_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Admin renounces, nobody can administer the contract anymore
+    app.execute_contract(
+        new_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::RenounceAdmin {},
+        &[],
+    )
+    .unwrap();
+    let config: M::Config = query(&mut app, swap.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(config.admin(), None);
+    let err = app
+        .execute_contract(new_admin, swap, &propose_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+}
+
+// Admin can delegate permissions with roles
+pub fn test_roles<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let fee_manager = Addr::unchecked("fee_manager");
+
+    let swap = create_swap::<M>(&mut app, &swap_admin, Addr::unchecked("nft"));
+
+    let update_msg = ExecuteMsg::UpdateFeeRecipients {
+        recipients: vec![FeeRecipient {
+            address: Addr::unchecked("treasury"),
+            weight: 1,
+        }],
+    };
+    let err = app
+        .execute_contract(fee_manager.clone(), swap.clone(), &update_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Only admin can grant roles
+    let grant_msg = ExecuteMsg::GrantRole {
+        role: Role::FeeManager,
+        address: fee_manager.clone(),
+    };
+    let err = app
+        .execute_contract(fee_manager.clone(), swap.clone(), &grant_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(swap_admin.clone(), swap.clone(), &grant_msg, &[])
+        .unwrap();
+    let members: Vec<Addr> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::RoleMembers {
+            role: Role::FeeManager,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(members, vec![fee_manager.clone()]);
+
+    // Fee manager can update fee recipients, but not administer roles
+    app.execute_contract(fee_manager.clone(), swap.clone(), &update_msg, &[])
+        .unwrap();
+    let err = app
+        .execute_contract(fee_manager.clone(), swap.clone(), &grant_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Fee manager can update the fees, but no other config field
+    let mut config: M::Config = query(&mut app, swap.clone(), QueryMsg::Config {}).unwrap();
+    config.set_fees(5);
+    let json = to_json_string(&config).unwrap();
+    app.execute_contract(
+        fee_manager.clone(),
+        swap.clone(),
+        &UpdateConfigMsg::UpdateConfig { config },
+        &[],
+    )
+    .unwrap();
+    let config: M::Config = query(&mut app, swap.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(config.fees(), 5);
+    let config: M::Config =
+        from_json(json.replace(r#""denom":"aarch""#, r#""denom":"uatom""#)).unwrap();
+    assert_eq!(config.denom(), "uatom");
+    let err = app
+        .execute_contract(
+            fee_manager.clone(),
+            swap.clone(),
+            &UpdateConfigMsg::UpdateConfig { config },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Only the admin can withdraw fees to itself
+    let err = app
+        .execute_contract(
+            fee_manager.clone(),
+            swap.clone(),
+            &ExecuteMsg::Withdraw(WithdrawMsg {
+                amount: Uint128::zero(),
+                denom: "aarch".to_string(),
+                payment_token: None,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Revoked roles lose their permissions
+    app.execute_contract(
+        swap_admin,
+        swap.clone(),
+        &ExecuteMsg::RevokeRole {
+            role: Role::FeeManager,
+            address: fee_manager.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(fee_manager, swap, &update_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+}
@@ -0,0 +1,169 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::Expiration;
+
+use crate::msg::{CancelMsg, ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg};
+use crate::prelude::{DetailsResponse, SwapStatus, SwapType};
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, mint_and_approve, mint_native, mock_app, query,
+    Marketplace,
+};
+
+fn swap_msg(
+    id: &str,
+    nft: &Addr,
+    payment_token: Option<Addr>,
+    token_id: &str,
+    expires: Expiration,
+    swap_type: SwapType,
+) -> ExecuteMsg {
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(id.to_string()),
+        cw721: nft.clone(),
+        payment_token,
+        token_id: token_id.to_string(),
+        expires,
+        price: Uint128::from(100_u32),
+        swap_type,
+    })
+}
+
+fn details(app: &mut App, swap: &Addr, id: &str) -> DetailsResponse {
+    query(app, swap.clone(), QueryMsg::Details { id: id.to_string() }).unwrap()
+}
+
+// Closed swaps are archived with how they were closed, and their ids can't be reused
+pub fn test_swap_status<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+    let buyer = Addr::unchecked("buyer");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token2");
+    mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u32));
+
+    let never = Expiration::AtHeight(384798573487439743);
+    let soon = Expiration::AtHeight(app.block_info().height + 1);
+    let creates = [
+        (
+            &cw721_owner,
+            swap_msg("sale", &nft, None, "token1", never, SwapType::Sale),
+        ),
+        (
+            &cw721_owner,
+            swap_msg("listing", &nft, None, "token2", never, SwapType::Sale),
+        ),
+        (
+            &bidder,
+            swap_msg(
+                "offer",
+                &nft,
+                Some(cw20.clone()),
+                "token1",
+                never,
+                SwapType::Offer,
+            ),
+        ),
+        (
+            &bidder,
+            swap_msg(
+                "late_offer",
+                &nft,
+                Some(cw20.clone()),
+                "token1",
+                soon,
+                SwapType::Offer,
+            ),
+        ),
+    ];
+    for (sender, msg) in creates {
+        app.execute_contract(sender.clone(), swap.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let sale = details(&mut app, &swap, "sale");
+    assert_eq!(sale.status, SwapStatus::Active);
+    assert_eq!(sale.filler, None);
+    assert_eq!(sale.close_height, None);
+
+    // Open swaps past their expiration are reported as expired
+    app.update_block(|block| block.height += 2);
+    assert_eq!(
+        details(&mut app, &swap, "late_offer").status,
+        SwapStatus::Expired
+    );
+
+    // Filling a sale closes the other swaps of its token
+    app.execute_contract(
+        buyer.clone(),
+        swap.clone(),
+        &ExecuteMsg::Finish(FinishSwapMsg {
+            id: "sale".to_string(),
+        }),
+        &[Coin {
+            denom: "aarch".to_string(),
+            amount: Uint128::from(100_u32),
+        }],
+    )
+    .unwrap();
+    let height = app.block_info().height;
+    let sale = details(&mut app, &swap, "sale");
+    assert_eq!(sale.status, SwapStatus::Filled);
+    assert_eq!(sale.filler, Some(buyer.clone()));
+    assert_eq!(sale.close_height, Some(height));
+    assert_eq!(sale.creator, cw721_owner);
+    let offer = details(&mut app, &swap, "offer");
+    assert_eq!(offer.status, SwapStatus::Invalidated);
+    assert_eq!(offer.close_height, Some(height));
+    assert_eq!(
+        details(&mut app, &swap, "late_offer").status,
+        SwapStatus::Expired
+    );
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Cancel(CancelMsg {
+            id: "listing".to_string(),
+        }),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        details(&mut app, &swap, "listing").status,
+        SwapStatus::Cancelled
+    );
+
+    // Ids of closed swaps can't be reused
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &swap_msg("sale", &nft, None, "token2", never, SwapType::Sale),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Completed atomic swap");
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &swap_msg("listing", &nft, None, "token2", never, SwapType::Sale),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Cancelled atomic swap");
+}
@@ -0,0 +1,259 @@
+use cosmwasm_std::{from_json, Addr, Coin, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw20::Expiration;
+use cw721::OwnerOfResponse;
+use cw721_base::msg::QueryMsg as Cw721QueryMsg;
+
+use crate::msg::{BatchCreateResponse, ExecuteMsg, QueryMsg, SwapMsg, UpdateMsg};
+use crate::prelude::{DetailsResponse, ListResponse, SwapType};
+use crate::testing::{
+    bank_query, create_cw721, create_swap, mint_and_approve, mint_native, mock_app, query,
+    Marketplace,
+};
+
+static DENOM: &str = "aarch";
+
+fn sale(nft: &Addr, token_id: &str, price: u128) -> SwapMsg {
+    SwapMsg {
+        id: None,
+        cw721: nft.clone(),
+        payment_token: None,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(price),
+        swap_type: SwapType::Sale,
+    }
+}
+
+// Number of per swap events of type `ty` emitted by a batch
+fn count_events(res: &AppResponse, ty: &str) -> usize {
+    res.events
+        .iter()
+        .filter(|event| event.ty == format!("wasm-{}", ty))
+        .count()
+}
+
+fn list(app: &mut cw_multi_test::App, swap: &Addr) -> Vec<String> {
+    let res: ListResponse = query(
+        app,
+        swap.clone(),
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    res.swaps
+}
+
+// Many swaps are created in one message, or none at all
+pub fn test_batch_create<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    for token_id in ["token1", "token2", "token3"] {
+        mint_and_approve(&mut app, &swap, &nft, &cw721_owner, token_id);
+    }
+
+    // Empty and oversized batches are rejected
+    for size in [0, 51] {
+        let msgs = (0..size).map(|_| sale(&nft, "token1", 1000)).collect();
+        let err = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap.clone(),
+                &ExecuteMsg::BatchCreate(msgs),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "Batch must contain between 1 and 50 swaps"
+        );
+    }
+
+    // A single invalid swap reverts the whole batch
+    let native_offer = SwapMsg {
+        swap_type: SwapType::Offer,
+        ..sale(&nft, "token2", 1000)
+    };
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::BatchCreate(vec![sale(&nft, "token1", 1000), native_offer]),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid payment token");
+    assert!(list(&mut app, &swap).is_empty());
+
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::BatchCreate(vec![
+                sale(&nft, "token1", 1000),
+                sale(&nft, "token2", 2000),
+                sale(&nft, "token3", 3000),
+            ]),
+            &[],
+        )
+        .unwrap();
+    let data: BatchCreateResponse = from_json(res.data.clone().unwrap()).unwrap();
+    assert_eq!(data.ids, vec!["~1", "~2", "~3"]);
+    assert_eq!(count_events(&res, "create"), 3);
+    assert_eq!(list(&mut app, &swap), vec!["~1", "~2", "~3"]);
+}
+
+// Many swaps are updated or cancelled in one message
+pub fn test_batch_update_cancel<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let random = Addr::unchecked("random");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    for token_id in ["token1", "token2", "token3"] {
+        mint_and_approve(&mut app, &swap, &nft, &cw721_owner, token_id);
+    }
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::BatchCreate(vec![
+            sale(&nft, "token1", 1000),
+            sale(&nft, "token2", 2000),
+            sale(&nft, "token3", 3000),
+        ]),
+        &[],
+    )
+    .unwrap();
+
+    let update = |id: &str, price: u128| UpdateMsg {
+        id: id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(price),
+    };
+    let update_msg = ExecuteMsg::BatchUpdate(vec![update("~1", 1500), update("~2", 2500)]);
+
+    // Only the creator can update their swaps
+    let err = app
+        .execute_contract(random.clone(), swap.clone(), &update_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    let res = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &update_msg, &[])
+        .unwrap();
+    assert_eq!(count_events(&res, "update"), 2);
+    for (id, price) in [("~1", 1500_u128), ("~2", 2500), ("~3", 3000)] {
+        let details: DetailsResponse = query(
+            &mut app,
+            swap.clone(),
+            QueryMsg::Details { id: id.to_string() },
+        )
+        .unwrap();
+        assert_eq!(details.price, Uint128::from(price));
+    }
+
+    // A single unknown swap reverts the whole batch
+    let cancel_msg = ExecuteMsg::BatchCancel(vec!["~1".to_string(), "~4".to_string()]);
+    app.execute_contract(cw721_owner.clone(), swap.clone(), &cancel_msg, &[])
+        .unwrap_err();
+    assert_eq!(list(&mut app, &swap), vec!["~1", "~2", "~3"]);
+
+    let cancel_msg = ExecuteMsg::BatchCancel(vec!["~1".to_string(), "~3".to_string()]);
+    let res = app
+        .execute_contract(cw721_owner, swap.clone(), &cancel_msg, &[])
+        .unwrap();
+    assert_eq!(count_events(&res, "cancel"), 2);
+    assert_eq!(list(&mut app, &swap), vec!["~2"]);
+}
+
+// Buyer purchases many listings with a single payment
+pub fn test_batch_finish<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let buyer = Addr::unchecked("buyer");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    for token_id in ["token1", "token2", "token3"] {
+        mint_and_approve(&mut app, &swap, &nft, &cw721_owner, token_id);
+    }
+    mint_native(&mut app, buyer.to_string(), Uint128::from(10000_u128));
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::BatchCreate(vec![
+            sale(&nft, "token1", 1000),
+            sale(&nft, "token2", 2000),
+            sale(&nft, "token3", 3000),
+        ]),
+        &[],
+    )
+    .unwrap();
+
+    let finish_msg = ExecuteMsg::BatchFinish(vec!["~1".to_string(), "~3".to_string()]);
+
+    // Payment must cover exactly the prices of the native listings
+    for amount in [3999_u128, 4001] {
+        let err = app
+            .execute_contract(
+                buyer.clone(),
+                swap.clone(),
+                &finish_msg,
+                &[Coin {
+                    denom: DENOM.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "Must send exactly the required funds"
+        );
+    }
+
+    let res = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &finish_msg,
+            &[Coin {
+                denom: DENOM.to_string(),
+                amount: Uint128::from(4000_u128),
+            }],
+        )
+        .unwrap();
+    assert_eq!(count_events(&res, "finish"), 2);
+
+    // Seller is paid each price and the buyer receives the NFTs
+    assert_eq!(
+        bank_query(&app, &cw721_owner).amount,
+        Uint128::from(4000_u128)
+    );
+    assert_eq!(bank_query(&app, &buyer).amount, Uint128::from(6000_u128));
+    for token_id in ["token1", "token3"] {
+        let owner: OwnerOfResponse = query(
+            &mut app,
+            nft.clone(),
+            Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(owner.owner, buyer.to_string());
+    }
+    assert_eq!(list(&mut app, &swap), vec!["~2"]);
+}
@@ -0,0 +1,135 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::Executor;
+
+use crate::prelude::ListResponse;
+use cw20::Expiration;
+
+use crate::msg::{BlockedToken, BlocklistMsg, ExecuteMsg, QueryMsg, SwapMsg};
+use crate::prelude::SwapType;
+use crate::testing::{create_cw721, create_swap, mint_and_approve, mock_app, query, Marketplace};
+
+fn sale_msg(nft: &Addr, token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(token_id.to_string()),
+        cw721: nft.clone(),
+        payment_token: None,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000_u128),
+        swap_type: SwapType::Sale,
+    })
+}
+
+// Blocked tokens can't be traded and their swaps can be delisted
+pub fn test_blocked_token<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "stolen");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "legit");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "other");
+    for token_id in ["stolen", "legit"] {
+        app.execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &sale_msg(&nft, token_id),
+            &[],
+        )
+        .unwrap();
+    }
+
+    let stolen = BlockedToken {
+        cw721: nft.clone(),
+        token_id: "stolen".to_string(),
+    };
+    app.execute_contract(
+        swap_admin,
+        swap.clone(),
+        &ExecuteMsg::Block(BlocklistMsg {
+            addresses: vec![],
+            tokens: vec![stolen.clone()],
+        }),
+        &[],
+    )
+    .unwrap();
+    let blocked: Vec<BlockedToken> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::BlockedTokens {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(blocked, vec![stolen]);
+
+    // Anyone can delist swaps of blocked tokens, a page at a time
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::DelistBlocked {
+                start_after: None,
+                limit: Some(1),
+            },
+            &[],
+        )
+        .unwrap();
+    let done = res
+        .events
+        .iter()
+        .flat_map(|e| &e.attributes)
+        .any(|attr| attr.key == "done" && attr.value == "true");
+    assert!(!done);
+    let swaps: ListResponse = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(swaps.swaps.len(), 2);
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::DelistBlocked {
+            start_after: Some("legit".to_string()),
+            limit: Some(1),
+        },
+        &[],
+    )
+    .unwrap();
+    let swaps: ListResponse = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(swaps.swaps, vec!["legit".to_string()]);
+
+    // Blocked token can't be listed again
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &sale_msg(&nft, "stolen"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Token is blocked");
+
+    // Other tokens are unaffected
+    app.execute_contract(cw721_owner, swap, &sale_msg(&nft, "other"), &[])
+        .unwrap();
+}
@@ -0,0 +1,132 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::Executor;
+
+use cw20::Expiration;
+
+use crate::msg::{BlocklistMsg, ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg};
+use crate::prelude::SwapType;
+use crate::testing::{
+    create_cw721, create_swap, mint_and_approve, mint_native, mock_app, query, Marketplace,
+};
+
+static DENOM: &str = "aarch";
+
+fn sale_msg(nft: &Addr, token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(token_id.to_string()),
+        cw721: nft.clone(),
+        payment_token: None,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000_u128),
+        swap_type: SwapType::Sale,
+    })
+}
+
+// Blocked addresses can't trade
+pub fn test_blocked_address<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_native(&mut app, arch_owner.to_string(), Uint128::from(1000_u128));
+
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "first");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "second");
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &sale_msg(&nft, "first"),
+        &[],
+    )
+    .unwrap();
+
+    // Only admin or curators can block
+    let block_msg = BlocklistMsg {
+        addresses: vec![cw721_owner.clone()],
+        tokens: vec![],
+    };
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Block(block_msg.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Blocked addresses must be valid
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &ExecuteMsg::Block(BlocklistMsg {
+                addresses: vec![Addr::unchecked("Not_An_Address")],
+                tokens: vec![],
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Invalid input: address not normalized"
+    );
+
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::Block(block_msg.clone()),
+        &[],
+    )
+    .unwrap();
+    let blocked: Vec<Addr> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::BlockedAddresses {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(blocked, vec![cw721_owner.clone()]);
+
+    // Blocked seller can't create swaps
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &sale_msg(&nft, "second"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Address is blocked");
+
+    // Existing swaps of a blocked seller can't be finished
+    let finish_msg = ExecuteMsg::Finish(FinishSwapMsg {
+        id: "first".to_string(),
+    });
+    let funds = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(1000_u128),
+    }];
+    let err = app
+        .execute_contract(arch_owner.clone(), swap.clone(), &finish_msg, &funds)
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Address is blocked");
+
+    // Unblocked seller can trade again
+    app.execute_contract(
+        swap_admin,
+        swap.clone(),
+        &ExecuteMsg::Unblock(block_msg),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(arch_owner, swap, &finish_msg, &funds)
+        .unwrap();
+}
@@ -1,19 +1,17 @@
-#![cfg(test)]
 use cosmwasm_std::{Addr, Uint128};
 use cw_multi_test::Executor;
 
+use crate::prelude::PageResult;
 use cw20::{Cw20ExecuteMsg, Expiration};
 use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
-use cw721_marketplace_utils::prelude::PageResult;
 
-use crate::integration_tests::util::{create_cw20, create_cw721, create_swap, mock_app, query};
 use crate::msg::{CancelMsg, ExecuteMsg, QueryMsg, SwapMsg};
-use crate::state::SwapType;
+use crate::prelude::SwapType;
+use crate::testing::{create_cw20, create_cw721, create_swap, mock_app, query, Marketplace};
 
 // Seller must be able to cancel sale
 // cw20 and native ARCH
-#[test]
-fn test_cancel_sales() {
+pub fn test_cancel_sales<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -25,7 +23,7 @@ fn test_cancel_sales() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw721_owner mints a cw721
@@ -44,7 +42,7 @@ fn test_cancel_sales() {
     // Create a SwapMsg for creating / finishing a swap
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         cw721: nft.clone(),
         payment_token: None,
         token_id: token_id.clone(),
@@ -118,8 +116,7 @@ fn test_cancel_sales() {
 
 // Bidders must be able to cancel offers
 // cw20 only
-#[test]
-fn test_cancel_offers() {
+pub fn test_cancel_offers<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -133,7 +130,7 @@ fn test_cancel_offers() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -172,7 +169,7 @@ fn test_cancel_offers() {
     // Bidding buyer creates an offer
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20)),
         token_id: token_id.clone(),
@@ -0,0 +1,343 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+use cw721::OwnerOfResponse;
+use cw721_base::msg::QueryMsg as Cw721QueryMsg;
+
+use crate::msg::{CancelMsg, CounterMsg, ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg, UpdateMsg};
+use crate::prelude::{DetailsResponse, NegotiationsResponse, SwapStatus, SwapType};
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, mint_and_approve, mint_native, mock_app, query,
+    Marketplace,
+};
+
+struct Setup {
+    app: App,
+    swap: Addr,
+    nft: Addr,
+    cw20: Addr,
+    cw721_owner: Addr,
+    bidder: Addr,
+}
+
+// cw721_owner owns "token1" and bidder makes a cw20 offer of 1000 for it
+fn setup<M: Marketplace>() -> Setup {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &ExecuteMsg::Create(SwapMsg {
+            id: Some("offer".to_string()),
+            cw721: nft.clone(),
+            payment_token: Some(cw20.clone()),
+            token_id: "token1".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(1000_u32),
+            swap_type: SwapType::Offer,
+        }),
+        &[],
+    )
+    .unwrap();
+
+    Setup {
+        app,
+        swap,
+        nft,
+        cw20,
+        cw721_owner,
+        bidder,
+    }
+}
+
+fn counter(id: &str, price: u32) -> ExecuteMsg {
+    ExecuteMsg::Counter(CounterMsg {
+        id: Some(id.to_string()),
+        offer_id: "offer".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(price),
+    })
+}
+
+fn negotiations(app: &mut App, swap: &Addr, nft: &Addr) -> NegotiationsResponse {
+    query(
+        app,
+        swap.clone(),
+        QueryMsg::NegotiationsOfToken {
+            token_id: "token1".to_string(),
+            cw721: nft.clone(),
+            page: None,
+            limit: None,
+        },
+    )
+    .unwrap()
+}
+
+// The offerer accepts the token owner's counter offer
+pub fn test_counter_offer_accepted<M: Marketplace>() {
+    let Setup {
+        mut app,
+        swap,
+        nft,
+        cw20,
+        cw721_owner,
+        bidder,
+    } = setup::<M>();
+
+    // Only the token owner can counter an offer
+    let err = app
+        .execute_contract(
+            Addr::unchecked("random"),
+            swap.clone(),
+            &counter("counter", 1500),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &counter("counter", 1500),
+        &[],
+    )
+    .unwrap();
+
+    // Counter offers can't be created with `Create`
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: None,
+                cw721: nft.clone(),
+                payment_token: Some(cw20.clone()),
+                token_id: "token1".to_string(),
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(1500_u32),
+                swap_type: SwapType::Counter,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    // The counter offer is linked to the offer
+    let res = negotiations(&mut app, &swap, &nft);
+    assert_eq!(res.total, 1);
+    assert_eq!(res.negotiations[0].offer.id, "offer");
+    assert_eq!(res.negotiations[0].counters.len(), 1);
+    assert_eq!(res.negotiations[0].counters[0].id, "counter");
+    assert_eq!(res.negotiations[0].counters[0].creator, cw721_owner);
+    assert_eq!(
+        res.negotiations[0].counters[0].payment_token,
+        Some(cw20.clone())
+    );
+
+    // Only the offerer can accept the counter offer
+    let finish_msg = ExecuteMsg::Finish(FinishSwapMsg {
+        id: "counter".to_string(),
+    });
+    let err = app
+        .execute_contract(Addr::unchecked("random"), swap.clone(), &finish_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(
+        bidder.clone(),
+        cw20.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: swap.to_string(),
+            amount: Uint128::from(1500_u32),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(bidder.clone(), swap.clone(), &finish_msg, &[])
+        .unwrap();
+
+    // Bidder paid the counter price and received the NFT
+    let owner: OwnerOfResponse = query(
+        &mut app,
+        nft.clone(),
+        Cw721QueryMsg::OwnerOf {
+            token_id: "token1".to_string(),
+            include_expired: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(owner.owner, bidder.to_string());
+    let balance: BalanceResponse = query(
+        &mut app,
+        cw20,
+        Cw20QueryMsg::Balance {
+            address: cw721_owner.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(balance.balance, Uint128::from(1500_u32));
+
+    // Offer and counter offer are closed
+    assert_eq!(negotiations(&mut app, &swap, &nft).total, 0);
+}
+
+// The offerer declines a counter offer, and cancelling
+// an offer also removes its counter offers
+pub fn test_counter_offer_declined<M: Marketplace>() {
+    let Setup {
+        mut app,
+        swap,
+        nft,
+        cw721_owner,
+        bidder,
+        ..
+    } = setup::<M>();
+
+    for id in ["counter1", "counter2"] {
+        app.execute_contract(cw721_owner.clone(), swap.clone(), &counter(id, 1500), &[])
+            .unwrap();
+    }
+
+    // Only the offerer can decline a counter offer
+    let decline_msg = ExecuteMsg::DeclineCounter {
+        id: "counter1".to_string(),
+    };
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &decline_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(bidder.clone(), swap.clone(), &decline_msg, &[])
+        .unwrap();
+    let res = negotiations(&mut app, &swap, &nft);
+    let counters: Vec<String> = res.negotiations[0]
+        .counters
+        .iter()
+        .map(|counter| counter.id.clone())
+        .collect();
+    assert_eq!(counters, vec!["counter2"]);
+
+    // Updating the offer keeps its counter offers
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &ExecuteMsg::Update(UpdateMsg {
+            id: "offer".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(1200_u32),
+        }),
+        &[],
+    )
+    .unwrap();
+    let res = negotiations(&mut app, &swap, &nft);
+    assert_eq!(res.negotiations[0].offer.price, Uint128::from(1200_u32));
+    assert_eq!(res.negotiations[0].counters.len(), 1);
+
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &ExecuteMsg::Cancel(CancelMsg {
+            id: "offer".to_string(),
+        }),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(negotiations(&mut app, &swap, &nft).total, 0);
+    let details: DetailsResponse = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::Details {
+            id: "counter2".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(details.status, SwapStatus::Invalidated);
+}
+
+// Selling the token invalidates its offer and the counter offer made to it, once each
+pub fn test_counter_offer_invalidated<M: Marketplace>() {
+    let Setup {
+        mut app,
+        swap,
+        nft,
+        cw721_owner,
+        ..
+    } = setup::<M>();
+    let buyer = Addr::unchecked("buyer");
+    mint_native(&mut app, buyer.to_string(), Uint128::from(500_u32));
+
+    // Swaps are invalidated in id order, the offer before its counter offer
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &counter("rebid", 1500),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Create(SwapMsg {
+            id: Some("sale".to_string()),
+            cw721: nft.clone(),
+            payment_token: None,
+            token_id: "token1".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(500_u32),
+            swap_type: SwapType::Sale,
+        }),
+        &[],
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &ExecuteMsg::Finish(FinishSwapMsg {
+                id: "sale".to_string(),
+            }),
+            &[Coin {
+                denom: "aarch".to_string(),
+                amount: Uint128::from(500_u32),
+            }],
+        )
+        .unwrap();
+
+    let mut closed: Vec<String> = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "wasm-marketplace-close")
+        .flat_map(|event| &event.attributes)
+        .filter(|attr| attr.key == "swap_id")
+        .map(|attr| attr.value.clone())
+        .collect();
+    closed.sort();
+    assert_eq!(closed, vec!["offer", "rebid"]);
+
+    for id in ["offer", "rebid"] {
+        let details: DetailsResponse = query(
+            &mut app,
+            swap.clone(),
+            QueryMsg::Details { id: id.to_string() },
+        )
+        .unwrap();
+        assert_eq!(details.status, SwapStatus::Invalidated);
+    }
+}
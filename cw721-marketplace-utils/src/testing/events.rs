@@ -0,0 +1,376 @@
+use cosmwasm_std::{to_json_string, Addr, Coin, Event, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+
+use cw20::Expiration;
+
+use crate::hooks::HookSubject;
+use crate::msg::{
+    BlocklistMsg, CancelMsg, DistributeFeesMsg, ExecuteMsg, FinishSwapMsg, HookMsg, PauseScope,
+    SwapMsg, UpdateMsg, WithdrawMsg,
+};
+use crate::prelude::SwapType;
+use crate::roles::Role;
+use crate::state::{ExpirationRules, IdCharset, SwapIdRules};
+use crate::testing::{
+    create_cw721, create_swap_with_fees, mint_and_approve, mint_native, mock_app, Marketplace,
+};
+use crate::FeeRecipient;
+
+// Marketplace events of `res`, with the attribute values of `keys`
+fn marketplace_events(res: &AppResponse, keys: &[&str]) -> Vec<(String, Vec<String>)> {
+    res.events
+        .iter()
+        .filter(|event| event.ty.starts_with("wasm-marketplace-"))
+        .map(|event| {
+            let values = keys.iter().map(|key| value(event, key)).collect();
+            (event.ty.clone(), values)
+        })
+        .collect()
+}
+
+fn value(event: &Event, key: &str) -> String {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.clone())
+        .unwrap_or_default()
+}
+
+fn sequences(res: &AppResponse) -> Vec<u64> {
+    marketplace_events(res, &["sequence"])
+        .into_iter()
+        .map(|(_, values)| values[0].parse().unwrap())
+        .collect()
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+// State changes emit typed events, numbered by a global sequence
+pub fn test_structured_events<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let buyer = Addr::unchecked("buyer");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap_with_fees::<M>(&mut app, &swap_admin, nft.clone(), 10);
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token2");
+    mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u32));
+
+    let never = Expiration::AtHeight(384798573487439743);
+    let mut seen = vec![];
+    for token_id in ["token1", "token2"] {
+        let res = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap.clone(),
+                &ExecuteMsg::Create(SwapMsg {
+                    id: Some(token_id.to_string()),
+                    cw721: nft.clone(),
+                    payment_token: None,
+                    token_id: token_id.to_string(),
+                    expires: never,
+                    price: Uint128::from(500_u32),
+                    swap_type: SwapType::Sale,
+                }),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            marketplace_events(&res, &["swap_id", "swap_type", "creator", "payment_token"]),
+            vec![(
+                "wasm-marketplace-create".to_string(),
+                strings(&[token_id, "Sale", cw721_owner.as_str(), "aarch"])
+            )]
+        );
+        seen.extend(sequences(&res));
+    }
+
+    // Updates carry the old and new values
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Update(UpdateMsg {
+                id: "token1".to_string(),
+                expires: never,
+                price: Uint128::from(1000_u32),
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        marketplace_events(&res, &["swap_id", "old_price", "price"]),
+        vec![(
+            "wasm-marketplace-update".to_string(),
+            strings(&["token1", "500", "1000"])
+        )]
+    );
+    seen.extend(sequences(&res));
+
+    let res = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &ExecuteMsg::Finish(FinishSwapMsg {
+                id: "token1".to_string(),
+            }),
+            &[Coin {
+                denom: "aarch".to_string(),
+                amount: Uint128::from(1000_u32),
+            }],
+        )
+        .unwrap();
+    let keys = [
+        "buyer",
+        "seller",
+        "collection",
+        "token_id",
+        "price",
+        "fee",
+        "royalty",
+        "seller_amount",
+    ];
+    assert_eq!(
+        marketplace_events(&res, &keys),
+        vec![(
+            "wasm-marketplace-sale".to_string(),
+            strings(&[
+                buyer.as_str(),
+                cw721_owner.as_str(),
+                nft.as_str(),
+                "token1",
+                "1000",
+                "100",
+                "0",
+                "900"
+            ])
+        )]
+    );
+    seen.extend(sequences(&res));
+
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Cancel(CancelMsg {
+                id: "token2".to_string(),
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        marketplace_events(&res, &["swap_id", "status"]),
+        vec![(
+            "wasm-marketplace-cancel".to_string(),
+            strings(&["token2", "cancelled"])
+        )]
+    );
+    seen.extend(sequences(&res));
+
+    // Admin actions carry the old and new values
+    let curator = Addr::unchecked("curator");
+    let listener = Addr::unchecked("listener");
+    let new_admin = Addr::unchecked("new_admin");
+    let recipients = vec![FeeRecipient {
+        address: curator.clone(),
+        weight: 1,
+    }];
+    let old_id_rules = to_json_string(&SwapIdRules::default()).unwrap();
+    let id_rules = SwapIdRules {
+        max_length: 16,
+        charset: IdCharset::UrlSafe,
+    };
+    let old_expiration_rules = to_json_string(&ExpirationRules::default()).unwrap();
+    let expiration_rules = ExpirationRules {
+        block_time: 5,
+        ..ExpirationRules::default()
+    };
+    let pause = PauseScope::SwapType(SwapType::Offer);
+    let blocked = BlocklistMsg {
+        addresses: vec![buyer.clone()],
+        tokens: vec![],
+    };
+    let hook = HookMsg {
+        subject: HookSubject::Collection(nft.clone()),
+        listener: listener.clone(),
+    };
+    let cases = vec![
+        (
+            &swap_admin,
+            ExecuteMsg::Pause {
+                scope: pause.clone(),
+            },
+            "pause",
+            vec!["scope", "old_paused", "paused"],
+            strings(&["Offer", "false", "true"]),
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::Unpause { scope: pause },
+            "unpause",
+            vec!["scope", "old_paused", "paused"],
+            strings(&["Offer", "true", "false"]),
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::Block(blocked.clone()),
+            "block",
+            vec!["address", "old_blocked", "blocked"],
+            strings(&[buyer.as_str(), "false", "true"]),
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::Unblock(blocked),
+            "unblock",
+            vec!["address", "old_blocked", "blocked"],
+            strings(&[buyer.as_str(), "true", "false"]),
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::GrantRole {
+                role: Role::Curator,
+                address: curator.clone(),
+            },
+            "grant-role",
+            vec!["role", "address", "old_granted", "granted"],
+            strings(&["curator", curator.as_str(), "false", "true"]),
+        ),
+        (
+            &curator,
+            ExecuteMsg::AddHook(hook.clone()),
+            "add-hook",
+            vec!["collection", "listener", "old_hooked", "hooked"],
+            strings(&[nft.as_str(), listener.as_str(), "false", "true"]),
+        ),
+        (
+            &curator,
+            ExecuteMsg::RemoveHook(hook),
+            "remove-hook",
+            vec!["collection", "listener", "old_hooked", "hooked"],
+            strings(&[nft.as_str(), listener.as_str(), "true", "false"]),
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::RevokeRole {
+                role: Role::Curator,
+                address: curator.clone(),
+            },
+            "revoke-role",
+            vec!["role", "address", "old_granted", "granted"],
+            strings(&["curator", curator.as_str(), "true", "false"]),
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::UpdateFeeRecipients {
+                recipients: recipients.clone(),
+            },
+            "update-fee-recipients",
+            vec!["old_recipients", "recipients"],
+            vec!["[]".to_string(), to_json_string(&recipients).unwrap()],
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::UpdateMinPrice {
+                payment_token: None,
+                min_price: Uint128::from(10_u32),
+            },
+            "update-min-price",
+            vec!["payment_token", "old_min_price", "min_price"],
+            strings(&["aarch", "0", "10"]),
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::UpdateSwapIdRules {
+                rules: id_rules.clone(),
+            },
+            "update-swap-id-rules",
+            vec!["old_rules", "rules"],
+            vec![old_id_rules, to_json_string(&id_rules).unwrap()],
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::UpdateExpirationRules {
+                rules: expiration_rules.clone(),
+            },
+            "update-expiration-rules",
+            vec!["old_rules", "rules"],
+            vec![
+                old_expiration_rules,
+                to_json_string(&expiration_rules).unwrap(),
+            ],
+        ),
+        // The marketplace holds the 100 fee of the sale
+        (
+            &swap_admin,
+            ExecuteMsg::Withdraw(WithdrawMsg {
+                amount: Uint128::from(40_u32),
+                denom: "aarch".to_string(),
+                payment_token: None,
+            }),
+            "withdraw-fees",
+            vec!["payment_token", "recipients", "old_balance", "balance"],
+            vec![
+                "aarch".to_string(),
+                format!(r#"[["{swap_admin}","40"]]"#),
+                "100".to_string(),
+                "60".to_string(),
+            ],
+        ),
+        (
+            &buyer,
+            ExecuteMsg::DistributeFees(DistributeFeesMsg {
+                payment_token: None,
+            }),
+            "distribute-fees",
+            vec!["payment_token", "recipients", "old_balance", "balance"],
+            vec![
+                "aarch".to_string(),
+                format!(r#"[["{curator}","60"]]"#),
+                "60".to_string(),
+                "0".to_string(),
+            ],
+        ),
+        (
+            &swap_admin,
+            ExecuteMsg::ProposeAdmin {
+                admin: new_admin.clone(),
+            },
+            "propose-admin",
+            vec!["pending_admin"],
+            strings(&[new_admin.as_str()]),
+        ),
+        (
+            &new_admin,
+            ExecuteMsg::AcceptAdmin {},
+            "accept-admin",
+            vec!["old_admin", "admin"],
+            strings(&[swap_admin.as_str(), new_admin.as_str()]),
+        ),
+        (
+            &new_admin,
+            ExecuteMsg::RenounceAdmin {},
+            "renounce-admin",
+            vec!["old_admin"],
+            strings(&[new_admin.as_str()]),
+        ),
+    ];
+    for (sender, msg, action, keys, values) in cases {
+        let res = app
+            .execute_contract(sender.clone(), swap.clone(), &msg, &[])
+            .unwrap();
+        assert_eq!(
+            marketplace_events(&res, &keys),
+            vec![(format!("wasm-marketplace-{action}"), values)]
+        );
+        seen.extend(sequences(&res));
+    }
+
+    // Every event has the next sequence number
+    assert_eq!(seen, (1..=22).collect::<Vec<u64>>());
+}
@@ -0,0 +1,216 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::{App, AppResponse, Executor};
+
+use cw20::Expiration;
+
+use crate::msg::{ExecuteMsg, QueryMsg, SwapMsg, UpdateMsg};
+use crate::prelude::{DetailsResponse, SwapType};
+use crate::state::ExpirationRules;
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, mint_and_approve, mock_app, query, Marketplace,
+};
+
+const DAY: u64 = 86400;
+
+fn create(nft: &Addr, id: &str, payment_token: Option<Addr>, expires: Expiration) -> ExecuteMsg {
+    let swap_type = match payment_token {
+        Some(_) => SwapType::Offer,
+        None => SwapType::Sale,
+    };
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(id.to_string()),
+        cw721: nft.clone(),
+        payment_token,
+        token_id: "token1".to_string(),
+        expires,
+        price: Uint128::from(100_u32),
+        swap_type,
+    })
+}
+
+fn update(id: &str, expires: Expiration) -> ExecuteMsg {
+    ExecuteMsg::Update(UpdateMsg {
+        id: id.to_string(),
+        expires,
+        price: Uint128::from(100_u32),
+    })
+}
+
+fn expires(app: &mut App, swap: &Addr, id: &str) -> Expiration {
+    let details: DetailsResponse =
+        query(app, swap.clone(), QueryMsg::Details { id: id.to_string() }).unwrap();
+    details.expires
+}
+
+fn expires_at(res: &AppResponse) -> Option<String> {
+    res.events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .find(|attr| attr.key == "expires_at")
+        .map(|attr| attr.value.clone())
+}
+
+// Swaps without an expiration get the default duration, and swaps
+// can't expire later than the max duration of their type
+pub fn test_expiration_rules<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+
+    // Expirations are unbounded by default
+    let rules: ExpirationRules =
+        query(&mut app, swap.clone(), QueryMsg::ExpirationRules {}).unwrap();
+    assert_eq!(rules, ExpirationRules::default());
+
+    let rules = ExpirationRules {
+        max_listing_duration: Some(30 * DAY),
+        max_offer_duration: Some(7 * DAY),
+        default_duration: Some(14 * DAY),
+        block_time: 6,
+    };
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::UpdateExpirationRules {
+                rules: rules.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &ExecuteMsg::UpdateExpirationRules {
+                rules: ExpirationRules {
+                    block_time: 0,
+                    ..rules.clone()
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::UpdateExpirationRules {
+            rules: rules.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    let stored: ExpirationRules =
+        query(&mut app, swap.clone(), QueryMsg::ExpirationRules {}).unwrap();
+    assert_eq!(stored, rules);
+
+    let block = app.block_info();
+    let after = |seconds: u64| Expiration::AtTime(block.time.plus_seconds(seconds));
+
+    // Listings
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &create(&nft, "sale", None, after(31 * DAY)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Expiration is more than 2592000 seconds away"
+    );
+    // Heights are timed with the block time
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &create(
+                &nft,
+                "sale",
+                None,
+                Expiration::AtHeight(block.height + 31 * DAY / 6),
+            ),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Expiration is more than 2592000 seconds away"
+    );
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &create(&nft, "sale", None, Expiration::Never {}),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(expires(&mut app, &swap, "sale"), after(14 * DAY));
+    assert_eq!(
+        expires_at(&res),
+        Some((block.time.seconds() + 14 * DAY).to_string())
+    );
+
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &update("sale", after(60 * DAY)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Expiration is more than 2592000 seconds away"
+    );
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &update("sale", Expiration::AtHeight(block.height + 100)),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        expires_at(&res),
+        Some((block.time.seconds() + 600).to_string())
+    );
+
+    // Offers, whose default duration is capped to their max duration
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &create(&nft, "offer", Some(cw20.clone()), after(8 * DAY)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Expiration is more than 604800 seconds away"
+    );
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &create(&nft, "offer", Some(cw20.clone()), Expiration::Never {}),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(expires(&mut app, &swap, "offer"), after(7 * DAY));
+}
@@ -0,0 +1,312 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use crate::prelude::FeeRecipient;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::msg::{DistributeFeesMsg, ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg};
+use crate::prelude::SwapType;
+use crate::testing::{
+    bank_query, create_cw20, create_cw721, create_swap_with_fees, mint_native, mock_app, query,
+    Marketplace,
+};
+
+static DENOM: &str = "aarch";
+
+fn fee_recipients() -> Vec<FeeRecipient> {
+    vec![
+        FeeRecipient {
+            address: Addr::unchecked("treasury"),
+            weight: 70,
+        },
+        FeeRecipient {
+            address: Addr::unchecked("partner"),
+            weight: 30,
+        },
+    ]
+}
+
+// Mints `token_id` to `owner` and lists it for sale
+fn list_nft(
+    app: &mut App,
+    swap: &Addr,
+    nft: &Addr,
+    owner: &Addr,
+    token_id: &str,
+    payment_token: Option<Addr>,
+    price: Uint128,
+) {
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: None,
+    });
+    app.execute_contract(owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+
+    let creation_msg = SwapMsg {
+        id: Some(token_id.to_string()),
+        cw721: nft.clone(),
+        payment_token,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price,
+        swap_type: SwapType::Sale,
+    };
+    app.execute_contract(
+        owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Create(creation_msg),
+        &[],
+    )
+    .unwrap();
+}
+
+// Fee recipients are paid directly when a swap paid in ARCH is finished
+pub fn test_fee_recipients_native<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap_with_fees::<M>(&mut app, &swap_admin, nft.clone(), 10); // 10% Marketplace fees
+    mint_native(&mut app, arch_owner.to_string(), Uint128::from(1010_u128));
+
+    // Only admin can set fee recipients
+    let err = app
+        .execute_contract(
+            arch_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::UpdateFeeRecipients {
+                recipients: fee_recipients(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Weights must be positive
+    let mut invalid = fee_recipients();
+    invalid[1].weight = 0;
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &ExecuteMsg::UpdateFeeRecipients {
+                recipients: invalid,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid fee recipients");
+
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::UpdateFeeRecipients {
+            recipients: fee_recipients(),
+        },
+        &[],
+    )
+    .unwrap();
+    let recipients: Vec<FeeRecipient> =
+        query(&mut app, swap.clone(), QueryMsg::FeeRecipients {}).unwrap();
+    assert_eq!(recipients, fee_recipients());
+
+    list_nft(
+        &mut app,
+        &swap,
+        &nft,
+        &cw721_owner,
+        "petrify",
+        None,
+        Uint128::from(1010_u128),
+    );
+
+    app.execute_contract(
+        arch_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Finish(FinishSwapMsg {
+            id: "petrify".to_string(),
+        }),
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(1010_u128),
+        }],
+    )
+    .unwrap();
+
+    // 101 aarch in fees split 70 / 30, with the dust going to the first recipient
+    assert_eq!(
+        bank_query(&app, &cw721_owner).amount,
+        Uint128::from(909_u128)
+    );
+    assert_eq!(
+        bank_query(&app, &Addr::unchecked("treasury")).amount,
+        Uint128::from(71_u128)
+    );
+    assert_eq!(
+        bank_query(&app, &Addr::unchecked("partner")).amount,
+        Uint128::from(30_u128)
+    );
+    assert_eq!(bank_query(&app, &swap).amount, Uint128::zero());
+}
+
+// Fee recipients are paid directly when a swap paid in cw20 is finished
+pub fn test_fee_recipients_cw20<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let cw20_owner = Addr::unchecked("cw20_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap_with_fees::<M>(&mut app, &swap_admin, nft.clone(), 10); // 10% Marketplace fees
+    let cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(1010_u128),
+    );
+
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::UpdateFeeRecipients {
+            recipients: fee_recipients(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    list_nft(
+        &mut app,
+        &swap,
+        &nft,
+        &cw721_owner,
+        "petrify",
+        Some(cw20.clone()),
+        Uint128::from(1010_u128),
+    );
+
+    // Buyer approves the swap contract to spend their cw20
+    let cw20_approve_msg = Cw20ExecuteMsg::IncreaseAllowance {
+        spender: swap.to_string(),
+        amount: Uint128::from(1010_u128),
+        expires: None,
+    };
+    app.execute_contract(cw20_owner.clone(), cw20.clone(), &cw20_approve_msg, &[])
+        .unwrap();
+
+    app.execute_contract(
+        cw20_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Finish(FinishSwapMsg {
+            id: "petrify".to_string(),
+        }),
+        &[],
+    )
+    .unwrap();
+
+    let expected = [
+        (cw721_owner.to_string(), 909_u128),
+        ("treasury".to_string(), 71_u128),
+        ("partner".to_string(), 30_u128),
+        (swap.to_string(), 0_u128),
+    ];
+    for (address, amount) in expected {
+        let balance: BalanceResponse =
+            query(&mut app, cw20.clone(), Cw20QueryMsg::Balance { address }).unwrap();
+        assert_eq!(balance.balance, Uint128::from(amount));
+    }
+}
+
+// Fees retained by the contract can be distributed by anyone
+pub fn test_distribute_fees<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap_with_fees::<M>(&mut app, &swap_admin, nft.clone(), 10); // 10% Marketplace fees
+    mint_native(&mut app, arch_owner.to_string(), Uint128::from(1000_u128));
+
+    list_nft(
+        &mut app,
+        &swap,
+        &nft,
+        &cw721_owner,
+        "petrify",
+        None,
+        Uint128::from(1000_u128),
+    );
+    app.execute_contract(
+        arch_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Finish(FinishSwapMsg {
+            id: "petrify".to_string(),
+        }),
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(1000_u128),
+        }],
+    )
+    .unwrap();
+
+    // Without fee recipients the contract retains its fees
+    assert_eq!(bank_query(&app, &swap).amount, Uint128::from(100_u128));
+    let distribute_msg = ExecuteMsg::DistributeFees(DistributeFeesMsg {
+        payment_token: None,
+    });
+    let err = app
+        .execute_contract(arch_owner.clone(), swap.clone(), &distribute_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "No fee recipients configured");
+
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::UpdateFeeRecipients {
+            recipients: fee_recipients(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Anyone can distribute the retained fees
+    app.execute_contract(arch_owner.clone(), swap.clone(), &distribute_msg, &[])
+        .unwrap();
+    assert_eq!(
+        bank_query(&app, &Addr::unchecked("treasury")).amount,
+        Uint128::from(70_u128)
+    );
+    assert_eq!(
+        bank_query(&app, &Addr::unchecked("partner")).amount,
+        Uint128::from(30_u128)
+    );
+    assert_eq!(bank_query(&app, &swap).amount, Uint128::zero());
+
+    // Nothing left to distribute
+    let err = app
+        .execute_contract(arch_owner, swap, &distribute_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Insufficient contract balance"
+    );
+}
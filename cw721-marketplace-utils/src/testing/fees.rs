@@ -1,37 +1,29 @@
-#![cfg(test)]
 use cosmwasm_std::{Addr, Coin, Uint128};
 use cw_multi_test::Executor;
 
+use crate::prelude::PageResult;
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
 use cw721::OwnerOfResponse;
 use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
-use cw721_marketplace_utils::prelude::PageResult;
-use rstest::rstest;
 
-use crate::integration_tests::util::{
+use crate::msg::{ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg, WithdrawMsg};
+use crate::prelude::SwapType;
+use crate::testing::{
     bank_query, create_cw20, create_cw721, create_swap_with_fees, mint_native, mock_app, query,
+    Marketplace,
 };
-use crate::msg::{ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg, WithdrawMsg};
-use crate::state::SwapType;
 
 static DENOM: &str = "aarch";
 
 // Swap buyer pays with ARCH
 // ensuring marketplace fees are respected
-#[rstest]
-#[case(10, false, 20, 2)]
-#[case(100, true, 22, 22)]
-#[case(1000, true, 15, 150)]
-#[case(10000, true, 30, 3000)]
-#[case(100000, true, 5, 5000)]
-#[case(u128::MAX, false, 10, 34028236692093846346337460743176821145)]
-fn test_fees_native(
-    #[case] amount: u128,
-    #[case] in_arch: bool,
-    #[case] fee_split: u64,
-    #[case] expected: u128,
+pub fn test_fees_native<M: Marketplace>(
+    amount: u128,
+    in_arch: bool,
+    fee_split: u64,
+    expected: u128,
 ) {
     let mut app = mock_app();
 
@@ -57,7 +49,7 @@ fn test_fees_native(
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap_with_fees(&mut app, &swap_admin, fee_split); // 10% Marketplace fees
+    let swap = create_swap_with_fees::<M>(&mut app, &swap_admin, nft.clone(), fee_split); // 10% Marketplace fees
     let swap_inst = swap.clone();
 
     // Mint native to `arch_owner`
@@ -82,7 +74,7 @@ fn test_fees_native(
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: None,
         token_id: token_id.clone(),
@@ -91,7 +83,7 @@ fn test_fees_native(
         swap_type: SwapType::Sale,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
@@ -184,19 +176,7 @@ fn test_fees_native(
 
 // Receive cw20 tokens and release upon approval
 // ensuring marketplace fees are respected
-#[rstest]
-#[case(10, false, 20, 2)]
-#[case(100, true, 22, 22)]
-#[case(1000, true, 15, 150)]
-#[case(10000, true, 30, 3000)]
-#[case(100000, true, 5, 5000)]
-#[case(u128::MAX, false, 10, 34028236692093846346337460743176821145)]
-fn test_fees_cw20(
-    #[case] amount: u128,
-    #[case] in_arch: bool,
-    #[case] fee_split: u64,
-    #[case] expected: u128,
-) {
+pub fn test_fees_cw20<M: Marketplace>(amount: u128, in_arch: bool, fee_split: u64, expected: u128) {
     let mut app = mock_app();
 
     let amount = if in_arch {
@@ -221,7 +201,7 @@ fn test_fees_cw20(
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap_with_fees(&mut app, &swap_admin, fee_split); // 10% Marketplace fees
+    let swap = create_swap_with_fees::<M>(&mut app, &swap_admin, nft.clone(), fee_split); // 10% Marketplace fees
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -249,7 +229,7 @@ fn test_fees_cw20(
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20.clone())),
         token_id: token_id.clone(),
@@ -258,7 +238,7 @@ fn test_fees_cw20(
         swap_type: SwapType::Sale,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
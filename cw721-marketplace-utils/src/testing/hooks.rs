@@ -0,0 +1,197 @@
+use cosmwasm_std::{to_json_binary, Addr, Empty, Response, StdError, Uint128};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+use cw20::Expiration;
+
+use crate::hooks::{HookExecuteMsg, HookSubject, OfferHookMsg};
+use crate::msg::{ExecuteMsg, HookMsg, QueryMsg, SwapMsg};
+use crate::prelude::SwapType;
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, mint_and_approve, mock_app, query, Marketplace,
+};
+
+// Listener recording the offers it's notified of, or failing every hook
+const FAIL: Item<bool> = Item::new("fail");
+const RECEIVED: Item<Vec<OfferHookMsg>> = Item::new("received");
+
+fn contract_listener() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |deps, _env, _info, msg: HookExecuteMsg| -> Result<Response, StdError> {
+            if FAIL.load(deps.storage)? {
+                return Err(StdError::generic_err("listener failed"));
+            }
+            let HookExecuteMsg::OfferHook(offer) = msg;
+            let mut received = RECEIVED.load(deps.storage)?;
+            received.push(offer);
+            RECEIVED.save(deps.storage, &received)?;
+            Ok(Response::new())
+        },
+        |deps, _env, _info, fail: bool| -> Result<Response, StdError> {
+            FAIL.save(deps.storage, &fail)?;
+            RECEIVED.save(deps.storage, &vec![])?;
+            Ok(Response::new())
+        },
+        |deps, _env, _msg: Empty| to_json_binary(&RECEIVED.load(deps.storage)?),
+    );
+    Box::new(contract)
+}
+
+fn create_listener(app: &mut App, fail: bool) -> Addr {
+    let code_id = app.store_code(contract_listener());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked("listener_deployer"),
+        &fail,
+        &[],
+        "listener",
+        None,
+    )
+    .unwrap()
+}
+
+fn received(app: &mut App, listener: &Addr) -> Vec<OfferHookMsg> {
+    query(app, listener.clone(), Empty {}).unwrap()
+}
+
+fn offer(nft: &Addr, cw20: &Addr, id: &str) -> ExecuteMsg {
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(id.to_string()),
+        cw721: nft.clone(),
+        payment_token: Some(cw20.clone()),
+        token_id: "token1".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000_u32),
+        swap_type: SwapType::Offer,
+    })
+}
+
+// Listeners of the token owner and of the collection are notified of offers,
+// and failing listeners don't revert the offer
+pub fn test_offer_hooks<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    let wallet_listener = create_listener(&mut app, false);
+    let failing_listener = create_listener(&mut app, true);
+
+    // Addresses subscribe for themselves, and the curator for collections
+    let owner_hook = HookMsg {
+        subject: HookSubject::Address(cw721_owner.clone()),
+        listener: wallet_listener.clone(),
+    };
+    let collection_hook = HookMsg {
+        subject: HookSubject::Collection(nft.clone()),
+        listener: failing_listener.clone(),
+    };
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &ExecuteMsg::AddHook(owner_hook.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::AddHook(collection_hook.clone()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // Listeners must be valid addresses
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::AddHook(HookMsg {
+                subject: HookSubject::Address(cw721_owner.clone()),
+                listener: Addr::unchecked("Not_A_Listener"),
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Invalid input: address not normalized"
+    );
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::AddHook(owner_hook.clone()),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::AddHook(collection_hook),
+        &[],
+    )
+    .unwrap();
+    let hooks: Vec<Addr> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::Hooks {
+            subject: HookSubject::Address(cw721_owner.clone()),
+        },
+    )
+    .unwrap();
+    assert_eq!(hooks, vec![wallet_listener.clone()]);
+
+    let res = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &offer(&nft, &cw20, "offer1"),
+            &[],
+        )
+        .unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "hook_failed")));
+
+    let notified = received(&mut app, &wallet_listener);
+    assert_eq!(notified.len(), 1);
+    assert_eq!(notified[0].swap_id, "offer1");
+    assert_eq!(notified[0].token_id, "token1");
+    assert_eq!(notified[0].owner, Some(cw721_owner.clone()));
+    assert_eq!(notified[0].bidder, bidder);
+    assert_eq!(notified[0].price, Uint128::from(1000_u32));
+
+    // Listeners can unsubscribe themselves
+    app.execute_contract(
+        wallet_listener.clone(),
+        swap.clone(),
+        &ExecuteMsg::RemoveHook(owner_hook),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &offer(&nft, &cw20, "offer2"),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(received(&mut app, &wallet_listener).len(), 1);
+}
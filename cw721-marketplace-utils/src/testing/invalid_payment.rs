@@ -1,4 +1,3 @@
-#![cfg(test)]
 use cosmwasm_std::{Addr, Coin, Uint128};
 use cw_multi_test::Executor;
 
@@ -8,17 +7,16 @@ use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
 
-use crate::integration_tests::util::{
-    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query,
-};
 use crate::msg::{ExecuteMsg, FinishSwapMsg, SwapMsg};
-use crate::state::SwapType;
+use crate::prelude::SwapType;
+use crate::testing::{
+    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query, Marketplace,
+};
 
 static DENOM: &str = "aarch";
 
 // cw721 buyer must send correct ARCH amount
-#[test]
-fn test_invalid_payment_native() {
+pub fn test_invalid_payment_native<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -32,7 +30,7 @@ fn test_invalid_payment_native() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // Mint native to `arch_owner`
@@ -57,7 +55,7 @@ fn test_invalid_payment_native() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: None,
         token_id: token_id.clone(),
@@ -66,7 +64,7 @@ fn test_invalid_payment_native() {
         swap_type: SwapType::Sale,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
@@ -127,8 +125,7 @@ fn test_invalid_payment_native() {
 }
 
 // ARCH offers must send the correct ARCH payment
-#[test]
-fn test_invalid_payment_native_offer() {
+pub fn test_invalid_payment_native_offer<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -142,7 +139,7 @@ fn test_invalid_payment_native_offer() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // Mint native to `arch_owner`
@@ -168,7 +165,7 @@ fn test_invalid_payment_native_offer() {
     // Bidding buyer creates an offer (with an invalid payment)
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         cw721: nft.clone(),
         payment_token: None,
         token_id: token_id.clone(),
@@ -212,8 +209,7 @@ fn test_invalid_payment_native_offer() {
 
 // Sales cannot be consumed with invalid payment parameters
 // cw721 buyer must approve (and own) sufficient cw20 amount
-#[test]
-fn test_invalid_payment_cw20() {
+pub fn test_invalid_payment_cw20<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -229,7 +225,7 @@ fn test_invalid_payment_cw20() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -256,7 +252,7 @@ fn test_invalid_payment_cw20() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20.clone())),
         token_id: token_id.clone(),
@@ -265,7 +261,7 @@ fn test_invalid_payment_cw20() {
         swap_type: SwapType::Offer,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
@@ -322,8 +318,7 @@ fn test_invalid_payment_cw20() {
 // cw20 offers cannot be consumed with invalid approvals, or
 // insufficient funds. cw20 owners must approve marketplace
 // to spend all funds required by the swap price
-#[test]
-fn test_invalid_payment_cw20_offer() {
+pub fn test_invalid_payment_cw20_offer<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -337,7 +332,7 @@ fn test_invalid_payment_cw20_offer() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -375,7 +370,7 @@ fn test_invalid_payment_cw20_offer() {
 
     // Bidding buyer (cw20_owner) creates an offer
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20)),
         token_id: token_id.clone(),
@@ -384,7 +379,7 @@ fn test_invalid_payment_cw20_offer() {
         swap_type: SwapType::Offer,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     let _res = app
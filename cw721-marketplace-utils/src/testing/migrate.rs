@@ -0,0 +1,255 @@
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Coin, Empty, Response, StdError, Uint128,
+};
+use cw2::set_contract_version;
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+use serde::{Deserialize, Serialize};
+
+use cw20::Expiration;
+
+use crate::msg::{
+    CreateResponse, ExecuteMsg, MigrateMsg, QueryMsg, SwapMsg, SweepMsg, SweepResponse,
+};
+use crate::prelude::{CW721Swap, DetailsResponse, MarketConfig, SwapType};
+use crate::state::{ExpirationRules, ALLOWED_CW721, CONFIG_KEY, SWAPS};
+use crate::testing::{create_cw721, mint_and_approve, mint_native, mock_app, query, Marketplace};
+
+// State written by v0.1.x marketplaces: their config, the allowlist of
+// permissioned marketplaces, and swaps without any index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct LegacyState {
+    config: Binary,
+    allowed_cw721: Addr,
+    swaps: Vec<CW721Swap>,
+}
+
+fn contract_legacy() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_deps, _env, _info, _msg: Empty| -> Result<Response, StdError> { Ok(Response::new()) },
+        |deps, _env, _info, msg: LegacyState| -> Result<Response, StdError> {
+            set_contract_version(deps.storage, "crates.io:archid-marketplace", "0.1.6")?;
+            deps.storage.set(CONFIG_KEY.as_bytes(), &msg.config);
+            ALLOWED_CW721.save(deps.storage, msg.allowed_cw721.as_str(), &())?;
+            for swap in msg.swaps {
+                SWAPS.save(deps.storage, &swap.id, &swap)?;
+            }
+            Ok(Response::new())
+        },
+        |_deps, _env, _msg: Empty| to_json_binary(&Empty {}),
+    );
+    Box::new(contract)
+}
+
+fn sale(owner: &Addr, nft: &Addr, id: u32) -> CW721Swap {
+    CW721Swap {
+        id: id.to_string(),
+        creator: owner.clone(),
+        nft_contract: nft.clone(),
+        payment_token: None,
+        token_id: format!("token{id}"),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(id * 100),
+        swap_type: SwapType::Sale,
+    }
+}
+
+fn attribute(res: &AppResponse, key: &str) -> String {
+    res.events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.clone())
+        .unwrap()
+}
+
+// A v0.1.x marketplace is migrated in batches, after which its
+// config is readable and its sales are indexed for sweeping
+pub fn test_migrate_from_v0_1<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let buyer = Addr::unchecked("buyer");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let legacy_id = app.store_code(contract_legacy());
+    let mut state = LegacyState {
+        config: M::legacy_config(&swap_admin, &nft, 0),
+        allowed_cw721: nft.clone(),
+        swaps: (1..=5).map(|id| sale(&cw721_owner, &nft, id)).collect(),
+    };
+    // The last sale never expires
+    state.swaps[4].expires = Expiration::Never {};
+    let swap = app
+        .instantiate_contract(
+            legacy_id,
+            swap_admin.clone(),
+            &state,
+            &[],
+            "swap721",
+            Some(swap_admin.to_string()),
+        )
+        .unwrap();
+    for id in 1..=5 {
+        mint_and_approve(&mut app, &swap, &nft, &cw721_owner, &format!("token{id}"));
+    }
+    mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u32));
+
+    // Plain upgrades can't skip the migration of v0.1.x state
+    let code_id = app.store_code(M::contract());
+    let err = app
+        .migrate_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &MigrateMsg::Upgrade {
+                expiration_rules: None,
+                batch_size: None,
+            },
+            code_id,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Can't migrate from version 0.1.6"
+    );
+
+    let res = app
+        .migrate_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &MigrateMsg::FromV0_1 {
+                batch_size: Some(2),
+                expiration_rules: Some(ExpirationRules {
+                    max_listing_duration: Some(86400 * 30),
+                    ..ExpirationRules::default()
+                }),
+            },
+            code_id,
+        )
+        .unwrap();
+    assert_eq!(attribute(&res, "migrated"), "2");
+    assert_eq!(attribute(&res, "done"), "false");
+
+    // The v0.1.x state can only be migrated once
+    let err = app
+        .migrate_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &MigrateMsg::FromV0_1 {
+                batch_size: None,
+                expiration_rules: None,
+            },
+            code_id,
+        )
+        .unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("Can't migrate from version"));
+
+    let continue_msg = MigrateMsg::Continue {
+        batch_size: Some(2),
+    };
+    let res = app
+        .migrate_contract(swap_admin.clone(), swap.clone(), &continue_msg, code_id)
+        .unwrap();
+    assert_eq!(attribute(&res, "migrated"), "4");
+    assert_eq!(attribute(&res, "done"), "false");
+
+    // Swaps can't be used until the migration is done
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: None,
+                cw721: nft.clone(),
+                payment_token: None,
+                token_id: "token6".to_string(),
+                expires: Expiration::Never {},
+                price: Uint128::from(100_u32),
+                swap_type: SwapType::Sale,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "A migration is already in progress"
+    );
+
+    let res = app
+        .migrate_contract(swap_admin.clone(), swap.clone(), &continue_msg, code_id)
+        .unwrap();
+    assert_eq!(attribute(&res, "migrated"), "5");
+    assert_eq!(attribute(&res, "capped"), "1");
+    assert_eq!(attribute(&res, "done"), "true");
+
+    // Sales that never expired expire after the max listing duration
+    let details: DetailsResponse = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::Details {
+            id: "5".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        details.expires,
+        Expiration::AtTime(app.block_info().time.plus_seconds(86400 * 30))
+    );
+    let err = app
+        .migrate_contract(swap_admin.clone(), swap.clone(), &continue_msg, code_id)
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "No migration in progress");
+
+    let config: M::Config = query(&mut app, swap.clone(), QueryMsg::Config {}).unwrap();
+    assert_eq!(config.admin(), Some(&swap_admin));
+    assert_eq!(config.fees(), 0);
+
+    // Migrated sales are swept from the cheapest
+    let res = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &ExecuteMsg::Sweep(SweepMsg {
+                cw721: nft.clone(),
+                max_items: 2,
+                max_price_per_item: Uint128::from(500_u32),
+                payment_token: None,
+            }),
+            &[Coin {
+                denom: "aarch".to_string(),
+                amount: Uint128::from(300_u32),
+            }],
+        )
+        .unwrap();
+    let data: SweepResponse = from_json(res.data.unwrap()).unwrap();
+    let bought: Vec<&str> = data
+        .bought
+        .iter()
+        .map(|item| item.token_id.as_str())
+        .collect();
+    assert_eq!(bought, vec!["token1", "token2"]);
+
+    // Assigned ids never collide with the ids of migrated swaps
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token6");
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: None,
+                cw721: nft.clone(),
+                payment_token: None,
+                token_id: "token6".to_string(),
+                expires: Expiration::Never {},
+                price: Uint128::from(100_u32),
+                swap_type: SwapType::Sale,
+            }),
+            &[],
+        )
+        .unwrap();
+    let data: CreateResponse = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(data.id, "~1");
+}
@@ -0,0 +1,191 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::Expiration;
+
+use crate::msg::{ExecuteMsg, QueryMsg, SwapMsg, UpdateMsg};
+use crate::prelude::SwapType;
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, mint_and_approve, mock_app, query, Marketplace,
+};
+
+fn create(nft: &Addr, id: &str, payment_token: Option<Addr>, price: u128) -> ExecuteMsg {
+    let swap_type = match payment_token {
+        Some(_) => SwapType::Offer,
+        None => SwapType::Sale,
+    };
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(id.to_string()),
+        cw721: nft.clone(),
+        payment_token,
+        token_id: "token1".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(price),
+        swap_type,
+    })
+}
+
+fn update(id: &str, price: u128) -> ExecuteMsg {
+    ExecuteMsg::Update(UpdateMsg {
+        id: id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(price),
+    })
+}
+
+fn min_price(app: &mut App, swap: &Addr, payment_token: Option<Addr>) -> Uint128 {
+    query(app, swap.clone(), QueryMsg::MinPrice { payment_token }).unwrap()
+}
+
+// Swaps priced below the minimum price of their payment token
+// can't be created, nor updated
+pub fn test_min_price<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+
+    let native_min = ExecuteMsg::UpdateMinPrice {
+        payment_token: None,
+        min_price: Uint128::from(100_u32),
+    };
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &native_min, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    app.execute_contract(swap_admin.clone(), swap.clone(), &native_min, &[])
+        .unwrap();
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::UpdateMinPrice {
+            payment_token: Some(cw20.clone()),
+            min_price: Uint128::from(1000_u32),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(min_price(&mut app, &swap, None), Uint128::from(100_u32));
+    assert_eq!(
+        min_price(&mut app, &swap, Some(cw20.clone())),
+        Uint128::from(1000_u32)
+    );
+
+    // Native sales
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &create(&nft, "sale", None, 99),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Price is below the minimum price of 100"
+    );
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &create(&nft, "sale", None, 100),
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &update("sale", 50), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Price is below the minimum price of 100"
+    );
+    app.execute_contract(cw721_owner.clone(), swap.clone(), &update("sale", 150), &[])
+        .unwrap();
+
+    // cw20 offers
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &create(&nft, "offer", Some(cw20.clone()), 500),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Price is below the minimum price of 1000"
+    );
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &create(&nft, "offer", Some(cw20.clone()), 1000),
+        &[],
+    )
+    .unwrap();
+
+    // A zero minimum price removes the minimum
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::UpdateMinPrice {
+            payment_token: None,
+            min_price: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(min_price(&mut app, &swap, None), Uint128::zero());
+    app.execute_contract(cw721_owner.clone(), swap.clone(), &update("sale", 1), &[])
+        .unwrap();
+}
+
+// Swaps can't be listed for free
+pub fn test_zero_price<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &create(&nft, "sale", None, 0),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Price must be greater than zero"
+    );
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &create(&nft, "sale", None, 1),
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &update("sale", 0), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Price must be greater than zero"
+    );
+}
@@ -0,0 +1,373 @@
+//! Multi-test suite shared by the marketplace contracts, see [`marketplace_tests`](crate::marketplace_tests)
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+
+use cosmwasm_schema::Api;
+
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, BalanceResponse as BalanceResponseBank, BankQuery, Binary,
+    Coin, Empty, Querier, QueryRequest, StdError, Uint128, WasmQuery,
+};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+use cw20::Cw20Coin;
+use cw721_base::msg::InstantiateMsg as Cw721InstantiateMsg;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::state::MarketConfig;
+
+pub mod admin;
+pub mod archive;
+pub mod batch;
+pub mod blocked_tokens;
+pub mod blocklist;
+pub mod cancel;
+pub mod counter;
+pub mod events;
+pub mod expiration;
+pub mod fee_recipients;
+pub mod fees;
+pub mod hooks;
+pub mod invalid_payment;
+pub mod migrate;
+pub mod min_price;
+pub mod offer;
+pub mod overpayment;
+pub mod pagination;
+pub mod pause;
+pub mod rental;
+pub mod sale;
+pub mod settlement;
+pub mod swap_ids;
+pub mod sweep;
+pub mod trait_offer;
+pub mod update;
+
+static DENOM: &str = "aarch";
+static NO_FEES: u64 = 0_u64;
+
+/// Marketplace contract the shared suite runs against
+pub trait Marketplace {
+    type Config: MarketConfig + Debug;
+    type InstantiateMsg: Serialize;
+
+    fn contract() -> Box<dyn Contract<Empty>>;
+
+    /// Instantiation of a marketplace where `cw721` can be traded
+    fn instantiate_msg(admin: &Addr, cw721: &Addr, fee_percentage: u64) -> Self::InstantiateMsg;
+
+    /// Config stored by v0.1.x of the marketplace, migrated with `MigrateMsg::FromV0_1`
+    fn legacy_config(admin: &Addr, cw721: &Addr, fee_percentage: u64) -> Binary;
+}
+
+pub fn mock_app() -> App {
+    App::default()
+}
+
+pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn contract_cw721() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw721_base::entry::execute,
+        cw721_base::entry::instantiate,
+        cw721_base::entry::query,
+    );
+    Box::new(contract)
+}
+
+pub fn create_swap<M: Marketplace>(router: &mut App, owner: &Addr, cw721: Addr) -> Addr {
+    create_swap_with_fees::<M>(router, owner, cw721, NO_FEES)
+}
+
+pub fn create_swap_with_fees<M: Marketplace>(
+    router: &mut App,
+    owner: &Addr,
+    cw721: Addr,
+    fees: u64,
+) -> Addr {
+    let swap_id = router.store_code(M::contract());
+    let msg = M::instantiate_msg(owner, &cw721, fees);
+    let swap_addr = router
+        .instantiate_contract(swap_id, owner.clone(), &msg, &[], "swap721", None)
+        .unwrap();
+    swap_addr
+}
+
+pub fn create_cw721(router: &mut App, minter: &Addr) -> Addr {
+    let cw721_id = router.store_code(contract_cw721());
+    let msg = Cw721InstantiateMsg {
+        name: "TESTNFT".to_string(),
+        symbol: "TSNFT".to_string(),
+        minter: String::from(minter),
+    };
+    let contract = router
+        .instantiate_contract(cw721_id, minter.clone(), &msg, &[], "swap721", None)
+        .unwrap();
+    contract
+}
+
+// Mints `token_id` to `owner` and approves the swap contract to transfer it
+pub fn mint_and_approve(app: &mut App, swap: &Addr, nft: &Addr, owner: &Addr, token_id: &str) {
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: None,
+    });
+    app.execute_contract(owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+}
+
+pub fn mint_native(app: &mut App, beneficiary: String, amount: Uint128) {
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: beneficiary,
+            amount: vec![Coin {
+                denom: DENOM.to_string(),
+                amount: amount,
+            }],
+        },
+    ))
+    .unwrap();
+}
+
+pub fn create_cw20(
+    router: &mut App,
+    owner: &Addr,
+    name: String,
+    symbol: String,
+    balance: Uint128,
+) -> Addr {
+    // set up cw20 contract with some tokens
+    let cw20_id = router.store_code(contract_cw20());
+    let msg = cw20_base::msg::InstantiateMsg {
+        name: name,
+        symbol: symbol,
+        decimals: 2,
+        initial_balances: vec![Cw20Coin {
+            address: owner.to_string(),
+            amount: balance,
+        }],
+        mint: None,
+        marketing: None,
+    };
+    let addr = router
+        .instantiate_contract(cw20_id, owner.clone(), &msg, &[], "CASH", None)
+        .unwrap();
+    addr
+}
+
+pub fn query<M, T>(router: &mut App, target_contract: Addr, msg: M) -> Result<T, StdError>
+where
+    M: Serialize + DeserializeOwned,
+    T: Serialize + DeserializeOwned,
+{
+    router.wrap().query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: target_contract.to_string(),
+        msg: to_json_binary(&msg).unwrap(),
+    }))
+}
+
+pub fn bank_query(app: &App, address: &Addr) -> Coin {
+    let req: QueryRequest<BankQuery> = QueryRequest::Bank(BankQuery::Balance {
+        address: address.to_string(),
+        denom: DENOM.to_string(),
+    });
+    let res = app
+        .raw_query(&to_json_binary(&req).unwrap())
+        .unwrap()
+        .unwrap();
+    let balance: BalanceResponseBank = from_json(&res).unwrap();
+    return balance.amount;
+}
+
+/// Asserts `schema_dir` holds the schema of `api` and nothing else, as
+/// written by `cosmwasm_schema::write_api!` (the `schema` example)
+pub fn assert_schema_current(api: Api, schema_dir: &Path) {
+    let name = api.contract_name.clone();
+    let api = api.render();
+    let mut expected = vec![(format!("{name}.json"), api.to_string().unwrap())];
+    for (file, json) in api.to_schema_files().unwrap() {
+        expected.push((format!("raw/{file}"), json));
+    }
+
+    let checked_in = |dir: &str| -> Vec<String> {
+        fs::read_dir(schema_dir.join(dir))
+            .map(|entries| {
+                entries
+                    .map(|entry| entry.unwrap().path())
+                    .filter(|path| path.is_file())
+                    .map(|path| {
+                        let file = path.file_name().unwrap().to_string_lossy();
+                        format!("{dir}{file}")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut files = checked_in("");
+    files.extend(checked_in("raw/"));
+    files.sort();
+    let mut expected_files: Vec<String> = expected.iter().map(|(file, _)| file.clone()).collect();
+    expected_files.sort();
+    assert_eq!(
+        files, expected_files,
+        "schema files are out of date, run `cargo schema`"
+    );
+
+    for (file, json) in expected {
+        let current = fs::read_to_string(schema_dir.join(&file)).unwrap();
+        assert!(
+            current == json + "\n",
+            "schema/{file} is out of date, run `cargo schema`"
+        );
+    }
+}
+
+pub fn has_unique_elements<T>(iter: T) -> bool
+where
+    T: IntoIterator,
+    T::Item: Eq + Hash,
+{
+    let mut uniq = HashSet::new();
+    iter.into_iter().all(move |x| uniq.insert(x))
+}
+
+/// Runs the shared suite against marketplace `$marketplace`, which
+/// must be given as an absolute path (e.g. `crate::integration_tests::util::Swap`)
+#[macro_export]
+macro_rules! marketplace_tests {
+    ($marketplace:ty) => {
+        $crate::marketplace_tests!(@modules $marketplace;
+            admin: [test_admin_transfer, test_roles],
+            archive: [test_swap_status],
+            batch: [test_batch_create, test_batch_update_cancel, test_batch_finish],
+            blocklist: [test_blocked_address],
+            cancel: [test_cancel_sales, test_cancel_offers],
+            counter: [
+                test_counter_offer_accepted,
+                test_counter_offer_declined,
+                test_counter_offer_invalidated
+            ],
+            events: [test_structured_events],
+            expiration: [test_expiration_rules],
+            fee_recipients: [
+                test_fee_recipients_native,
+                test_fee_recipients_cw20,
+                test_distribute_fees
+            ],
+            hooks: [test_offer_hooks],
+            migrate: [test_migrate_from_v0_1],
+            min_price: [test_min_price, test_zero_price],
+            invalid_payment: [
+                test_invalid_payment_native,
+                test_invalid_payment_native_offer,
+                test_invalid_payment_cw20,
+                test_invalid_payment_cw20_offer
+            ],
+            offer: [test_cw20_offer_accepted, test_cw20_offer_exploit],
+            overpayment: [
+                test_overpayment_native,
+                test_overpayment_native_offer,
+                test_overpayment_cw20
+            ],
+            pagination: [test_pagination],
+            pause: [test_pause_all, test_pause_granular],
+            rental: [test_rental_native, test_rental_cw20],
+            sale: [test_buy_native, test_buy_cw20],
+            settlement: [
+                test_nft_transfer_failed,
+                test_payment_failed,
+                test_simulate_finish
+            ],
+            swap_ids: [test_assigned_ids, test_swap_id_rules],
+            sweep: [test_sweep_native, test_sweep_cw20],
+            trait_offer: [test_trait_offer_filled, test_trait_offer_without_metadata],
+            update: [
+                test_updating_sales,
+                test_updating_offers,
+                test_updating_payment_token
+            ]
+        );
+
+        mod fees {
+            #[rstest::rstest]
+            #[case(10, false, 20, 2)]
+            #[case(100, true, 22, 22)]
+            #[case(1000, true, 15, 150)]
+            #[case(10000, true, 30, 3000)]
+            #[case(100000, true, 5, 5000)]
+            #[case(u128::MAX, false, 10, 34028236692093846346337460743176821145)]
+            fn test_fees_native(
+                #[case] amount: u128,
+                #[case] in_arch: bool,
+                #[case] fee_split: u64,
+                #[case] expected: u128,
+            ) {
+                $crate::testing::fees::test_fees_native::<$marketplace>(
+                    amount, in_arch, fee_split, expected,
+                )
+            }
+
+            #[rstest::rstest]
+            #[case(10, false, 20, 2)]
+            #[case(100, true, 22, 22)]
+            #[case(1000, true, 15, 150)]
+            #[case(10000, true, 30, 3000)]
+            #[case(100000, true, 5, 5000)]
+            #[case(u128::MAX, false, 10, 34028236692093846346337460743176821145)]
+            fn test_fees_cw20(
+                #[case] amount: u128,
+                #[case] in_arch: bool,
+                #[case] fee_split: u64,
+                #[case] expected: u128,
+            ) {
+                $crate::testing::fees::test_fees_cw20::<$marketplace>(
+                    amount, in_arch, fee_split, expected,
+                )
+            }
+        }
+    };
+    (@modules $marketplace:ty; $($module:ident: [$($test:ident),* $(,)?]),* $(,)?) => {
+        $(
+            mod $module {
+                $(
+                    #[test]
+                    fn $test() {
+                        $crate::testing::$module::$test::<$marketplace>()
+                    }
+                )*
+            }
+        )*
+    };
+}
+
+/// Runs the shared tests relying on swaps of several collections, for
+/// marketplaces where `BlocklistMsg` takes `BlockedToken`s
+#[macro_export]
+macro_rules! multi_collection_tests {
+    ($marketplace:ty) => {
+        $crate::marketplace_tests!(@modules $marketplace;
+            blocked_tokens: [test_blocked_token]
+        );
+    };
+}
@@ -1,4 +1,3 @@
-#![cfg(test)]
 use cosmwasm_std::{Addr, Uint128};
 use cw_multi_test::Executor;
 
@@ -8,17 +7,16 @@ use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
 
-use crate::integration_tests::util::{create_cw20, create_cw721, create_swap, mock_app, query};
 use crate::msg::{ExecuteMsg, FinishSwapMsg, SwapMsg};
-use crate::state::SwapType;
+use crate::prelude::SwapType;
+use crate::testing::{create_cw20, create_cw721, create_swap, mock_app, query, Marketplace};
 
 // cw721_owner accepts an offer for some cw20 from cw20_owner
 // XXX: cw20 spending approvals will only work for one swap at a time
 // unless the dapp does some logic to calculate the approval cumulatively
 // for all swaps of the cw20 token in question. Unclear how to manage this
 // with expiration date and with Offers being consumed by the NFT owner.
-#[test]
-fn test_cw20_offer_accepted() {
+pub fn test_cw20_offer_accepted<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -32,7 +30,7 @@ fn test_cw20_offer_accepted() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -75,7 +73,7 @@ fn test_cw20_offer_accepted() {
 
     // Bidding buyer (cw20_owner) creates an offer
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20)),
         token_id: token_id.clone(),
@@ -84,7 +82,7 @@ fn test_cw20_offer_accepted() {
         swap_type: SwapType::Offer,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     let _res = app
@@ -140,8 +138,7 @@ fn test_cw20_offer_accepted() {
     assert_eq!(balance_query.balance, Uint128::from(100000_u32));
 }
 
-#[test]
-fn test_cw20_offer_exploit() {
+pub fn test_cw20_offer_exploit<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -157,7 +154,7 @@ fn test_cw20_offer_exploit() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -200,7 +197,7 @@ fn test_cw20_offer_exploit() {
 
     // Bidding buyer (cw20_owner) creates an offer
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20)),
         token_id: token_id.clone(),
@@ -209,7 +206,7 @@ fn test_cw20_offer_exploit() {
         swap_type: SwapType::Offer,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     let _res = app
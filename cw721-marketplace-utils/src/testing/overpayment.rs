@@ -1,4 +1,3 @@
-#![cfg(test)]
 use cosmwasm_std::{Addr, Coin, Uint128};
 use cw_multi_test::Executor;
 
@@ -8,18 +7,17 @@ use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
 
-use crate::integration_tests::util::{
-    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query,
-};
 use crate::msg::{ExecuteMsg, FinishSwapMsg, SwapMsg};
-use crate::state::SwapType;
+use crate::prelude::SwapType;
+use crate::testing::{
+    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query, Marketplace,
+};
 
 static DENOM: &str = "aarch";
 
 // cw721 buyer (arch_owner) overpays
 // seller (cw721_owner) receives the full overpaid amount
-#[test]
-fn test_overpayment_native() {
+pub fn test_overpayment_native<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -33,7 +31,7 @@ fn test_overpayment_native() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // Mint native to `arch_owner`
@@ -58,7 +56,7 @@ fn test_overpayment_native() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: None,
         token_id: token_id.clone(),
@@ -67,7 +65,7 @@ fn test_overpayment_native() {
         swap_type: SwapType::Sale,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
@@ -126,8 +124,7 @@ fn test_overpayment_native() {
 
 // Over paying will fail, must send exactly the required funds
 // to create a SwapType::Offer for native ARCH
-#[test]
-fn test_overpayment_native_offer() {
+pub fn test_overpayment_native_offer<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -141,7 +138,7 @@ fn test_overpayment_native_offer() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // Mint native to `arch_owner`
@@ -167,7 +164,7 @@ fn test_overpayment_native_offer() {
     // Bidding buyer creates an offer
     let swap_id: String = "firstswap".to_string();
     let creation_msg = SwapMsg {
-        id: swap_id.clone(),
+        id: Some(swap_id.clone()),
         cw721: nft.clone(),
         payment_token: None,
         token_id: token_id.clone(),
@@ -194,8 +191,7 @@ fn test_overpayment_native_offer() {
 // cw721 buyer increases payment allowance too high
 // but correct payment for swap is still enforced.
 // (True for both SwapType::Sale and SwapType::Offer)
-#[test]
-fn test_overpayment_cw20() {
+pub fn test_overpayment_cw20<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -209,7 +205,7 @@ fn test_overpayment_cw20() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin, nft.clone());
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -237,7 +233,7 @@ fn test_overpayment_cw20() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20.clone())),
         token_id: token_id.clone(),
@@ -246,7 +242,7 @@ fn test_overpayment_cw20() {
         swap_type: SwapType::Offer,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
@@ -1,21 +1,19 @@
-#![cfg(test)]
 use cosmwasm_std::{Addr, Uint128};
 use cw_multi_test::Executor;
 
+use crate::prelude::{ListResponse, PageResult};
 use cw20::Expiration;
 use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
-use cw721_marketplace_utils::prelude::{ListResponse, PageResult};
 
-use crate::integration_tests::util::{
-    create_cw721, create_swap, has_unique_elements, mock_app, query,
-};
 use crate::msg::{ExecuteMsg, QueryMsg, SwapMsg};
-use crate::state::SwapType;
+use crate::prelude::SwapType;
+use crate::testing::{
+    create_cw721, create_swap, has_unique_elements, mock_app, query, Marketplace,
+};
 
 // Listing swaps and querying filter entry points must be enumerable,
 // and must return correct results, totals, and page for all page sizes
-#[test]
-fn test_pagination() {
+pub fn test_pagination<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -28,7 +26,7 @@ fn test_pagination() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw721_owner mints 15 cw721 tokens
@@ -81,7 +79,7 @@ fn test_pagination() {
 
         // Swap msg
         let creation_msg = SwapMsg {
-            id: token_id.clone(),
+            id: Some(token_id.clone()),
             cw721: nft.clone(),
             payment_token: None,
             token_id: token_id.clone(),
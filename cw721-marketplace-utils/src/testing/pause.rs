@@ -0,0 +1,239 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use crate::prelude::Role;
+use cw20::Expiration;
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::msg::{
+    CancelMsg, ExecuteMsg, FinishSwapMsg, PauseScope, PausedResponse, QueryMsg, SwapMsg,
+};
+use crate::prelude::SwapType;
+use crate::testing::{create_cw721, create_swap, mint_native, mock_app, query, Marketplace};
+
+static DENOM: &str = "aarch";
+
+// Mints `token_id` to `owner` and approves the swap contract to transfer it
+fn mint_and_approve(app: &mut App, swap: &Addr, nft: &Addr, owner: &Addr, token_id: &str) {
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: None,
+    });
+    app.execute_contract(owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+}
+
+fn sale_msg(nft: &Addr, token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::Create(SwapMsg {
+        id: Some(token_id.to_string()),
+        cw721: nft.clone(),
+        payment_token: None,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000_u128),
+        swap_type: SwapType::Sale,
+    })
+}
+
+// Pausing the whole marketplace blocks trading but allows cancelling
+pub fn test_pause_all<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let pauser = Addr::unchecked("pauser");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let arch_owner = Addr::unchecked("arch_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_native(&mut app, arch_owner.to_string(), Uint128::from(1000_u128));
+
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "first");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "second");
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &sale_msg(&nft, "first"),
+        &[],
+    )
+    .unwrap();
+
+    // Only admin or pausers can pause
+    let pause_msg = ExecuteMsg::Pause {
+        scope: PauseScope::All,
+    };
+    let err = app
+        .execute_contract(pauser.clone(), swap.clone(), &pause_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::GrantRole {
+            role: Role::Pauser,
+            address: pauser.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(pauser.clone(), swap.clone(), &pause_msg, &[])
+        .unwrap();
+    let paused: PausedResponse = query(&mut app, swap.clone(), QueryMsg::Paused {}).unwrap();
+    assert!(paused.all);
+
+    // Create and Finish are blocked
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &sale_msg(&nft, "second"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Trading is paused");
+
+    let finish_msg = ExecuteMsg::Finish(FinishSwapMsg {
+        id: "first".to_string(),
+    });
+    let funds = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(1000_u128),
+    }];
+    let err = app
+        .execute_contract(arch_owner.clone(), swap.clone(), &finish_msg, &funds)
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Trading is paused");
+
+    // Unpausing resumes trading
+    app.execute_contract(
+        pauser,
+        swap.clone(),
+        &ExecuteMsg::Unpause {
+            scope: PauseScope::All,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(arch_owner, swap.clone(), &finish_msg, &funds)
+        .unwrap();
+
+    // Cancelling is allowed while paused
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &sale_msg(&nft, "second"),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(swap_admin, swap.clone(), &pause_msg, &[])
+        .unwrap();
+    app.execute_contract(
+        cw721_owner,
+        swap,
+        &ExecuteMsg::Cancel(CancelMsg {
+            id: "second".to_string(),
+        }),
+        &[],
+    )
+    .unwrap();
+}
+
+// Pausing a collection or a swap type leaves the rest of the marketplace trading
+pub fn test_pause_granular<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "first");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "second");
+
+    // Paused offers don't block sales
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::Pause {
+            scope: PauseScope::SwapType(SwapType::Offer),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &sale_msg(&nft, "first"),
+        &[],
+    )
+    .unwrap();
+
+    // Paused collections must be valid addresses
+    let err = app
+        .execute_contract(
+            swap_admin.clone(),
+            swap.clone(),
+            &ExecuteMsg::Pause {
+                scope: PauseScope::Collection(Addr::unchecked("Not_An_Address")),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Invalid input: address not normalized"
+    );
+
+    // Paused collection blocks its swaps
+    app.execute_contract(
+        swap_admin.clone(),
+        swap.clone(),
+        &ExecuteMsg::Pause {
+            scope: PauseScope::Collection(nft.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+    let paused: PausedResponse = query(&mut app, swap.clone(), QueryMsg::Paused {}).unwrap();
+    assert_eq!(
+        paused,
+        PausedResponse {
+            all: false,
+            swap_types: vec![SwapType::Offer],
+            collections: vec![nft.clone()],
+        }
+    );
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &sale_msg(&nft, "second"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Trading is paused");
+
+    app.execute_contract(
+        swap_admin,
+        swap.clone(),
+        &ExecuteMsg::Unpause {
+            scope: PauseScope::Collection(nft.clone()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(cw721_owner, swap, &sale_msg(&nft, "second"), &[])
+        .unwrap();
+}
@@ -0,0 +1,298 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+use cw721::OwnerOfResponse;
+use cw721_base::msg::QueryMsg as Cw721QueryMsg;
+
+use crate::msg::{
+    CancelMsg, ExecuteMsg, FinishSwapMsg, QueryMsg, RentMsg, RentalMsg, SwapMsg, UserResponse,
+};
+use crate::prelude::{Rental, SwapType};
+use crate::testing::{
+    bank_query, create_cw20, create_cw721, create_swap, mint_and_approve, mint_native, mock_app,
+    query, Marketplace,
+};
+
+static DENOM: &str = "aarch";
+static DAY: u64 = 86400;
+
+fn rental(nft: &Addr, payment_token: Option<Addr>, price: u32) -> RentalMsg {
+    RentalMsg {
+        id: Some("rental".to_string()),
+        cw721: nft.clone(),
+        payment_token,
+        token_id: "token1".to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(price),
+        period: DAY,
+        max_periods: 7,
+    }
+}
+
+fn rent(periods: u32) -> ExecuteMsg {
+    ExecuteMsg::Rent(RentMsg {
+        id: "rental".to_string(),
+        periods,
+    })
+}
+
+fn owner_of(app: &mut App, nft: &Addr) -> String {
+    let res: OwnerOfResponse = query(
+        app,
+        nft.clone(),
+        Cw721QueryMsg::OwnerOf {
+            token_id: "token1".to_string(),
+            include_expired: None,
+        },
+    )
+    .unwrap();
+    res.owner
+}
+
+fn user_of(app: &mut App, swap: &Addr, nft: &Addr) -> UserResponse {
+    query(
+        app,
+        swap.clone(),
+        QueryMsg::UserOf {
+            token_id: "token1".to_string(),
+            cw721: nft.clone(),
+        },
+    )
+    .unwrap()
+}
+
+// cw721_owner rents out an NFT for aarch, and reclaims it after the rental
+pub fn test_rental_native<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let renter = Addr::unchecked("renter");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    mint_native(&mut app, renter.to_string(), Uint128::from(1000_u32));
+
+    // The token is held in escrow while listed
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::ListRental(rental(&nft, None, 100)),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&mut app, &nft), swap.to_string());
+    assert_eq!(user_of(&mut app, &swap, &nft).user, None);
+
+    // Renting is paid with exactly the price of the periods rented
+    let err = app
+        .execute_contract(
+            renter.clone(),
+            swap.clone(),
+            &rent(3),
+            &[Coin {
+                denom: DENOM.to_string(),
+                amount: Uint128::from(200_u32),
+            }],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Must send exactly the required funds"
+    );
+    app.execute_contract(
+        renter.clone(),
+        swap.clone(),
+        &rent(3),
+        &[Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(300_u32),
+        }],
+    )
+    .unwrap();
+    assert_eq!(
+        bank_query(&app, &cw721_owner).amount,
+        Uint128::from(300_u32)
+    );
+
+    let rented_until = Expiration::AtTime(app.block_info().time.plus_seconds(3 * DAY));
+    assert_eq!(
+        user_of(&mut app, &swap, &nft),
+        UserResponse {
+            user: Some(renter.clone()),
+            expires: Some(rented_until),
+        }
+    );
+    let terms: Rental = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::Rental {
+            id: "rental".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(terms.renter, Some(renter.clone()));
+
+    // The token can't be reclaimed while rented
+    let cancel_msg = ExecuteMsg::Cancel(CancelMsg {
+        id: "rental".to_string(),
+    });
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &cancel_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Token is rented");
+
+    // After the rental the token is returned
+    app.update_block(|block| block.time = block.time.plus_seconds(3 * DAY));
+    assert_eq!(user_of(&mut app, &swap, &nft).user, None);
+    app.execute_contract(cw721_owner.clone(), swap.clone(), &cancel_msg, &[])
+        .unwrap();
+    assert_eq!(owner_of(&mut app, &nft), cw721_owner.to_string());
+
+    // Free rentals are rented without funds
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token2");
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::ListRental(RentalMsg {
+            id: Some("free".to_string()),
+            token_id: "token2".to_string(),
+            ..rental(&nft, None, 0)
+        }),
+        &[],
+    )
+    .unwrap();
+    let rent_free = ExecuteMsg::Rent(RentMsg {
+        id: "free".to_string(),
+        periods: 1,
+    });
+    let err = app
+        .execute_contract(
+            renter.clone(),
+            swap.clone(),
+            &rent_free,
+            &[Coin {
+                denom: DENOM.to_string(),
+                amount: Uint128::from(100_u32),
+            }],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Must send exactly the required funds"
+    );
+    app.execute_contract(renter.clone(), swap.clone(), &rent_free, &[])
+        .unwrap();
+    assert_eq!(bank_query(&app, &renter).amount, Uint128::from(700_u32));
+    let terms: Rental = query(
+        &mut app,
+        swap,
+        QueryMsg::Rental {
+            id: "free".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(terms.renter, Some(renter));
+}
+
+// Rentals are paid from the renter's cw20 allowance, and
+// can't be rented again or finished like other swaps
+pub fn test_rental_cw20<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let renter = Addr::unchecked("renter");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &renter,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(1000_u32),
+    );
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+
+    // Rentals can only be listed with `ListRental`
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: None,
+                cw721: nft.clone(),
+                payment_token: Some(cw20.clone()),
+                token_id: "token1".to_string(),
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(100_u32),
+                swap_type: SwapType::Rental,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::ListRental(rental(&nft, Some(cw20.clone()), 100)),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        renter.clone(),
+        cw20.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: swap.to_string(),
+            amount: Uint128::from(1000_u32),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // At most `max_periods` can be rented
+    let err = app
+        .execute_contract(renter.clone(), swap.clone(), &rent(8), &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    // Rentals can't be finished
+    let err = app
+        .execute_contract(
+            renter.clone(),
+            swap.clone(),
+            &ExecuteMsg::Finish(FinishSwapMsg {
+                id: "rental".to_string(),
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    app.execute_contract(renter.clone(), swap.clone(), &rent(2), &[])
+        .unwrap();
+    let balance: BalanceResponse = query(
+        &mut app,
+        cw20.clone(),
+        Cw20QueryMsg::Balance {
+            address: cw721_owner.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(balance.balance, Uint128::from(200_u32));
+    assert_eq!(user_of(&mut app, &swap, &nft).user, Some(renter.clone()));
+
+    // A rented token can't be rented again until the rental expires
+    let err = app
+        .execute_contract(renter.clone(), swap.clone(), &rent(1), &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Token is rented");
+    app.update_block(|block| block.time = block.time.plus_seconds(2 * DAY));
+    app.execute_contract(renter.clone(), swap.clone(), &rent(1), &[])
+        .unwrap();
+}
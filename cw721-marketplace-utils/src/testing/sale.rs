@@ -1,25 +1,23 @@
-#![cfg(test)]
 use cosmwasm_std::{Addr, Coin, Uint128};
 use cw_multi_test::Executor;
 
+use crate::prelude::PageResult;
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
 use cw721::OwnerOfResponse;
 use cw721_base::{
     msg::ExecuteMsg as Cw721ExecuteMsg, msg::QueryMsg as Cw721QueryMsg, Extension, MintMsg,
 };
-use cw721_marketplace_utils::prelude::PageResult;
 
-use crate::integration_tests::util::{
-    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query,
-};
 use crate::msg::{ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg};
-use crate::state::SwapType;
+use crate::prelude::SwapType;
+use crate::testing::{
+    bank_query, create_cw20, create_cw721, create_swap, mint_native, mock_app, query, Marketplace,
+};
 
 static DENOM: &str = "aarch";
 
 // Swap buyer pays with ARCH
-#[test]
-fn test_buy_native() {
+pub fn test_buy_native<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -33,7 +31,7 @@ fn test_buy_native() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // Mint native to `arch_owner`
@@ -58,7 +56,7 @@ fn test_buy_native() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: None,
         token_id: token_id.clone(),
@@ -67,7 +65,7 @@ fn test_buy_native() {
         swap_type: SwapType::Sale,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
@@ -140,8 +138,7 @@ fn test_buy_native() {
 }
 
 // Receive cw20 tokens and release upon approval
-#[test]
-fn test_buy_cw20() {
+pub fn test_buy_cw20<M: Marketplace>() {
     let mut app = mock_app();
 
     // Swap owner deploys
@@ -155,7 +152,7 @@ fn test_buy_cw20() {
     let nft = create_cw721(&mut app, &cw721_owner);
 
     // swap_admin creates the swap contract
-    let swap = create_swap(&mut app, &swap_admin);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
     let swap_inst = swap.clone();
 
     // cw20_owner creates a cw20 coin
@@ -183,7 +180,7 @@ fn test_buy_cw20() {
 
     // Create a SwapMsg for creating / finishing a swap
     let creation_msg = SwapMsg {
-        id: "firstswap".to_string(),
+        id: Some("firstswap".to_string()),
         cw721: nft.clone(),
         payment_token: Some(Addr::unchecked(cw20.clone())),
         token_id: token_id.clone(),
@@ -192,7 +189,7 @@ fn test_buy_cw20() {
         swap_type: SwapType::Sale,
     };
     let finish_msg = FinishSwapMsg {
-        id: creation_msg.id.clone(),
+        id: creation_msg.id.clone().unwrap(),
     };
 
     // Seller (cw721_owner) must approve the swap contract to spend their NFT
@@ -0,0 +1,323 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::{Cw20ExecuteMsg, Expiration};
+use cw721_base::msg::ExecuteMsg as Cw721ExecuteMsg;
+use cw721_base::Extension;
+
+use crate::msg::{ExecuteMsg, FinishSwapMsg, QueryMsg, SwapMsg};
+use crate::prelude::{
+    DetailsResponse, SimulateFinishResponse, SimulatedTransfer, SwapType, TransferType,
+};
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, create_swap_with_fees, mint_and_approve, mint_native,
+    mock_app, query, Marketplace,
+};
+
+fn finish(id: &str) -> ExecuteMsg {
+    ExecuteMsg::Finish(FinishSwapMsg { id: id.to_string() })
+}
+
+fn details(app: &mut App, swap: &Addr, id: &str) -> DetailsResponse {
+    query(app, swap.clone(), QueryMsg::Details { id: id.to_string() }).unwrap()
+}
+
+// A sale whose NFT was moved away fails with the cw721 contract, and
+// stays listed; a settled sale is replied to once
+pub fn test_nft_transfer_failed<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let buyer = Addr::unchecked("buyer");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token2");
+    mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u32));
+
+    for token_id in ["token1", "token2"] {
+        app.execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: Some(token_id.to_string()),
+                cw721: nft.clone(),
+                payment_token: None,
+                token_id: token_id.to_string(),
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(100_u32),
+                swap_type: SwapType::Sale,
+            }),
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        cw721_owner.clone(),
+        nft.clone(),
+        &Cw721ExecuteMsg::<Extension>::TransferNft {
+            recipient: "random".to_string(),
+            token_id: "token1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let funds = [Coin {
+        denom: "aarch".to_string(),
+        amount: Uint128::from(100_u32),
+    }];
+    let err = app
+        .execute_contract(buyer.clone(), swap.clone(), &finish("token1"), &funds)
+        .unwrap_err();
+    let error = err.root_cause().to_string();
+    assert!(error.starts_with(&format!("NFT transfer failed on {nft}")));
+    assert_eq!(details(&mut app, &swap, "token1").token_id, "token1");
+
+    let res = app
+        .execute_contract(buyer.clone(), swap.clone(), &finish("token2"), &funds)
+        .unwrap();
+    let settled: Vec<(String, String)> = res
+        .events
+        .iter()
+        .filter(|event| {
+            event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == "settle")
+        })
+        .map(|event| {
+            let value = |key: &str| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|attr| attr.key == key)
+                    .map(|attr| attr.value.clone())
+                    .unwrap()
+            };
+            (value("swap_id"), value("transfers"))
+        })
+        .collect();
+    assert_eq!(settled, vec![("token2".to_string(), "2".to_string())]);
+}
+
+// An offer accepted without enough allowance fails with the cw20 contract,
+// and stays open
+pub fn test_payment_failed<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &ExecuteMsg::Create(SwapMsg {
+            id: Some("offer".to_string()),
+            cw721: nft.clone(),
+            payment_token: Some(cw20.clone()),
+            token_id: "token1".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(1000_u32),
+            swap_type: SwapType::Offer,
+        }),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        bidder.clone(),
+        cw20.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: swap.to_string(),
+            amount: Uint128::from(500_u32),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &finish("offer"), &[])
+        .unwrap_err();
+    let error = err.root_cause().to_string();
+    assert!(error.starts_with(&format!("Payment failed on {cw20}")));
+    assert_eq!(details(&mut app, &swap, "offer").creator, bidder);
+}
+
+fn simulate(
+    app: &mut App,
+    swap: &Addr,
+    id: &str,
+    sender: &Addr,
+    funds: &[Coin],
+) -> SimulateFinishResponse {
+    let msg = QueryMsg::SimulateFinish {
+        id: id.to_string(),
+        sender: sender.clone(),
+        funds: funds.to_vec(),
+    };
+    query(app, swap.clone(), msg).unwrap()
+}
+
+fn transfer(
+    transfer_type: TransferType,
+    recipient: &Addr,
+    token: &str,
+    amount: u128,
+    token_id: Option<&str>,
+) -> SimulatedTransfer {
+    SimulatedTransfer {
+        transfer_type,
+        recipient: recipient.clone(),
+        token: token.to_string(),
+        amount: Uint128::from(amount),
+        token_id: token_id.map(str::to_string),
+    }
+}
+
+// Simulating a finish fails with the error finishing fails with,
+// or returns the transfers finishing makes
+pub fn test_simulate_finish<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let buyer = Addr::unchecked("buyer");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap_with_fees::<M>(&mut app, &swap_admin, nft.clone(), 10);
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token2");
+    mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u32));
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Create(SwapMsg {
+            id: Some("sale".to_string()),
+            cw721: nft.clone(),
+            payment_token: None,
+            token_id: "token1".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(100_u32),
+            swap_type: SwapType::Sale,
+        }),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &ExecuteMsg::Create(SwapMsg {
+            id: Some("offer".to_string()),
+            cw721: nft.clone(),
+            payment_token: Some(cw20.clone()),
+            token_id: "token2".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(1000_u32),
+            swap_type: SwapType::Offer,
+        }),
+        &[],
+    )
+    .unwrap();
+    let allowance = Cw20ExecuteMsg::IncreaseAllowance {
+        spender: swap.to_string(),
+        amount: Uint128::from(500_u32),
+        expires: None,
+    };
+    app.execute_contract(bidder.clone(), cw20.clone(), &allowance, &[])
+        .unwrap();
+
+    // Underpaid sale
+    let underpaid = [Coin {
+        denom: "aarch".to_string(),
+        amount: Uint128::from(50_u32),
+    }];
+    let simulated = simulate(&mut app, &swap, "sale", &buyer, &underpaid);
+    let err = app
+        .execute_contract(buyer.clone(), swap.clone(), &finish("sale"), &underpaid)
+        .unwrap_err();
+    assert_eq!(simulated.error, Some(err.root_cause().to_string()));
+    assert!(simulated.transfers.is_empty());
+
+    // Offer without enough allowance, or accepted by someone else than the owner
+    let simulated = simulate(&mut app, &swap, "offer", &cw721_owner, &[]);
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &finish("offer"), &[])
+        .unwrap_err();
+    assert_eq!(
+        simulated.error,
+        Some(format!(
+            "Payment failed on {cw20}: allowance of 500 is less than 1000"
+        ))
+    );
+    assert_eq!(simulated.error, Some(err.root_cause().to_string()));
+    let simulated = simulate(&mut app, &swap, "offer", &buyer, &[]);
+    assert_eq!(simulated.error, Some("Unauthorized".to_string()));
+    let simulated = simulate(&mut app, &swap, "missing", &buyer, &[]);
+    assert!(simulated.error.is_some());
+
+    // Paid sale, the marketplace keeps the native fee
+    let funds = [Coin {
+        denom: "aarch".to_string(),
+        amount: Uint128::from(100_u32),
+    }];
+    let simulated = simulate(&mut app, &swap, "sale", &buyer, &funds);
+    assert_eq!(simulated.error, None);
+    assert_eq!(
+        simulated.transfers,
+        vec![
+            transfer(TransferType::Nft, &buyer, nft.as_str(), 1, Some("token1")),
+            transfer(TransferType::SellerPayout, &cw721_owner, "aarch", 90, None),
+            transfer(TransferType::Fee, &swap, "aarch", 10, None),
+        ]
+    );
+
+    // Accepted offer, the cw20 fee is transferred to the marketplace
+    app.execute_contract(bidder.clone(), cw20.clone(), &allowance, &[])
+        .unwrap();
+    let simulated = simulate(&mut app, &swap, "offer", &cw721_owner, &[]);
+    assert_eq!(simulated.error, None);
+    assert_eq!(
+        simulated.transfers,
+        vec![
+            transfer(TransferType::Nft, &bidder, nft.as_str(), 1, Some("token2")),
+            transfer(
+                TransferType::SellerPayout,
+                &cw721_owner,
+                cw20.as_str(),
+                900,
+                None
+            ),
+            transfer(TransferType::Fee, &swap, cw20.as_str(), 100, None),
+        ]
+    );
+    app.execute_contract(cw721_owner.clone(), swap.clone(), &finish("offer"), &[])
+        .unwrap();
+
+    // Finished swaps can't be finished again
+    let simulated = simulate(&mut app, &swap, "offer", &cw721_owner, &[]);
+    assert!(simulated.error.is_some());
+}
@@ -0,0 +1,161 @@
+use cosmwasm_std::{from_json, Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::Expiration;
+
+use crate::msg::{CreateResponse, ExecuteMsg, QueryMsg, SwapMsg};
+use crate::prelude::{ListResponse, SwapType};
+use crate::state::{IdCharset, SwapIdRules};
+use crate::testing::{create_cw721, create_swap, mint_and_approve, mock_app, query, Marketplace};
+
+fn sale_msg(nft: &Addr, id: Option<&str>, token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::Create(SwapMsg {
+        id: id.map(String::from),
+        cw721: nft.clone(),
+        payment_token: None,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000_u128),
+        swap_type: SwapType::Sale,
+    })
+}
+
+// Creates a sale and returns the id of the swap
+fn create_sale(app: &mut App, swap: &Addr, owner: &Addr, msg: &ExecuteMsg) -> String {
+    let res = app
+        .execute_contract(owner.clone(), swap.clone(), msg, &[])
+        .unwrap();
+    let data: CreateResponse = from_json(res.data.unwrap()).unwrap();
+
+    // Id is also emitted in the create event
+    let event = res.events.iter().find(|event| event.ty == "wasm").unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "swap_id" && attr.value == data.id));
+
+    data.id
+}
+
+// Contract assigns ids from its swap counter when none is supplied
+pub fn test_assigned_ids<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    for token_id in ["token1", "token2", "token3"] {
+        mint_and_approve(&mut app, &swap, &nft, &cw721_owner, token_id);
+    }
+
+    let id = create_sale(
+        &mut app,
+        &swap,
+        &cw721_owner,
+        &sale_msg(&nft, None, "token1"),
+    );
+    assert_eq!(id, "~1");
+
+    // The prefix of assigned ids is reserved for the swap counter
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &sale_msg(&nft, Some("~2"), "token2"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid atomic swap id");
+
+    // Numeric client supplied ids keep working
+    let id = create_sale(
+        &mut app,
+        &swap,
+        &cw721_owner,
+        &sale_msg(&nft, Some("2"), "token2"),
+    );
+    assert_eq!(id, "2");
+
+    let id = create_sale(
+        &mut app,
+        &swap,
+        &cw721_owner,
+        &sale_msg(&nft, None, "token3"),
+    );
+    assert_eq!(id, "~2");
+
+    let swaps: ListResponse = query(
+        &mut app,
+        swap,
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(swaps.swaps, vec!["2", "~1", "~2"]);
+}
+
+// Client supplied ids must follow the id rules set by the admin
+pub fn test_swap_id_rules<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+
+    let rules: SwapIdRules = query(&mut app, swap.clone(), QueryMsg::SwapIdRules {}).unwrap();
+    assert_eq!(rules, SwapIdRules::default());
+
+    // Ids can't be empty, too long or contain whitespace
+    let too_long = "a".repeat(rules.max_length as usize + 1);
+    for id in ["", "my swap", too_long.as_str()] {
+        let err = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap.clone(),
+                &sale_msg(&nft, Some(id), "token1"),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(err.root_cause().to_string(), "Invalid atomic swap id");
+    }
+
+    // Only admin can update the rules
+    let update_msg = ExecuteMsg::UpdateSwapIdRules {
+        rules: SwapIdRules {
+            max_length: 8,
+            charset: IdCharset::Alphanumeric,
+        },
+    };
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &update_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    app.execute_contract(swap_admin, swap.clone(), &update_msg, &[])
+        .unwrap();
+
+    for id in ["my-swap", "longswapid"] {
+        let err = app
+            .execute_contract(
+                cw721_owner.clone(),
+                swap.clone(),
+                &sale_msg(&nft, Some(id), "token1"),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(err.root_cause().to_string(), "Invalid atomic swap id");
+    }
+    app.execute_contract(
+        cw721_owner,
+        swap,
+        &sale_msg(&nft, Some("mySwap1"), "token1"),
+        &[],
+    )
+    .unwrap();
+}
@@ -0,0 +1,279 @@
+use cosmwasm_std::{from_json, Addr, Coin, Uint128};
+use cw_multi_test::{App, Executor};
+
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+use cw721::OwnerOfResponse;
+use cw721_base::msg::{ExecuteMsg as Cw721ExecuteMsg, QueryMsg as Cw721QueryMsg};
+use cw721_base::Extension;
+
+use crate::msg::{ExecuteMsg, QueryMsg, SwapMsg, SweepMsg, SweepResponse};
+use crate::prelude::{ListResponse, SwapType};
+use crate::testing::{
+    bank_query, create_cw20, create_cw721, create_swap, mint_and_approve, mint_native, mock_app,
+    query, Marketplace,
+};
+
+static DENOM: &str = "aarch";
+
+fn sale(nft: &Addr, payment_token: Option<Addr>, token_id: &str, price: u128) -> SwapMsg {
+    SwapMsg {
+        id: None,
+        cw721: nft.clone(),
+        payment_token,
+        token_id: token_id.to_string(),
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(price),
+        swap_type: SwapType::Sale,
+    }
+}
+
+fn sweep(nft: &Addr, payment_token: Option<Addr>, max_items: u32, max_price: u128) -> ExecuteMsg {
+    ExecuteMsg::Sweep(SweepMsg {
+        cw721: nft.clone(),
+        max_items,
+        max_price_per_item: Uint128::from(max_price),
+        payment_token,
+    })
+}
+
+fn aarch(amount: u128) -> Vec<Coin> {
+    vec![Coin {
+        denom: DENOM.to_string(),
+        amount: Uint128::from(amount),
+    }]
+}
+
+fn owner_of(app: &mut App, nft: &Addr, token_id: &str) -> String {
+    let res: OwnerOfResponse = query(
+        app,
+        nft.clone(),
+        Cw721QueryMsg::OwnerOf {
+            token_id: token_id.to_string(),
+            include_expired: None,
+        },
+    )
+    .unwrap();
+    res.owner
+}
+
+fn list(app: &mut App, swap: &Addr) -> Vec<String> {
+    let res: ListResponse = query(
+        app,
+        swap.clone(),
+        QueryMsg::List {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    res.swaps
+}
+
+// The cheapest valid native listings are bought, and unspent funds refunded
+pub fn test_sweep_native<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let buyer = Addr::unchecked("buyer");
+    let random = Addr::unchecked("random");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    for token_id in ["token1", "token2", "token3", "token4", "token5"] {
+        mint_and_approve(&mut app, &swap, &nft, &cw721_owner, token_id);
+    }
+    mint_native(&mut app, buyer.to_string(), Uint128::from(10000_u128));
+
+    let expiring = SwapMsg {
+        expires: Expiration::AtHeight(app.block_info().height + 1),
+        ..sale(&nft, None, "token5", 800)
+    };
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::BatchCreate(vec![
+            sale(&nft, None, "token1", 500),
+            sale(&nft, None, "token2", 1000),
+            sale(&nft, None, "token3", 1500),
+            sale(&nft, None, "token4", 3000),
+            expiring,
+        ]),
+        &[],
+    )
+    .unwrap();
+
+    // token2 is transferred away by its seller, and token5's listing expires
+    app.execute_contract(
+        cw721_owner.clone(),
+        nft.clone(),
+        &Cw721ExecuteMsg::<Extension>::TransferNft {
+            recipient: random.to_string(),
+            token_id: "token2".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 2);
+
+    // Invalid item counts and other denoms are rejected
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &sweep(&nft, None, 0, 2000),
+            &aarch(5000),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Batch must contain between 1 and 50 swaps"
+    );
+    mint_native_denom(&mut app, &buyer, "uatom", 100);
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &sweep(&nft, None, 2, 2000),
+            &[Coin {
+                denom: "uatom".to_string(),
+                amount: Uint128::from(100_u128),
+            }],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid payment token");
+
+    let res = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &sweep(&nft, None, 2, 2000),
+            &aarch(5000),
+        )
+        .unwrap();
+    let data: SweepResponse = from_json(res.data.unwrap()).unwrap();
+    let bought: Vec<(&str, u128)> = data
+        .bought
+        .iter()
+        .map(|item| (item.token_id.as_str(), item.price.u128()))
+        .collect();
+    assert_eq!(bought, vec![("token1", 500), ("token3", 1500)]);
+
+    // Buyer pays only for what was bought
+    assert_eq!(bank_query(&app, &buyer).amount, Uint128::from(8000_u128));
+    assert_eq!(
+        bank_query(&app, &cw721_owner).amount,
+        Uint128::from(2000_u128)
+    );
+    assert_eq!(owner_of(&mut app, &nft, "token1"), buyer.to_string());
+    assert_eq!(owner_of(&mut app, &nft, "token3"), buyer.to_string());
+    assert_eq!(list(&mut app, &swap), vec!["~2", "~4", "~5"]);
+
+    // Listings above the max price or the funds sent are not bought
+    let res = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &sweep(&nft, None, 5, 5000),
+            &aarch(2500),
+        )
+        .unwrap();
+    let data: SweepResponse = from_json(res.data.unwrap()).unwrap();
+    assert!(data.bought.is_empty());
+    assert_eq!(bank_query(&app, &buyer).amount, Uint128::from(8000_u128));
+}
+
+// cw20 listings are bought from the buyer's allowance
+pub fn test_sweep_cw20<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let buyer = Addr::unchecked("buyer");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &buyer,
+        "testcw".to_string(),
+        "TSTCW".to_string(),
+        Uint128::from(10000_u128),
+    );
+    for token_id in ["token1", "token2", "token3"] {
+        mint_and_approve(&mut app, &swap, &nft, &cw721_owner, token_id);
+    }
+
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::BatchCreate(vec![
+            sale(&nft, Some(cw20.clone()), "token1", 1000),
+            sale(&nft, None, "token2", 100),
+            sale(&nft, Some(cw20.clone()), "token3", 2000),
+        ]),
+        &[],
+    )
+    .unwrap();
+
+    // Native funds can't be sent for cw20 listings
+    mint_native(&mut app, buyer.to_string(), Uint128::from(1000_u128));
+    let err = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &sweep(&nft, Some(cw20.clone()), 3, 5000),
+            &aarch(1000),
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid payment token");
+
+    // Allowance only covers the cheapest cw20 listing
+    app.execute_contract(
+        buyer.clone(),
+        cw20.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: swap.to_string(),
+            amount: Uint128::from(2500_u128),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            buyer.clone(),
+            swap.clone(),
+            &sweep(&nft, Some(cw20.clone()), 3, 5000),
+            &[],
+        )
+        .unwrap();
+    let data: SweepResponse = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(data.bought.len(), 1);
+    assert_eq!(data.bought[0].token_id, "token1");
+
+    let balance: BalanceResponse = query(
+        &mut app,
+        cw20.clone(),
+        Cw20QueryMsg::Balance {
+            address: cw721_owner.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(balance.balance, Uint128::from(1000_u128));
+    assert_eq!(owner_of(&mut app, &nft, "token1"), buyer.to_string());
+    assert_eq!(list(&mut app, &swap), vec!["~2", "~3"]);
+}
+
+fn mint_native_denom(app: &mut App, beneficiary: &Addr, denom: &str, amount: u128) {
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: beneficiary.to_string(),
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount: Uint128::from(amount),
+            }],
+        },
+    ))
+    .unwrap();
+}
@@ -0,0 +1,372 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use serde::{Deserialize, Serialize};
+
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+use cw721::OwnerOfResponse;
+use cw721_base::msg::{
+    ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg, QueryMsg as Cw721QueryMsg,
+};
+use cw721_base::{Cw721Contract, MintMsg};
+
+use crate::msg::{
+    CancelMsg, ExecuteMsg, FillTraitOfferMsg, FinishSwapMsg, QueryMsg, SwapMsg, TraitOfferMsg,
+};
+use crate::prelude::{DetailsResponse, SwapStatus, SwapType, Trait};
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, mint_and_approve, mock_app, query, Marketplace,
+};
+
+// cw721-metadata-onchain `Metadata`, trimmed to the fields used here
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+struct Metadata {
+    name: Option<String>,
+    attributes: Option<Vec<MetadataTrait>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct MetadataTrait {
+    display_type: Option<String>,
+    trait_type: String,
+    value: String,
+}
+
+type MetadataCw721<'a> = Cw721Contract<'a, Option<Metadata>, Empty>;
+
+fn contract_cw721_metadata() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |deps, env, info, msg: Cw721ExecuteMsg<Option<Metadata>>| {
+            MetadataCw721::default().execute(deps, env, info, msg)
+        },
+        |deps, env, info, msg: Cw721InstantiateMsg| {
+            MetadataCw721::default().instantiate(deps, env, info, msg)
+        },
+        |deps, env, msg: Cw721QueryMsg| MetadataCw721::default().query(deps, env, msg),
+    );
+    Box::new(contract)
+}
+
+fn create_cw721_metadata(app: &mut App, minter: &Addr) -> Addr {
+    let code_id = app.store_code(contract_cw721_metadata());
+    let msg = Cw721InstantiateMsg {
+        name: "TESTNFT".to_string(),
+        symbol: "TSNFT".to_string(),
+        minter: minter.to_string(),
+    };
+    app.instantiate_contract(code_id, minter.clone(), &msg, &[], "swap721", None)
+        .unwrap()
+}
+
+// Mints `token_id` with `traits` to `owner` and approves the swap contract to transfer it
+fn mint_with_traits(
+    app: &mut App,
+    swap: &Addr,
+    nft: &Addr,
+    owner: &Addr,
+    token_id: &str,
+    traits: &[(&str, &str)],
+) {
+    let attributes = traits
+        .iter()
+        .map(|(trait_type, value)| MetadataTrait {
+            display_type: None,
+            trait_type: trait_type.to_string(),
+            value: value.to_string(),
+        })
+        .collect();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: Some(Metadata {
+            name: Some(token_id.to_string()),
+            attributes: Some(attributes),
+        }),
+    });
+    app.execute_contract(owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    let approve_msg = Cw721ExecuteMsg::<Option<Metadata>>::Approve {
+        spender: swap.to_string(),
+        token_id: token_id.to_string(),
+        expires: None,
+    };
+    app.execute_contract(owner.clone(), nft.clone(), &approve_msg, &[])
+        .unwrap();
+}
+
+fn gold_background() -> Vec<Trait> {
+    vec![Trait {
+        trait_type: "background".to_string(),
+        value: "gold".to_string(),
+    }]
+}
+
+fn trait_offer(nft: &Addr, payment_token: Option<Addr>, traits: Vec<Trait>) -> ExecuteMsg {
+    ExecuteMsg::CreateTraitOffer(TraitOfferMsg {
+        id: Some("trait_offer".to_string()),
+        cw721: nft.clone(),
+        payment_token,
+        expires: Expiration::AtHeight(384798573487439743),
+        price: Uint128::from(1000_u32),
+        traits,
+    })
+}
+
+fn fill(token_id: &str) -> ExecuteMsg {
+    ExecuteMsg::FillTraitOffer(FillTraitOfferMsg {
+        id: "trait_offer".to_string(),
+        token_id: token_id.to_string(),
+    })
+}
+
+fn allow(app: &mut App, cw20: &Addr, owner: &Addr, swap: &Addr, amount: u32) {
+    app.execute_contract(
+        owner.clone(),
+        cw20.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: swap.to_string(),
+            amount: Uint128::from(amount),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn owner_of(app: &mut App, nft: &Addr, token_id: &str) -> String {
+    let res: OwnerOfResponse = query(
+        app,
+        nft.clone(),
+        Cw721QueryMsg::OwnerOf {
+            token_id: token_id.to_string(),
+            include_expired: None,
+        },
+    )
+    .unwrap();
+    res.owner
+}
+
+// Bidder's trait offer is filled with the only token having the required trait
+pub fn test_trait_offer_filled<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721_metadata(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+    mint_with_traits(
+        &mut app,
+        &swap,
+        &nft,
+        &cw721_owner,
+        "token1",
+        &[("background", "gold"), ("eyes", "laser")],
+    );
+    mint_with_traits(
+        &mut app,
+        &swap,
+        &nft,
+        &cw721_owner,
+        "token2",
+        &[("background", "blue")],
+    );
+
+    // Trait offers are paid in cw20 tokens, and only created with `CreateTraitOffer`
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &trait_offer(&nft, None, gold_background()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid payment token");
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &ExecuteMsg::Create(SwapMsg {
+                id: None,
+                cw721: nft.clone(),
+                payment_token: Some(cw20.clone()),
+                token_id: "token1".to_string(),
+                expires: Expiration::AtHeight(384798573487439743),
+                price: Uint128::from(1000_u32),
+                swap_type: SwapType::TraitOffer,
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &trait_offer(&nft, Some(cw20.clone()), gold_background()),
+        &[],
+    )
+    .unwrap();
+    allow(&mut app, &cw20, &bidder, &swap, 1000);
+    let traits: Vec<Trait> = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::Traits {
+            id: "trait_offer".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(traits, gold_background());
+
+    // Trait offers can't be finished like other swaps
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Finish(FinishSwapMsg {
+                id: "trait_offer".to_string(),
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    // Tokens without the required traits can't fill the offer
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &fill("token2"), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Token does not have the required traits"
+    );
+
+    // Only the token owner can fill the offer
+    let err = app
+        .execute_contract(
+            Addr::unchecked("random"),
+            swap.clone(),
+            &fill("token1"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    app.execute_contract(cw721_owner.clone(), swap.clone(), &fill("token1"), &[])
+        .unwrap();
+    assert_eq!(owner_of(&mut app, &nft, "token1"), bidder.to_string());
+    let balance: BalanceResponse = query(
+        &mut app,
+        cw20,
+        Cw20QueryMsg::Balance {
+            address: cw721_owner.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(balance.balance, Uint128::from(1000_u32));
+
+    // The trait offer is closed once filled, with the token it was filled with
+    let details: DetailsResponse = query(
+        &mut app,
+        swap.clone(),
+        QueryMsg::Details {
+            id: "trait_offer".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(details.status, SwapStatus::Filled);
+    assert_eq!(details.filler, Some(cw721_owner));
+    assert_eq!(details.token_id, "token1");
+}
+
+// Tokens without on-chain metadata have no traits, and
+// only fill trait offers requiring none (collection offers)
+pub fn test_trait_offer_without_metadata<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let cw20 = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    allow(&mut app, &cw20, &bidder, &swap, 1000);
+
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &trait_offer(&nft, Some(cw20.clone()), gold_background()),
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(cw721_owner.clone(), swap.clone(), &fill("token1"), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Token does not have the required traits"
+    );
+
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &ExecuteMsg::Cancel(CancelMsg {
+            id: "trait_offer".to_string(),
+        }),
+        &[],
+    )
+    .unwrap();
+
+    // Ids of cancelled swaps can't be reused
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &trait_offer(&nft, Some(cw20.clone()), vec![]),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Cancelled atomic swap");
+
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &ExecuteMsg::CreateTraitOffer(TraitOfferMsg {
+            id: Some("collection_offer".to_string()),
+            cw721: nft.clone(),
+            payment_token: Some(cw20),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(1000_u32),
+            traits: vec![],
+        }),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::FillTraitOffer(FillTraitOfferMsg {
+            id: "collection_offer".to_string(),
+            token_id: "token1".to_string(),
+        }),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(owner_of(&mut app, &nft, "token1"), bidder.to_string());
+}
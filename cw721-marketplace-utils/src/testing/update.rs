@@ -0,0 +1,458 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::{App, AppResponse, Executor};
+
+use cw20::{Cw20ExecuteMsg, Expiration};
+use cw721_base::{msg::ExecuteMsg as Cw721ExecuteMsg, Extension, MintMsg};
+
+use crate::msg::{CounterMsg, ExecuteMsg, QueryMsg, SwapMsg, UpdateMsg, UpdatePaymentTokenMsg};
+use crate::prelude::{DetailsResponse, SwapStatus, SwapType};
+use crate::testing::{
+    create_cw20, create_cw721, create_swap, mint_and_approve, mock_app, query, Marketplace,
+};
+
+// Updating a swap of type SwapType::Sale
+pub fn test_updating_sales<M: Marketplace>() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri.clone()),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Create a SwapMsg for creating a swap
+    let swap_id: String = "firstswap".to_string();
+    let creation_msg = SwapMsg {
+        id: Some(swap_id.clone()),
+        cw721: nft.clone(),
+        payment_token: None,
+        token_id: token_id.clone(),
+        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        price: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        swap_type: SwapType::Sale,
+    };
+
+    // Seller (cw721_owner) must approve the swap contract to spend their NFT
+    let nft_approve_msg = Cw721ExecuteMsg::Approve::<Extension> {
+        spender: swap.to_string(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    app.execute_contract(cw721_owner.clone(), nft.clone(), &nft_approve_msg, &[])
+        .unwrap();
+
+    // cw721 seller (cw721_owner) creates a swap
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+
+    // Original swap details (price and expiration) are correct
+    let swap_details: DetailsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: swap_id.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        swap_details.expires,
+        Expiration::from(cw20::Expiration::AtHeight(384798573487439743))
+    );
+    assert_eq!(swap_details.price, Uint128::from(1000000000000000000_u128));
+
+    // cw721 seller (cw721_owner) updates the swap
+    let update_msg = UpdateMsg {
+        id: swap_id.clone(),
+        expires: Expiration::from(cw20::Expiration::AtHeight(400000000000000000)),
+        price: Uint128::from(2000000000000000000_u128),
+    };
+    let _res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Update(update_msg),
+            &[],
+        )
+        .unwrap();
+
+    // Swap details (price and expiration) must be updated
+    let swap_details: DetailsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: swap_id.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        swap_details.expires,
+        Expiration::from(cw20::Expiration::AtHeight(400000000000000000))
+    );
+    assert_eq!(swap_details.price, Uint128::from(2000000000000000000_u128));
+}
+
+// Updating a swap of type SwapType::Offer
+pub fn test_updating_offers<M: Marketplace>() {
+    let mut app = mock_app();
+
+    // Swap owner deploys
+    let swap_admin = Addr::unchecked("swap_deployer");
+    // cw721_owner owns the cw721
+    let cw721_owner = Addr::unchecked("original_owner");
+    // cw20_owner owns wARCH (wrapped ARCH)
+    let cw20_owner = Addr::unchecked("cw20_owner");
+
+    // cw721_owner creates the cw721
+    let nft = create_cw721(&mut app, &cw721_owner);
+
+    // swap_admin creates the swap contract
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    let swap_inst = swap.clone();
+
+    // cw20_owner creates a cw20 coin
+    let cw20 = create_cw20(
+        &mut app,
+        &cw20_owner,
+        "wrapped arch".to_string(),
+        "wARCH".to_string(),
+        Uint128::from(9000000000000000000_u128), // 9 wARCH
+    );
+    let cw20_inst = cw20.clone();
+
+    // cw721_owner mints a cw721
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: cw721_owner.to_string(),
+        token_uri: Some(token_uri.clone()),
+        extension: None,
+    });
+    let _res = app
+        .execute_contract(cw721_owner.clone(), nft.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // Bidding buyer (cw20_owner) must approve swap contract to spend their cw20
+    let cw20_approve_msg = Cw20ExecuteMsg::IncreaseAllowance {
+        spender: swap.to_string(),
+        amount: Uint128::from(9000000000000000000_u128),
+        expires: None,
+    };
+    let _res = app
+        .execute_contract(
+            cw20_owner.clone(),
+            cw20_inst.clone(),
+            &cw20_approve_msg,
+            &[],
+        )
+        .unwrap();
+
+    // Bidding buyer creates an offer
+    let swap_id: String = "firstswap".to_string();
+    let creation_msg = SwapMsg {
+        id: Some(swap_id.clone()),
+        cw721: nft.clone(),
+        payment_token: Some(Addr::unchecked(cw20)),
+        token_id: token_id.clone(),
+        expires: Expiration::from(cw20::Expiration::AtHeight(384798573487439743)),
+        price: Uint128::from(9000000000000000000_u128), // 9 wARCH
+        swap_type: SwapType::Offer,
+    };
+
+    let _res = app
+        .execute_contract(
+            cw20_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Create(creation_msg),
+            &[],
+        )
+        .unwrap();
+
+    // Original swap details (price and expiration) are correct
+    let swap_details: DetailsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: swap_id.clone(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        swap_details.expires,
+        Expiration::from(cw20::Expiration::AtHeight(384798573487439743))
+    );
+    assert_eq!(swap_details.price, Uint128::from(9000000000000000000_u128));
+
+    // Bidder (cw20_owner) updates the swap
+    let update_msg = UpdateMsg {
+        id: swap_id.clone(),
+        expires: Expiration::from(cw20::Expiration::AtHeight(400000000000000000)),
+        price: Uint128::from(1000000000000000000_u128),
+    };
+    let _res = app
+        .execute_contract(
+            cw20_owner.clone(),
+            swap_inst.clone(),
+            &ExecuteMsg::Update(update_msg),
+            &[],
+        )
+        .unwrap();
+
+    // Swap details were correctly updated
+    let swap_details: DetailsResponse = query(
+        &mut app,
+        swap_inst.clone(),
+        QueryMsg::Details {
+            id: swap_id.clone(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        swap_details.expires,
+        Expiration::from(cw20::Expiration::AtHeight(400000000000000000))
+    );
+    assert_eq!(swap_details.price, Uint128::from(1000000000000000000_u128));
+}
+
+fn update_payment_token(id: &str, payment_token: Option<&Addr>, price: u128) -> ExecuteMsg {
+    ExecuteMsg::UpdatePaymentToken(UpdatePaymentTokenMsg {
+        id: id.to_string(),
+        payment_token: payment_token.cloned(),
+        price: Uint128::from(price),
+    })
+}
+
+fn details(app: &mut App, swap: &Addr, id: &str) -> DetailsResponse {
+    query(app, swap.clone(), QueryMsg::Details { id: id.to_string() }).unwrap()
+}
+
+fn update_attributes(res: &AppResponse, keys: &[&str]) -> Vec<String> {
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "wasm-marketplace-update")
+        .unwrap();
+    keys.iter()
+        .map(|key| {
+            event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == *key)
+                .map(|attr| attr.value.clone())
+                .unwrap()
+        })
+        .collect()
+}
+
+// Changing the payment token of swaps keeps their id, offers must
+// be covered by an allowance of the new token
+pub fn test_updating_payment_token<M: Marketplace>() {
+    let mut app = mock_app();
+
+    let swap_admin = Addr::unchecked("swap_deployer");
+    let cw721_owner = Addr::unchecked("original_owner");
+    let bidder = Addr::unchecked("bidder");
+
+    let nft = create_cw721(&mut app, &cw721_owner);
+    let swap = create_swap::<M>(&mut app, &swap_admin, nft.clone());
+    mint_and_approve(&mut app, &swap, &nft, &cw721_owner, "token1");
+    let old_token = create_cw20(
+        &mut app,
+        &bidder,
+        "testcw".to_string(),
+        "tscw".to_string(),
+        Uint128::from(10000_u32),
+    );
+    let new_token = create_cw20(
+        &mut app,
+        &bidder,
+        "othercw".to_string(),
+        "ocw".to_string(),
+        Uint128::from(10000_u32),
+    );
+
+    let create = |id: &str, payment_token: Option<&Addr>, swap_type: SwapType| {
+        ExecuteMsg::Create(SwapMsg {
+            id: Some(id.to_string()),
+            cw721: nft.clone(),
+            payment_token: payment_token.cloned(),
+            token_id: "token1".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(100_u32),
+            swap_type,
+        })
+    };
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &create("sale", None, SwapType::Sale),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        bidder.clone(),
+        swap.clone(),
+        &create("offer", Some(&old_token), SwapType::Offer),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        cw721_owner.clone(),
+        swap.clone(),
+        &ExecuteMsg::Counter(CounterMsg {
+            id: Some("counter".to_string()),
+            offer_id: "offer".to_string(),
+            expires: Expiration::AtHeight(384798573487439743),
+            price: Uint128::from(150_u32),
+        }),
+        &[],
+    )
+    .unwrap();
+
+    // Sales can be paid in a cw20 instead of the native denom
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &update_payment_token("sale", Some(&old_token), 50),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    let res = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &update_payment_token("sale", Some(&old_token), 50),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        update_attributes(
+            &res,
+            &["swap_id", "old_payment_token", "payment_token", "price"]
+        ),
+        vec!["sale", "aarch", old_token.as_str(), "50"]
+    );
+    let sale = details(&mut app, &swap, "sale");
+    assert_eq!(sale.payment_token, Some(old_token.clone()));
+    assert_eq!(sale.price, Uint128::from(50_u32));
+
+    // Counter offers are paid in the token of their offer
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &update_payment_token("counter", Some(&new_token), 150),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid input");
+
+    // Offers can't be paid in the native denom, nor above the allowance of their token
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &update_payment_token("offer", None, 200),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid payment token");
+    let err = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &update_payment_token("offer", Some(&new_token), 200),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("Payment failed on {new_token}: allowance of 0 is less than 200")
+    );
+    app.execute_contract(
+        bidder.clone(),
+        new_token.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: swap.to_string(),
+            amount: Uint128::from(200_u32),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            bidder.clone(),
+            swap.clone(),
+            &update_payment_token("offer", Some(&new_token), 200),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        update_attributes(&res, &["old_payment_token", "payment_token"]),
+        vec![old_token.as_str(), new_token.as_str()]
+    );
+    assert_eq!(
+        details(&mut app, &swap, "offer").payment_token,
+        Some(new_token.clone())
+    );
+    // Its counter offer was priced in the old token
+    assert_eq!(
+        details(&mut app, &swap, "counter").status,
+        SwapStatus::Invalidated
+    );
+
+    // Expired swaps can't be updated
+    app.update_block(|block| block.height = 384798573487439743);
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &update_payment_token("sale", None, 100),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Expired atomic swap");
+    let err = app
+        .execute_contract(
+            cw721_owner.clone(),
+            swap.clone(),
+            &ExecuteMsg::Update(UpdateMsg {
+                id: "sale".to_string(),
+                expires: Expiration::Never {},
+                price: Uint128::from(100_u32),
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Expired atomic swap");
+}
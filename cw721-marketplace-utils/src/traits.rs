@@ -0,0 +1,58 @@
+//! Token traits that trait offers are filled against; each collection
+//! `Extension` shape is read by its own `TraitReader`
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult};
+use cw721::{Cw721QueryMsg, NftInfoResponse};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// A trait of a token, e.g. `{"trait_type": "background", "value": "gold"}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Trait {
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// Reads the traits of a token from its collection
+pub trait TraitReader {
+    fn traits(
+        &self,
+        querier: &QuerierWrapper,
+        cw721: &Addr,
+        token_id: &str,
+    ) -> StdResult<Vec<Trait>>;
+}
+
+/// Traits stored in the `attributes` of cw721-metadata-onchain `Metadata`,
+/// any other metadata fields are ignored
+pub struct OnchainMetadata;
+
+#[derive(Deserialize)]
+struct Attributes {
+    #[serde(default)]
+    attributes: Option<Vec<Trait>>,
+}
+
+impl TraitReader for OnchainMetadata {
+    fn traits(
+        &self,
+        querier: &QuerierWrapper,
+        cw721: &Addr,
+        token_id: &str,
+    ) -> StdResult<Vec<Trait>> {
+        let res: NftInfoResponse<Option<Attributes>> = querier.query_wasm_smart(
+            cw721,
+            &Cw721QueryMsg::NftInfo {
+                token_id: token_id.to_string(),
+            },
+        )?;
+        Ok(res
+            .extension
+            .and_then(|metadata| metadata.attributes)
+            .unwrap_or_default())
+    }
+}
+
+/// Whether `traits` has every trait of `required`
+pub fn has_traits(traits: &[Trait], required: &[Trait]) -> bool {
+    required.iter().all(|t| traits.contains(t))
+}